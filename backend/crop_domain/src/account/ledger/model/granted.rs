@@ -0,0 +1,19 @@
+use super::Transaction;
+use crate::account::AccountId;
+
+/// Adminが付与したポイント
+#[must_use]
+pub struct Granted {
+    pub(super) account_id: AccountId,
+    pub transaction: Transaction,
+}
+
+impl Granted {
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn amount(&self) -> i64 {
+        self.transaction.amount_for(&self.account_id)
+    }
+}
@@ -0,0 +1,907 @@
+use crate::account::AccountId;
+use crate::admin::model::{Admin, AdminId};
+use crate::contest::{poll::PollId, ContestId};
+use chrono::{DateTime, Utc};
+use crop_infra::pg::{
+    point_entry::{PointEntryTable as _, QueriedEscrowEntry, QueriedWalletEntry},
+    point_transaction::{
+        NewPointEntry, NewPointTransaction, PointTransactionTable as _, Posting, QueriedPointEntry,
+        QueriedPollTransaction,
+    },
+    types::PointBook,
+    Connection,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+mod adjusted;
+mod granted;
+
+pub use adjusted::Adjusted;
+pub use granted::Granted;
+
+pub type TransactionKind = crop_infra::pg::types::PointTransactionKind;
+
+/// 冪等キーの最大の長さ
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 128;
+
+/// 仕訳の対象となる帳簿
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Book {
+    /// アカウントの残高
+    Wallet(AccountId),
+    /// ポイントの発行元。Adminが付与したポイントは、ここから払い出す。
+    Issuance,
+    /// アカウントがPollに賭けて、精算を待っているポイント。
+    /// どのPollに賭けたかは、取引の `poll_id` で分かる。
+    Escrow(AccountId),
+}
+
+/// 取引の1行。正なら帳簿の残高が増え、負なら減る。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    book: Book,
+    amount: i64,
+}
+
+/// ポイントの取引。
+/// 複数の仕訳からなり、仕訳の金額の合計は常に0になる。
+/// 同じ冪等キーの取引は一度しか記帳されないので、
+/// Resolveなどを再試行しても二重に記帳されることはない。
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    id: TransactionId,
+    idempotency_key: String,
+    kind: TransactionKind,
+    contest_id: Option<ContestId>,
+    poll_id: Option<PollId>,
+    admin_id: Option<AdminId>,
+    memo: Option<String>,
+    entries: Vec<Entry>,
+    created_at: DateTime<Utc>,
+}
+
+impl Transaction {
+    /// 仕訳の金額の合計が0になっていなければエラーになる
+    pub(crate) fn new(
+        kind: TransactionKind,
+        idempotency_key: String,
+        entries: Vec<Entry>,
+    ) -> anyhow::Result<Transaction> {
+        let idempotency_key = idempotency_key.trim().to_string();
+        if idempotency_key.is_empty() {
+            return Err(anyhow::anyhow!("Idempotency key is empty"));
+        }
+        if idempotency_key.chars().count() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(anyhow::anyhow!("Idempotency key is too long"));
+        }
+        if entries.is_empty() || entries.iter().any(|e| e.amount == 0) {
+            return Err(anyhow::anyhow!("Transaction has an empty entry"));
+        }
+        if entries.iter().map(|e| e.amount).sum::<i64>() != 0 {
+            return Err(anyhow::anyhow!("Transaction is not balanced"));
+        }
+
+        Ok(Transaction {
+            id: TransactionId::new(),
+            idempotency_key,
+            kind,
+            contest_id: None,
+            poll_id: None,
+            admin_id: None,
+            memo: None,
+            entries,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// `from` から `to` へポイントを移す取引
+    pub(crate) fn transfer(
+        kind: TransactionKind,
+        idempotency_key: String,
+        from: Book,
+        to: Book,
+        amount: i64,
+    ) -> anyhow::Result<Transaction> {
+        let entries = vec![
+            Entry {
+                book: from,
+                amount: -amount,
+            },
+            Entry { book: to, amount },
+        ];
+        Transaction::new(kind, idempotency_key, entries)
+    }
+
+    pub(crate) fn with_contest(mut self, contest_id: Option<ContestId>) -> Self {
+        self.contest_id = contest_id;
+        self
+    }
+
+    pub(crate) fn with_poll(mut self, poll_id: PollId) -> Self {
+        self.poll_id = Some(poll_id);
+        self
+    }
+
+    pub(crate) fn with_admin<A>(mut self, admin: &A) -> Self
+    where
+        A: Admin,
+    {
+        self.admin_id = Some(*admin.id());
+        self
+    }
+
+    pub(crate) fn with_memo(mut self, memo: Option<String>) -> Self {
+        self.memo = memo;
+        self
+    }
+
+    pub fn id(&self) -> &TransactionId {
+        &self.id
+    }
+
+    pub fn idempotency_key(&self) -> &str {
+        self.idempotency_key.as_str()
+    }
+
+    pub fn kind(&self) -> TransactionKind {
+        self.kind
+    }
+
+    pub fn contest_id(&self) -> Option<&ContestId> {
+        self.contest_id.as_ref()
+    }
+
+    pub fn poll_id(&self) -> Option<&PollId> {
+        self.poll_id.as_ref()
+    }
+
+    pub fn memo(&self) -> Option<&str> {
+        self.memo.as_deref()
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        self.entries.as_slice()
+    }
+
+    /// 種類と仕訳が同じ取引か。仕訳の順番は問わない。
+    fn matches(&self, kind: TransactionKind, entries: &[Entry]) -> bool {
+        self.kind == kind
+            && self.entries.len() == entries.len()
+            && self.entries.iter().all(|e| entries.contains(e))
+    }
+
+    /// この取引によるアカウントの残高の増減
+    pub fn amount_for(&self, account_id: &AccountId) -> i64 {
+        self.entries
+            .iter()
+            .filter(|e| e.book == Book::Wallet(*account_id))
+            .map(|e| e.amount)
+            .sum()
+    }
+}
+
+impl Entry {
+    fn from_queried(queried: QueriedPointEntry) -> anyhow::Result<Entry> {
+        let book = match (queried.book, queried.account_id) {
+            (PointBook::Wallet, Some(account_id)) => Book::Wallet(AccountId(account_id)),
+            (PointBook::Issuance, None) => Book::Issuance,
+            (PointBook::Escrow, Some(account_id)) => Book::Escrow(AccountId(account_id)),
+            _ => return Err(anyhow::anyhow!("Point entry has an invalid account")),
+        };
+        Ok(Entry {
+            book,
+            amount: queried.amount,
+        })
+    }
+
+    pub fn book(&self) -> Book {
+        self.book
+    }
+
+    pub fn amount(&self) -> i64 {
+        self.amount
+    }
+}
+
+/// 取引を記帳する。
+/// 同じ冪等キーの取引が既に記帳されていた場合は何もせず `false` を返す。
+/// 残高が負になるアカウントがある場合は `Overdrawn` エラーになる。
+pub(crate) fn post(conn: &Connection, transaction: &Transaction) -> anyhow::Result<bool> {
+    let new_transaction = NewPointTransaction {
+        id: &transaction.id.0,
+        idempotency_key: transaction.idempotency_key.as_str(),
+        kind: transaction.kind,
+        contest_id: transaction.contest_id.as_ref().map(|id| &id.0),
+        poll_id: transaction.poll_id.as_ref().map(|id| &id.0),
+        admin_id: transaction.admin_id.as_ref().map(|id| &id.0),
+        memo: transaction.memo.as_deref(),
+        created_at: &transaction.created_at,
+    };
+    let entries = transaction
+        .entries
+        .iter()
+        .map(|entry| {
+            let (book, account_id) = match &entry.book {
+                Book::Wallet(account_id) => (PointBook::Wallet, Some(&account_id.0)),
+                Book::Issuance => (PointBook::Issuance, None),
+                Book::Escrow(account_id) => (PointBook::Escrow, Some(&account_id.0)),
+            };
+            NewPointEntry {
+                transaction_id: &transaction.id.0,
+                book,
+                account_id,
+                amount: entry.amount,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    match conn.post(new_transaction, entries.as_slice())? {
+        Posting::Posted => Ok(true),
+        Posting::AlreadyPosted => Ok(false),
+        Posting::Overdrawn(account_id) => Err(Overdrawn(AccountId(account_id)).into()),
+    }
+}
+
+/// 同じ冪等キーで記帳済みの取引のID。
+/// 再試行されたリクエストに、記帳済みの取引を返すために使う。
+/// 記帳済みの取引と種類や仕訳が異なる場合は `KeyReused` エラーになる。
+pub(crate) fn query_posted(
+    conn: &Connection,
+    transaction: &Transaction,
+) -> anyhow::Result<Option<TransactionId>> {
+    let (posted, entries) = match conn.query_by_idempotency_key(&transaction.idempotency_key)? {
+        Some(posted) => posted,
+        None => return Ok(None),
+    };
+    let entries = entries
+        .into_iter()
+        .map(Entry::from_queried)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if !transaction.matches(posted.kind, &entries) {
+        return Err(KeyReused.into());
+    }
+    Ok(Some(TransactionId(posted.id)))
+}
+
+/*
+ * ==========
+ * Wallet
+ * ==========
+ */
+/// 表示する取引履歴の数
+const NUM_RECENT_ENTRIES: i64 = 100;
+
+/// アカウントのポイントの残高と、最近の取引
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Wallet {
+    account_id: AccountId,
+    balance: i64,
+    /// 新しいものから順に並ぶ
+    entries: Vec<WalletEntry>,
+}
+
+/// 取引による残高の増減
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WalletEntry {
+    transaction_id: TransactionId,
+    kind: TransactionKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contest_id: Option<ContestId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    poll_id: Option<PollId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memo: Option<String>,
+    amount: i64,
+    created_at: DateTime<Utc>,
+}
+
+impl Wallet {
+    pub(crate) fn query(conn: &Connection, account_id: &AccountId) -> anyhow::Result<Wallet> {
+        let balance = conn.balance_of(&account_id.0)?;
+        let entries = conn
+            .query_by_account_id(&account_id.0, NUM_RECENT_ENTRIES)?
+            .into_iter()
+            .map(WalletEntry::from)
+            .collect();
+        Ok(Wallet {
+            account_id: *account_id,
+            balance,
+            entries,
+        })
+    }
+
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn balance(&self) -> i64 {
+        self.balance
+    }
+
+    pub fn entries(&self) -> &[WalletEntry] {
+        self.entries.as_slice()
+    }
+
+    /// Adminがポイントを付与する。
+    /// Contestの賞品として付与する場合は、そのContestを指定する。
+    pub fn grant<A>(
+        &self,
+        admin: &A,
+        amount: i64,
+        idempotency_key: String,
+        contest_id: Option<ContestId>,
+        memo: Option<String>,
+    ) -> anyhow::Result<Granted>
+    where
+        A: Admin,
+    {
+        if amount <= 0 {
+            return Err(anyhow::anyhow!("Amount must be positive"));
+        }
+        let transaction = Transaction::transfer(
+            TransactionKind::Grant,
+            idempotency_key,
+            Book::Issuance,
+            Book::Wallet(self.account_id),
+            amount,
+        )?
+        .with_contest(contest_id)
+        .with_admin(admin)
+        .with_memo(memo);
+        Ok(Granted {
+            account_id: self.account_id,
+            transaction,
+        })
+    }
+
+    /// Adminが残高を調整する。負の値を指定すると残高が減る。
+    /// 後から理由が分かるよう、メモが必要。
+    pub fn adjust<A>(
+        &self,
+        admin: &A,
+        amount: i64,
+        idempotency_key: String,
+        memo: String,
+    ) -> anyhow::Result<Adjusted>
+    where
+        A: Admin,
+    {
+        if amount == 0 {
+            return Err(anyhow::anyhow!("Amount is zero"));
+        }
+        if memo.trim().is_empty() {
+            return Err(anyhow::anyhow!("Memo is required for adjustment"));
+        }
+        if self.balance + amount < 0 {
+            return Err(Overdrawn(self.account_id).into());
+        }
+        let transaction = Transaction::transfer(
+            TransactionKind::Adjustment,
+            idempotency_key,
+            Book::Issuance,
+            Book::Wallet(self.account_id),
+            amount,
+        )?
+        .with_admin(admin)
+        .with_memo(Some(memo));
+        Ok(Adjusted {
+            account_id: self.account_id,
+            transaction,
+        })
+    }
+}
+
+/*
+ * ==========
+ * Escrow
+ * ==========
+ */
+/// Pollに賭けられたポイント
+#[derive(Debug, Clone)]
+pub(crate) struct Escrow {
+    poll_id: PollId,
+    /// アカウントごとの、預かっているポイント
+    amounts: HashMap<AccountId, i64>,
+    /// アカウントごとの、これまでに記帳した仕訳の数
+    num_entries: HashMap<AccountId, usize>,
+}
+
+impl Escrow {
+    pub(crate) fn query(conn: &Connection, poll_id: &PollId) -> anyhow::Result<Escrow> {
+        let mut escrow = Escrow {
+            poll_id: *poll_id,
+            amounts: HashMap::new(),
+            num_entries: HashMap::new(),
+        };
+        for QueriedEscrowEntry { account_id, amount } in conn.query_escrow_by_poll_id(&poll_id.0)? {
+            if let Some(account_id) = account_id {
+                *escrow.amounts.entry(AccountId(account_id)).or_default() += amount;
+                *escrow.num_entries.entry(AccountId(account_id)).or_default() += 1;
+            }
+        }
+        escrow.amounts.retain(|_, amount| *amount != 0);
+        Ok(escrow)
+    }
+
+    pub(crate) fn amounts(&self) -> &HashMap<AccountId, i64> {
+        &self.amounts
+    }
+
+    /// 賭けるポイントを `stake` に変更する取引。変更が無ければ `None` 。
+    ///
+    /// 冪等キーには記帳済みの仕訳の数を含めるので、
+    /// 同じ状態から同時に変更しようとした場合は、片方だけが記帳される。
+    pub(crate) fn restake(
+        &self,
+        account_id: &AccountId,
+        stake: i64,
+    ) -> anyhow::Result<Option<Transaction>> {
+        let current = self.amounts.get(account_id).copied().unwrap_or(0);
+        if stake < 0 {
+            return Err(anyhow::anyhow!("Stake must not be negative"));
+        } else if stake == current {
+            return Ok(None);
+        }
+        let key = format!(
+            "stake:{}:{}:{}",
+            self.poll_id.0,
+            account_id.0,
+            self.num_entries.get(account_id).copied().unwrap_or(0)
+        );
+        let transaction = Transaction::transfer(
+            TransactionKind::Stake,
+            key,
+            Book::Wallet(*account_id),
+            Book::Escrow(*account_id),
+            stake - current,
+        )?
+        .with_poll(self.poll_id);
+        Ok(Some(transaction))
+    }
+
+    /// Resolveしたときの精算の取引。
+    /// `payouts` に含まれないアカウントの賭けたポイントは、発行元に戻す。
+    pub(crate) fn settle(
+        &self,
+        payouts: &HashMap<AccountId, i64>,
+        settlement: &Settlement,
+    ) -> Vec<Transaction> {
+        self.amounts
+            .iter()
+            .filter(|(_, amount)| **amount > 0)
+            .filter_map(|(account_id, amount)| {
+                let key = settlement.key(format!("payout:{}:{}", self.poll_id.0, account_id.0))?;
+                let payout = payouts.get(account_id).copied().unwrap_or(0);
+                let mut entries = vec![
+                    Entry {
+                        book: Book::Escrow(*account_id),
+                        amount: -amount,
+                    },
+                    Entry {
+                        book: Book::Wallet(*account_id),
+                        amount: payout,
+                    },
+                    Entry {
+                        book: Book::Issuance,
+                        amount: amount - payout,
+                    },
+                ];
+                entries.retain(|e| e.amount != 0);
+                // 金額の合計は必ず0になる
+                let transaction = Transaction::new(TransactionKind::Payout, key, entries).unwrap();
+                Some(transaction.with_poll(self.poll_id))
+            })
+            .collect()
+    }
+
+    /// 中止したときの払い戻しの取引
+    pub(crate) fn refund(&self, settlement: &Settlement) -> Vec<Transaction> {
+        self.amounts
+            .iter()
+            .filter(|(_, amount)| **amount > 0)
+            .filter_map(|(account_id, amount)| {
+                let key = settlement.key(format!("refund:{}:{}", self.poll_id.0, account_id.0))?;
+                let transaction = Transaction::transfer(
+                    TransactionKind::Refund,
+                    key,
+                    Book::Escrow(*account_id),
+                    Book::Wallet(*account_id),
+                    *amount,
+                )
+                .unwrap();
+                Some(transaction.with_poll(self.poll_id))
+            })
+            .collect()
+    }
+}
+
+/*
+ * ==========
+ * Settlement
+ * ==========
+ */
+/// Pollの精算で記帳した、払い戻しや返金の取引。
+/// 正解を訂正したときに、記帳済みの精算を取り消して精算し直すために使う。
+#[derive(Debug, Clone)]
+pub(crate) struct Settlement {
+    poll_id: PollId,
+    /// 取り消した取引も含む
+    settled: Vec<SettledTransaction>,
+}
+
+#[derive(Debug, Clone)]
+struct SettledTransaction {
+    id: TransactionId,
+    idempotency_key: String,
+    entries: Vec<Entry>,
+    is_reversed: bool,
+}
+
+impl Settlement {
+    pub(crate) fn query(conn: &Connection, poll_id: &PollId) -> anyhow::Result<Settlement> {
+        let posted = conn.query_by_poll_id(&poll_id.0)?;
+        let reversed = posted
+            .iter()
+            .filter(|(t, _)| t.kind == TransactionKind::Reversal)
+            .map(|(t, _)| t.idempotency_key.clone())
+            .collect::<HashSet<_>>();
+        let settled = posted
+            .into_iter()
+            .filter(|(t, _)| matches!(t.kind, TransactionKind::Payout | TransactionKind::Refund))
+            .map(
+                |(
+                    QueriedPollTransaction {
+                        id,
+                        idempotency_key,
+                        ..
+                    },
+                    entries,
+                )| {
+                    let id = TransactionId(id);
+                    Ok(SettledTransaction {
+                        is_reversed: reversed.contains(&reversal_key(&id)),
+                        id,
+                        idempotency_key,
+                        entries: entries
+                            .into_iter()
+                            .map(Entry::from_queried)
+                            .collect::<anyhow::Result<_>>()?,
+                    })
+                },
+            )
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Settlement {
+            poll_id: *poll_id,
+            settled,
+        })
+    }
+
+    /// 精算の取引の冪等キー。
+    /// 精算し直す場合は、それまでに精算した回数を付けて、取り消した精算と区別する。
+    /// 取り消していない精算が残っていれば、精算済みなので `None` 。
+    pub(crate) fn key(&self, base: String) -> Option<String> {
+        let prefix = format!("{}:", base);
+        let settled = self
+            .settled
+            .iter()
+            .filter(|t| t.idempotency_key == base || t.idempotency_key.starts_with(&prefix))
+            .collect::<Vec<_>>();
+        if settled.iter().any(|t| !t.is_reversed) {
+            None
+        } else if settled.is_empty() {
+            Some(base)
+        } else {
+            Some(format!("{}{}", prefix, settled.len()))
+        }
+    }
+
+    /// 取り消していない精算を、全て取り消す取引。
+    /// 元の取引の仕訳の符号を反転するので、賭けたポイントは預かった状態に戻る。
+    pub(crate) fn reverse(&self) -> Vec<Transaction> {
+        self.settled
+            .iter()
+            .filter(|t| !t.is_reversed)
+            .map(|t| {
+                let entries = t
+                    .entries
+                    .iter()
+                    .map(|e| Entry {
+                        book: e.book,
+                        amount: -e.amount,
+                    })
+                    .collect();
+                // 記帳済みの取引を反転するので、金額の合計は必ず0になる
+                Transaction::new(TransactionKind::Reversal, reversal_key(&t.id), entries)
+                    .unwrap()
+                    .with_poll(self.poll_id)
+            })
+            .collect()
+    }
+}
+
+/// 取引ごとに一度しか取り消さないよう、元の取引のIDから決める
+fn reversal_key(id: &TransactionId) -> String {
+    format!("reversal:{}", id.0)
+}
+
+impl From<QueriedWalletEntry> for WalletEntry {
+    fn from(queried: QueriedWalletEntry) -> WalletEntry {
+        WalletEntry {
+            transaction_id: TransactionId(queried.transaction_id),
+            kind: queried.kind,
+            contest_id: queried.contest_id.map(ContestId),
+            poll_id: queried.poll_id.map(PollId),
+            memo: queried.memo,
+            amount: queried.amount,
+            created_at: queried.created_at,
+        }
+    }
+}
+
+/// アカウントの残高が足りない
+#[derive(Debug)]
+pub struct Overdrawn(pub AccountId);
+
+impl std::fmt::Display for Overdrawn {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Not enough points : {}", (self.0).0)
+    }
+}
+
+impl std::error::Error for Overdrawn {}
+
+/// 冪等キーが、内容の異なる取引に既に使われていた
+#[derive(Debug)]
+pub struct KeyReused;
+
+impl std::fmt::Display for KeyReused {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Idempotency key is already used for another transaction")
+    }
+}
+
+impl std::error::Error for KeyReused {}
+
+/// 同時に行われた他の変更と競合した
+#[derive(Debug)]
+pub struct Conflict;
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Conflicted with another transaction")
+    }
+}
+
+impl std::error::Error for Conflict {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct TransactionId(pub Uuid);
+
+impl TransactionId {
+    pub fn new() -> TransactionId {
+        TransactionId(Uuid::new_v4())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::model::Role;
+
+    struct TestAdmin(AdminId);
+
+    impl Admin for TestAdmin {
+        fn id(&self) -> &AdminId {
+            &self.0
+        }
+
+        fn role(&self) -> Role {
+            Role::Superadmin
+        }
+    }
+
+    /// 記帳した取引を、Pollの精算として反映する
+    fn post(settlement: &mut Settlement, transactions: &[Transaction]) {
+        for transaction in transactions.iter() {
+            match transaction.kind() {
+                TransactionKind::Reversal => {
+                    for settled in settlement.settled.iter_mut() {
+                        if reversal_key(&settled.id) == transaction.idempotency_key() {
+                            settled.is_reversed = true;
+                        }
+                    }
+                }
+                _ => settlement.settled.push(SettledTransaction {
+                    id: *transaction.id(),
+                    idempotency_key: transaction.idempotency_key().to_string(),
+                    entries: transaction.entries().to_vec(),
+                    is_reversed: false,
+                }),
+            }
+        }
+    }
+
+    fn wallet(balance: i64) -> Wallet {
+        Wallet {
+            account_id: AccountId(Uuid::new_v4()),
+            balance,
+            entries: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn transaction_must_balance() {
+        let account_id = AccountId(Uuid::new_v4());
+        let entries = vec![
+            Entry {
+                book: Book::Issuance,
+                amount: -10,
+            },
+            Entry {
+                book: Book::Wallet(account_id),
+                amount: 9,
+            },
+        ];
+        assert!(Transaction::new(TransactionKind::Grant, "key".to_string(), entries).is_err());
+
+        let transfer = Transaction::transfer(
+            TransactionKind::Grant,
+            "key".to_string(),
+            Book::Issuance,
+            Book::Wallet(account_id),
+            10,
+        )
+        .unwrap();
+        assert_eq!(transfer.amount_for(&account_id), 10);
+    }
+
+    #[test]
+    fn adjustment_can_not_overdraw() {
+        let admin = TestAdmin(AdminId::new());
+        let wallet = wallet(30);
+        let memo = || "correction".to_string();
+
+        assert!(wallet.adjust(&admin, -30, "a".to_string(), memo()).is_ok());
+        let err = wallet
+            .adjust(&admin, -31, "b".to_string(), memo())
+            .err()
+            .unwrap();
+        assert!(err.is::<Overdrawn>());
+        assert!(wallet
+            .adjust(&admin, 5, "c".to_string(), String::new())
+            .is_err());
+    }
+
+    #[test]
+    fn replayed_transaction_matches_only_same_entries() {
+        let admin = TestAdmin(AdminId::new());
+        let own = wallet(0);
+        let granted = own
+            .grant(&admin, 10, "a".to_string(), None, None)
+            .unwrap()
+            .transaction;
+        let mut reversed = granted.entries().to_vec();
+        reversed.reverse();
+        assert!(granted.matches(TransactionKind::Grant, &reversed));
+        assert!(!granted.matches(TransactionKind::Adjustment, &reversed));
+
+        let other_amount = own
+            .grant(&admin, 20, "a".to_string(), None, None)
+            .unwrap()
+            .transaction;
+        assert!(!granted.matches(TransactionKind::Grant, other_amount.entries()));
+        let other_account = wallet(0)
+            .grant(&admin, 10, "a".to_string(), None, None)
+            .unwrap()
+            .transaction;
+        assert!(!granted.matches(TransactionKind::Grant, other_account.entries()));
+    }
+
+    #[test]
+    fn grant_requires_idempotency_key() {
+        let admin = TestAdmin(AdminId::new());
+        let wallet = wallet(0);
+        assert!(wallet
+            .grant(&admin, 10, " ".to_string(), None, None)
+            .is_err());
+        assert!(wallet
+            .grant(&admin, 0, "key".to_string(), None, None)
+            .is_err());
+
+        let granted = wallet
+            .grant(&admin, 10, "key".to_string(), None, None)
+            .unwrap();
+        assert_eq!(granted.transaction.amount_for(wallet.account_id()), 10);
+    }
+
+    #[test]
+    fn escrow_restakes_and_settles() {
+        let winner = AccountId(Uuid::new_v4());
+        let loser = AccountId(Uuid::new_v4());
+        let escrow = Escrow {
+            poll_id: PollId(Uuid::new_v4()),
+            amounts: vec![(winner, 10), (loser, 20)].into_iter().collect(),
+            num_entries: vec![(winner, 1), (loser, 1)].into_iter().collect(),
+        };
+
+        assert!(escrow.restake(&winner, 10).unwrap().is_none());
+        let restaked = escrow.restake(&winner, 4).unwrap().unwrap();
+        assert_eq!(restaked.amount_for(&winner), 6);
+        assert!(restaked.idempotency_key().ends_with(":1"));
+
+        let payouts = vec![(winner, 25)].into_iter().collect();
+        let settlement = Settlement {
+            poll_id: escrow.poll_id,
+            settled: Vec::new(),
+        };
+        let settled = escrow.settle(&payouts, &settlement);
+        assert_eq!(settled.len(), 2);
+        for transaction in settled.iter() {
+            assert_eq!(
+                transaction.entries().iter().map(Entry::amount).sum::<i64>(),
+                0
+            );
+        }
+        let paid = |account_id| {
+            settled
+                .iter()
+                .map(|t| t.amount_for(account_id))
+                .sum::<i64>()
+        };
+        assert_eq!(paid(&winner), 25);
+        assert_eq!(paid(&loser), 0);
+    }
+
+    #[test]
+    fn re_settlement_reverses_previous_payouts() {
+        let (alice, bob) = (AccountId(Uuid::new_v4()), AccountId(Uuid::new_v4()));
+        let poll_id = PollId(Uuid::new_v4());
+        let mut escrow = Escrow {
+            poll_id,
+            amounts: vec![(alice, 10), (bob, 20)].into_iter().collect(),
+            num_entries: vec![(alice, 1), (bob, 1)].into_iter().collect(),
+        };
+        let mut settlement = Settlement {
+            poll_id,
+            settled: Vec::new(),
+        };
+        let mut posted = Vec::new();
+        let mut post_all = |settlement: &mut Settlement, transactions: Vec<Transaction>| {
+            post(settlement, &transactions);
+            posted.extend(transactions);
+        };
+
+        // 訂正前の正解では、aliceだけが当たっていた
+        let alice_wins = vec![(alice, 25)].into_iter().collect();
+        let settled = escrow.settle(&alice_wins, &settlement);
+        post_all(&mut settlement, settled);
+        assert!(escrow.settle(&alice_wins, &settlement).is_empty());
+
+        // 取り消すと、賭けたポイントは預かった状態に戻る
+        let reversals = settlement.reverse();
+        assert_eq!(reversals.len(), 2);
+        assert_eq!(
+            reversals.iter().map(|t| t.amount_for(&alice)).sum::<i64>(),
+            -25
+        );
+        post_all(&mut settlement, reversals);
+        assert!(settlement.reverse().is_empty());
+
+        // 訂正後の正解で、bobに払い戻す
+        let bob_wins = vec![(bob, 50)].into_iter().collect();
+        escrow.num_entries = vec![(alice, 3), (bob, 3)].into_iter().collect();
+        let resettled = escrow.settle(&bob_wins, &settlement);
+        assert!(resettled
+            .iter()
+            .all(|t| t.idempotency_key().ends_with(":1")));
+        post_all(&mut settlement, resettled);
+
+        let paid = |account_id| posted.iter().map(|t| t.amount_for(account_id)).sum::<i64>();
+        assert_eq!(paid(&alice), 0);
+        assert_eq!(paid(&bob), 50);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::account::{Account, AccountId, Updatable};
+use crate::admin::service::auth::Credentials;
+use crop_infra::pg::{
+    account_credential::{AccountCredentialTable, NewAccountCredential},
+    Connection,
+};
+
+#[must_use]
+pub struct CredentialsLinked<A> {
+    pub(super) account: A,
+    pub(super) email: String,
+    pub(super) credentials: Credentials,
+}
+
+impl<A> CredentialsLinked<A> {
+    pub fn email(&self) -> &str {
+        self.email.as_str()
+    }
+}
+
+impl<A> Account for CredentialsLinked<A>
+where
+    A: Account,
+{
+    fn id(&self) -> &AccountId {
+        self.account.id()
+    }
+
+    fn linked_email(&self) -> Option<&str> {
+        Some(self.email())
+    }
+}
+
+impl<A> Updatable for CredentialsLinked<A>
+where
+    A: Account,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let new_credential = NewAccountCredential {
+            account_id: self.id(),
+            email: self.email(),
+            cred: &self.credentials.cred[..],
+            salt: &self.credentials.salt[..],
+        };
+        AccountCredentialTable::save(conn, new_credential)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::admin::service::auth::derive_credentials;
 use chrono::{DateTime, Duration, NaiveDateTime, Utc};
 use crop_infra::jwt;
 use derive_more::Deref;
@@ -7,11 +8,15 @@ use uuid::Uuid;
 
 mod authenticated;
 mod brief;
+mod credentials_linked;
 mod new;
+mod unauthenticated;
 
 pub use authenticated::Authenticated;
 pub use brief::BriefAccount;
+pub use credentials_linked::CredentialsLinked;
 pub use new::New;
+pub use unauthenticated::Unauthenticated;
 
 pub fn new(name: String) -> New {
     New::new(name)
@@ -30,6 +35,65 @@ pub trait Account {
     {
         self._name()
     }
+
+    /// このモデルが既に紐付けたメールアドレス
+    fn linked_email(&self) -> Option<&str> {
+        None
+    }
+
+    /// メールアドレスとパスワードをアカウントに紐付ける。
+    /// 紐付けておけば、別の端末からでもアカウントを復元できる。
+    /// 紐付けられるのは1度だけで、DBに紐付け済みかどうかは呼び出し側で確認する。
+    fn link_credentials(self, email: &str, pass: &str) -> anyhow::Result<CredentialsLinked<Self>>
+    where
+        Self: Sized,
+    {
+        if self.linked_email().is_some() {
+            return Err(AlreadyLinked.into());
+        }
+
+        let email = normalize_email(email);
+        if !is_valid_email(email.as_str()) {
+            return Err(anyhow::anyhow!("Invalid email"));
+        }
+
+        if pass.chars().count() < MIN_PASS_LEN {
+            return Err(anyhow::anyhow!("Password is too short"));
+        }
+
+        Ok(CredentialsLinked {
+            account: self,
+            email,
+            credentials: derive_credentials(pass),
+        })
+    }
+}
+
+/// 既にメールアドレスが紐付けられている
+#[derive(Debug)]
+pub struct AlreadyLinked;
+
+impl std::fmt::Display for AlreadyLinked {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Credentials are already linked")
+    }
+}
+
+impl std::error::Error for AlreadyLinked {}
+
+const MIN_PASS_LEN: usize = 8;
+
+/// 大文字と小文字の違いで、同じメールアドレスが別のアカウントに紐付かないようにする
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+fn is_valid_email(email: &str) -> bool {
+    let mut parts = email.splitn(2, '@');
+    match (parts.next(), parts.next()) {
+        (Some(local), Some(domain)) => !local.is_empty() && domain.contains('.'),
+        _ => false,
+    }
 }
 
 pub trait WithAttrs {
@@ -46,6 +110,14 @@ impl AccountId {
     }
 }
 
+impl std::str::FromStr for AccountId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(AccountId(Uuid::parse_str(s)?))
+    }
+}
+
 /*
  * ============
  * AccessToken
@@ -100,3 +172,63 @@ struct JwtClaim {
     account_id: AccountId,
     exp: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::service::auth::verify_credentials;
+
+    fn account() -> Authenticated {
+        Authenticated {
+            id: AccountId::new(),
+        }
+    }
+
+    #[test]
+    fn valid_emails() {
+        assert!(is_valid_email("user@example.com"));
+        assert!(is_valid_email("first.last+tag@sub.example.co.jp"));
+    }
+
+    #[test]
+    fn invalid_emails() {
+        assert!(!is_valid_email(""));
+        assert!(!is_valid_email("user"));
+        assert!(!is_valid_email("@example.com"));
+        assert!(!is_valid_email("user@localhost"));
+    }
+
+    #[test]
+    fn link_credentials_normalizes_email() {
+        let linked = account()
+            .link_credentials(" User@Example.COM ", "password")
+            .unwrap();
+        assert_eq!(linked.email(), "user@example.com");
+        verify_credentials(
+            &linked.credentials.salt[..],
+            &linked.credentials.cred[..],
+            "password",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn link_credentials_rejects_invalid_input() {
+        assert!(account().link_credentials("user", "password").is_err());
+        assert!(account()
+            .link_credentials("user@example.com", "short")
+            .is_err());
+    }
+
+    #[test]
+    fn link_credentials_twice_is_rejected() {
+        let linked = account()
+            .link_credentials("user@example.com", "password")
+            .unwrap();
+        let e = linked
+            .link_credentials("other@example.com", "password")
+            .err()
+            .unwrap();
+        assert!(e.is::<AlreadyLinked>());
+    }
+}
@@ -0,0 +1,17 @@
+use super::{AccountId, Authenticated};
+use crate::admin::service::auth::verify_credentials;
+
+/// メールアドレスから取得した、まだauthenticateされていないアカウント
+pub struct Unauthenticated {
+    pub(in crate::account) id: AccountId,
+    pub(in crate::account) cred: Vec<u8>,
+    pub(in crate::account) salt: Vec<u8>,
+}
+
+impl Unauthenticated {
+    pub fn authenticate(&self, pass: &str) -> anyhow::Result<Authenticated> {
+        verify_credentials(self.salt.as_slice(), self.cred.as_slice(), pass)?;
+
+        Ok(Authenticated { id: self.id })
+    }
+}
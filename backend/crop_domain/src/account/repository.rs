@@ -1,5 +1,8 @@
-use crate::account::AccountId;
-use crop_infra::pg::Connection;
+use crate::account::{
+    ledger::{self, Transaction, TransactionId, Wallet},
+    normalize_email, AccountId, Unauthenticated,
+};
+use crop_infra::pg::{account_credential::AccountCredentialTable, Connection};
 
 pub trait AccountRepository {
     fn conn(&self) -> &Connection;
@@ -17,6 +20,44 @@ pub trait AccountRepository {
     {
         A::query_by_id(self.conn(), id)
     }
+
+    /// メールアドレスが紐付けられたアカウントを取得する。
+    /// メールアドレスの大文字と小文字は区別しない。
+    fn query_unauthenticated(&self, email: &str) -> anyhow::Result<Option<Unauthenticated>> {
+        let email = normalize_email(email);
+        if let Some(queried) = AccountCredentialTable::query_by_email(self.conn(), email.as_str())?
+        {
+            Ok(Some(Unauthenticated {
+                id: AccountId(queried.account_id),
+                cred: queried.cred,
+                salt: queried.salt,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// ポイントの残高と、最近の取引を取得する
+    fn query_wallet(&self, id: &AccountId) -> anyhow::Result<Wallet> {
+        Wallet::query(self.conn(), id)
+    }
+
+    /// ポイントの取引を記帳する。
+    /// 同じ冪等キーの取引が既に記帳されていた場合は、何もせず `false` を返す。
+    fn post(&self, transaction: &Transaction) -> anyhow::Result<bool> {
+        ledger::post(self.conn(), transaction)
+    }
+
+    /// 同じ冪等キーで記帳済みの取引のIDを取得する。
+    /// 内容の異なる取引にキーが使われていた場合は `KeyReused` エラーになる。
+    fn query_posted(&self, transaction: &Transaction) -> anyhow::Result<Option<TransactionId>> {
+        ledger::query_posted(self.conn(), transaction)
+    }
+
+    /// 既にメールアドレスが紐付けられているかどうか
+    fn is_credentials_linked(&self, id: &AccountId) -> anyhow::Result<bool> {
+        AccountCredentialTable::exists_by_account_id(self.conn(), &id.0)
+    }
 }
 
 impl AccountRepository for Connection {
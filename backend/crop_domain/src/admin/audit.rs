@@ -0,0 +1,702 @@
+//! Adminによる状態変更操作の記録
+//!
+//! 誰が・いつ・何に対して・どのような変更を行ったかを残す。
+//! 各操作の結果となるモデル（`PollAdded` や `poll::Resolved` など）に
+//! `Audited` を実装し、`record` で記録を作成する。
+//! 片方だけが残らないよう、記録は対象の変更と同じトランザクション
+//! （`crop_infra::pg::transaction`）で保存する。
+use crate::account::ledger;
+use crate::admin::{
+    model::{Admin, AdminId},
+    repository::Updatable,
+};
+use crate::contest::{
+    self,
+    poll::{
+        self, AnswerCommitment, Choice, ChoiceName, NumericSpec, Poll, PollId, PollKind,
+        PollScoring, PollStatus,
+    },
+    Contest, ContestId, ContestStatus,
+};
+use crate::reward;
+use chrono::{DateTime, Utc};
+use crop_infra::pg::{
+    admin_audit_log::{
+        AdminAuditLogFilter, AdminAuditLogTable as _, NewAdminAuditLog, QueriedAdminAuditLog,
+    },
+    Connection,
+};
+use data_encoding::HEXLOWER;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+
+pub type Action = crop_infra::pg::types::AdminAction;
+
+/// 監査ログの記録対象となる操作
+pub trait Audited {
+    fn action(&self) -> Action;
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        None
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        None
+    }
+
+    fn before_state(&self) -> Option<Value>;
+
+    fn after_state(&self) -> Option<Value>;
+}
+
+pub fn record<A, T>(admin: &A, target: &T) -> Recorded
+where
+    A: Admin,
+    T: Audited,
+{
+    record_by(Some(admin.id()), target)
+}
+
+/// ログイン失敗のように、操作したAdminが特定できない場合もある
+pub fn record_by<T>(admin_id: Option<&AdminId>, target: &T) -> Recorded
+where
+    T: Audited,
+{
+    Recorded {
+        admin_id: admin_id.copied(),
+        action: target.action(),
+        contest_id: target.contest_id().copied(),
+        poll_id: target.poll_id().copied(),
+        before_state: target.before_state(),
+        after_state: target.after_state(),
+        created_at: Utc::now(),
+    }
+}
+
+#[must_use]
+pub struct Recorded {
+    admin_id: Option<AdminId>,
+    action: Action,
+    contest_id: Option<ContestId>,
+    poll_id: Option<PollId>,
+    before_state: Option<Value>,
+    after_state: Option<Value>,
+    created_at: DateTime<Utc>,
+}
+
+impl Recorded {
+    /// Pollは自身が属するContestを知らないので、
+    /// Pollに対する操作の場合はここでContestを指定する
+    pub fn contest(mut self, contest_id: &ContestId) -> Self {
+        self.contest_id = Some(*contest_id);
+        self
+    }
+}
+
+impl Updatable for Recorded {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let log = NewAdminAuditLog {
+            admin_id: self.admin_id.as_ref().map(|id| &id.0),
+            action: self.action,
+            contest_id: self.contest_id.as_ref().map(|id| &id.0),
+            poll_id: self.poll_id.as_ref().map(|id| &id.0),
+            before_state: self.before_state.as_ref(),
+            after_state: self.after_state.as_ref(),
+            created_at: &self.created_at,
+        };
+        conn.save(log)
+    }
+}
+
+/*
+ * ========
+ * Query
+ * ========
+ */
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 1000;
+
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    pub admin_id: Option<AdminId>,
+    pub action: Option<Action>,
+    pub contest_id: Option<ContestId>,
+    pub poll_id: Option<PollId>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AuditLog {
+    id: i32,
+    admin_id: Option<AdminId>,
+    action: Action,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contest_id: Option<ContestId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    poll_id: Option<PollId>,
+    before_state: Option<Value>,
+    after_state: Option<Value>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<QueriedAdminAuditLog> for AuditLog {
+    fn from(queried: QueriedAdminAuditLog) -> AuditLog {
+        AuditLog {
+            id: queried.id,
+            admin_id: queried.admin_id.map(AdminId),
+            action: queried.action,
+            contest_id: queried.contest_id.map(ContestId),
+            poll_id: queried.poll_id.map(PollId),
+            before_state: queried.before_state,
+            after_state: queried.after_state,
+            created_at: queried.created_at,
+        }
+    }
+}
+
+pub(crate) fn query(conn: &Connection, filter: &Filter) -> anyhow::Result<Vec<AuditLog>> {
+    let limit = filter.limit.unwrap_or(DEFAULT_LIMIT).max(1).min(MAX_LIMIT);
+    let table_filter = AdminAuditLogFilter {
+        admin_id: filter.admin_id.as_ref().map(|id| &id.0),
+        action: filter.action,
+        contest_id: filter.contest_id.as_ref().map(|id| &id.0),
+        poll_id: filter.poll_id.as_ref().map(|id| &id.0),
+        since: filter.since.as_ref(),
+        until: filter.until.as_ref(),
+        limit,
+    };
+    Ok(conn
+        .query(table_filter)?
+        .into_iter()
+        .map(AuditLog::from)
+        .collect())
+}
+
+/*
+ * ==========
+ * State
+ * ==========
+ */
+#[derive(Serialize)]
+struct ContestState {
+    status: ContestStatus,
+}
+
+#[derive(Serialize)]
+struct PollState<'a> {
+    status: PollStatus,
+    kind: PollKind,
+    scoring: PollScoring,
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_sec: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lock_before_close_sec: Option<i64>,
+    choices: &'a [Choice],
+    resolved_choices: Option<&'a [ChoiceName]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    numeric: Option<&'a NumericSpec>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ranking_size: Option<usize>,
+    is_scored: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stake_multiplier: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer_commitment: Option<&'a AnswerCommitment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paused_at: Option<&'a DateTime<Utc>>,
+}
+
+impl<'a> PollState<'a> {
+    fn of<P>(poll: &'a P) -> PollState<'a>
+    where
+        P: poll::WithAttrs,
+    {
+        PollState {
+            status: poll.status(),
+            kind: poll.kind(),
+            scoring: poll.scoring(),
+            title: poll.title(),
+            duration_sec: poll.duration().map(|d| d.num_seconds()),
+            lock_before_close_sec: poll.lock_before_close().map(|d| d.num_seconds()),
+            choices: poll.choices(),
+            resolved_choices: poll.resolved_choices(),
+            numeric: poll.numeric(),
+            ranking_size: poll.ranking_size(),
+            is_scored: poll.is_scored(),
+            stake_multiplier: poll.stake_multiplier(),
+            resolved_value: poll.resolved_value(),
+            answer_commitment: poll.answer_commitment(),
+            paused_at: poll.paused_at(),
+        }
+    }
+}
+
+fn to_value<S: Serialize>(state: S) -> Option<Value> {
+    Some(serde_json::to_value(state).unwrap())
+}
+
+impl Audited for contest::New {
+    fn action(&self) -> Action {
+        Action::CreateContest
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        Some(self.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(serde_json::json!({
+            "status": self.status(),
+            "title": self.title(),
+            "category": self.category(),
+            "event_start_at": self.event_start_at(),
+        }))
+    }
+}
+
+/// 台本の内容はPollの下書きも含めて残す
+impl Audited for contest::script::Imported {
+    fn action(&self) -> Action {
+        Action::ImportContest
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        Some(self.contest.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        let drafts = self
+            .drafts
+            .iter()
+            .map(|draft| {
+                serde_json::json!({
+                    "title": draft.title(),
+                    "duration_sec": draft.duration().map(|d| d.num_seconds()),
+                    "choices": draft.choices(),
+                })
+            })
+            .collect::<Vec<_>>();
+        to_value(serde_json::json!({
+            "status": self.contest.status(),
+            "title": self.contest.title(),
+            "category": self.contest.category(),
+            "event_start_at": self.contest.event_start_at(),
+            "drafts": drafts,
+        }))
+    }
+}
+
+impl<C> Audited for contest::PollAdded<C>
+where
+    C: Contest,
+{
+    fn action(&self) -> Action {
+        Action::AddPoll
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        Some(self.contest.id())
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.poll.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(PollState::of(&self.poll))
+    }
+}
+
+impl<C> Audited for contest::Opened<C>
+where
+    C: contest::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::OpenContest
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        Some(self.contest.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(ContestState {
+            status: self.contest.status(),
+        })
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(ContestState {
+            status: ContestStatus::Open,
+        })
+    }
+}
+
+impl<C> Audited for contest::Closed<C>
+where
+    C: contest::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::CloseContest
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        Some(self.contest.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(ContestState {
+            status: self.contest.status(),
+        })
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(ContestState {
+            status: ContestStatus::Closed,
+        })
+    }
+}
+
+impl<C> Audited for contest::Archived<C>
+where
+    C: contest::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::ArchiveContest
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        Some(self.contest.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(ContestState {
+            status: self.contest.status(),
+        })
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(ContestState {
+            status: ContestStatus::Archived,
+        })
+    }
+}
+
+impl<P> Audited for poll::Closed<P>
+where
+    P: poll::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::ClosePoll
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(PollState::of(&self.poll))
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(PollState::of(self))
+    }
+}
+
+impl<P> Audited for poll::Resolved<P>
+where
+    P: poll::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::ResolvePoll
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(PollState::of(&self.poll))
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(PollState::of(self))
+    }
+}
+
+impl<P> Audited for poll::Reopened<P>
+where
+    P: poll::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::ReopenPoll
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(PollState::of(&self.poll))
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(PollState::of(self))
+    }
+}
+
+impl<P> Audited for poll::Extended<P>
+where
+    P: poll::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::ExtendPoll
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(PollState::of(&self.poll))
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(PollState::of(self))
+    }
+}
+
+impl<P> Audited for poll::Paused<P>
+where
+    P: poll::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::PausePoll
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(PollState::of(&self.poll))
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(PollState::of(self))
+    }
+}
+
+impl<P> Audited for poll::Resumed<P>
+where
+    P: poll::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::ResumePoll
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(PollState::of(&self.poll))
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(PollState::of(self))
+    }
+}
+
+/// 訂正前の正解はbefore_stateに残る
+impl<P> Audited for poll::ReResolved<P>
+where
+    P: poll::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::ReResolvePoll
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(PollState::of(&self.poll))
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(PollState::of(self))
+    }
+}
+
+impl<P> Audited for poll::Voided<P>
+where
+    P: poll::WithAttrs,
+{
+    fn action(&self) -> Action {
+        Action::VoidPoll
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        to_value(PollState::of(&self.poll))
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(PollState::of(self))
+    }
+}
+
+impl Audited for poll::market::Opened {
+    fn action(&self) -> Action {
+        Action::OpenMarket
+    }
+
+    fn poll_id(&self) -> Option<&PollId> {
+        Some(self.market.poll_id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(serde_json::json!({
+            "liquidity": self.market.liquidity(),
+            "budget": self.market.budget(),
+        }))
+    }
+}
+
+impl Audited for reward::New {
+    fn action(&self) -> Action {
+        Action::CreateReward
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        Some(self.contest_id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(serde_json::json!({
+            "reward_id": self.id(),
+            "name": self.name(),
+            "quantity": self.quantity(),
+            "selection": self.selection(),
+        }))
+    }
+}
+
+/// seedのハッシュ値だけを残す。seed自体は当選者を選ぶまで秘密にしておく。
+impl Audited for reward::SeedCommitted {
+    fn action(&self) -> Action {
+        Action::CommitRewardSeed
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        Some(self.contest_id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(serde_json::json!({
+            "reward_id": self.reward_id(),
+            "seed_hash": HEXLOWER.encode(self.seed_hash()),
+        }))
+    }
+}
+
+/// 抽選を後から再現できるよう、公開されたseedも残す
+impl Audited for reward::Drawn {
+    fn action(&self) -> Action {
+        Action::DrawReward
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        Some(self.contest_id())
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(serde_json::json!({
+            "reward_id": self.reward_id(),
+            "selection": self.selection(),
+            "seed": self.seed().map(|seed| HEXLOWER.encode(seed)),
+            "winners": self.winners().collect::<Vec<_>>(),
+        }))
+    }
+}
+
+impl Audited for ledger::Granted {
+    fn action(&self) -> Action {
+        Action::GrantPoints
+    }
+
+    fn contest_id(&self) -> Option<&ContestId> {
+        self.transaction.contest_id()
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(serde_json::json!({
+            "transaction_id": self.transaction.id(),
+            "account_id": self.account_id(),
+            "amount": self.amount(),
+            "memo": self.transaction.memo(),
+        }))
+    }
+}
+
+impl Audited for ledger::Adjusted {
+    fn action(&self) -> Action {
+        Action::AdjustPoints
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        to_value(serde_json::json!({
+            "transaction_id": self.transaction.id(),
+            "account_id": self.account_id(),
+            "amount": self.amount(),
+            "memo": self.transaction.memo(),
+        }))
+    }
+}
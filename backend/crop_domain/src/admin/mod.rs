@@ -1,3 +1,5 @@
+pub mod audit;
 pub mod model;
 pub mod repository;
 pub mod service;
+pub mod throttle;
@@ -0,0 +1,36 @@
+use super::{AccessToken, Admin, AdminId, Role, Scope};
+
+/*
+ * ==================
+ * AuthenticatedAdmin
+ * ==================
+ */
+pub struct AuthenticatedAdmin {
+    pub id: AdminId,
+    pub role: Role,
+    pub scope: Scope,
+}
+
+impl Admin for AuthenticatedAdmin {
+    fn id(&self) -> &AdminId {
+        &self.id
+    }
+
+    fn role(&self) -> Role {
+        self.role
+    }
+
+    fn scope(&self) -> Scope {
+        self.scope
+    }
+}
+
+impl From<AccessToken> for AuthenticatedAdmin {
+    fn from(token: AccessToken) -> AuthenticatedAdmin {
+        AuthenticatedAdmin {
+            id: token.admin_id,
+            role: token.role,
+            scope: token.scope,
+        }
+    }
+}
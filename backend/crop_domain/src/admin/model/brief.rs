@@ -0,0 +1,71 @@
+use super::{Admin, AdminId, Role, WithAttrs};
+use crate::admin::repository::{ListQueryable, Queryable};
+use crop_infra::pg::{
+    admin::{AdminTable as _, QueriedAdmin},
+    Connection,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BriefAdmin {
+    id: AdminId,
+    email: String,
+    role: Role,
+    disabled: bool,
+    totp_enabled: bool,
+    totp_required: bool,
+}
+
+impl Admin for BriefAdmin {
+    fn id(&self) -> &AdminId {
+        &self.id
+    }
+
+    fn role(&self) -> Role {
+        self.role
+    }
+}
+
+impl WithAttrs for BriefAdmin {
+    fn _email(&self) -> &str {
+        self.email.as_str()
+    }
+
+    fn _is_disabled(&self) -> bool {
+        self.disabled
+    }
+}
+
+impl From<QueriedAdmin> for BriefAdmin {
+    fn from(queried: QueriedAdmin) -> BriefAdmin {
+        BriefAdmin {
+            id: AdminId(queried.id),
+            email: queried.email,
+            role: queried.role,
+            disabled: queried.disabled,
+            totp_enabled: queried.totp_enabled,
+            totp_required: queried.totp_required,
+        }
+    }
+}
+
+impl Queryable for BriefAdmin {
+    fn query_by_id(conn: &Connection, id: &AdminId) -> anyhow::Result<Option<Self>> {
+        Ok(conn.query_by_id(&id.0)?.map(BriefAdmin::from))
+    }
+
+    fn query_by_email(conn: &Connection, email: &str) -> anyhow::Result<Option<Self>> {
+        Ok(conn.query_by_email(email)?.map(BriefAdmin::from))
+    }
+}
+
+impl ListQueryable for BriefAdmin {
+    fn query_all(conn: &Connection) -> anyhow::Result<Vec<Self>> {
+        Ok(conn
+            .query_all()?
+            .into_iter()
+            .map(BriefAdmin::from)
+            .collect())
+    }
+}
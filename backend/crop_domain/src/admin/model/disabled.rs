@@ -0,0 +1,30 @@
+use super::{Admin, AdminId, Role};
+use crate::admin::repository::Updatable;
+use crop_infra::pg::{admin::AdminTable as _, Connection};
+
+#[must_use]
+pub struct Disabled<A> {
+    pub(super) admin: A,
+}
+
+impl<A> Admin for Disabled<A>
+where
+    A: Admin,
+{
+    fn id(&self) -> &AdminId {
+        self.admin.id()
+    }
+
+    fn role(&self) -> Role {
+        self.admin.role()
+    }
+}
+
+impl<A> Updatable for Disabled<A>
+where
+    A: Admin,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        conn.update_disabled(&self.id().0, true)
+    }
+}
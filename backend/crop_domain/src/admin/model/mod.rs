@@ -0,0 +1,377 @@
+use crate::admin::service::{self, auth::derive_credentials};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use crop_infra::jwt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+mod authenticated;
+mod brief;
+mod disabled;
+mod new;
+mod password_reset;
+mod totp;
+
+pub use authenticated::AuthenticatedAdmin;
+pub use brief::BriefAdmin;
+pub use disabled::Disabled;
+pub use new::New;
+pub use password_reset::PasswordReset;
+pub use totp::{TotpAdmin, TotpEnabled, TotpEnrolling, TotpRequirementChanged, TotpReset};
+
+pub fn new(email: String, pass: &str, role: Role) -> anyhow::Result<New> {
+    if !email.contains('@') {
+        return Err(anyhow::anyhow!("Invalid email"));
+    }
+
+    validate_pass(pass)?;
+
+    Ok(New {
+        id: AdminId::new(),
+        email,
+        role,
+        credentials: derive_credentials(pass),
+    })
+}
+
+const MIN_PASS_LEN: usize = 12;
+
+fn validate_pass(pass: &str) -> anyhow::Result<()> {
+    if pass.chars().count() < MIN_PASS_LEN {
+        return Err(anyhow::anyhow!("Password is too short"));
+    }
+    Ok(())
+}
+
+/// Adminモデルを表現するトレイト
+/// このトレイトは最も基本的な要求しかしない
+pub trait Admin {
+    fn id(&self) -> &AdminId;
+
+    fn role(&self) -> Role;
+
+    fn scope(&self) -> Scope {
+        Scope::Full
+    }
+
+    fn has_permission(&self, permission: Permission) -> bool {
+        self.scope() == Scope::Full && permission.is_granted_to(self.role())
+    }
+
+    fn gen_access_token(&self) -> AccessToken {
+        AccessToken::new(self.id(), self.role(), self.scope())
+    }
+
+    fn email(&self) -> &str
+    where
+        Self: WithAttrs,
+    {
+        self._email()
+    }
+
+    fn is_disabled(&self) -> bool
+    where
+        Self: WithAttrs,
+    {
+        self._is_disabled()
+    }
+
+    /// Adminを無効化する。
+    /// 無効化されたAdminはログインできなくなる。
+    fn disable(self) -> anyhow::Result<Disabled<Self>>
+    where
+        Self: WithAttrs + Sized,
+    {
+        if self.is_disabled() {
+            return Err(anyhow::anyhow!("Admin is already disabled"));
+        }
+
+        Ok(Disabled { admin: self })
+    }
+
+    fn reset_password(self, pass: &str) -> anyhow::Result<PasswordReset<Self>>
+    where
+        Self: Sized,
+    {
+        validate_pass(pass)?;
+
+        Ok(PasswordReset {
+            admin: self,
+            credentials: derive_credentials(pass),
+        })
+    }
+
+    /// TOTPの登録を開始する。
+    /// 生成したsecretは、`enable_totp` で検証されるまで有効にならない。
+    fn start_totp_enrollment(self) -> anyhow::Result<TotpEnrolling<Self>>
+    where
+        Self: WithTotp + Sized,
+    {
+        if self._is_totp_enabled() {
+            return Err(anyhow::anyhow!("TOTP is already enabled"));
+        }
+
+        Ok(TotpEnrolling {
+            admin: self,
+            secret: service::totp::gen_secret(),
+        })
+    }
+
+    /// 認証アプリが生成したコードを検証し、TOTPを有効化する
+    fn enable_totp(self, code: &str) -> anyhow::Result<TotpEnabled<Self>>
+    where
+        Self: WithTotp + Sized,
+    {
+        if self._is_totp_enabled() {
+            return Err(anyhow::anyhow!("TOTP is already enabled"));
+        }
+        let secret = self
+            ._totp_secret()
+            .ok_or_else(|| anyhow::anyhow!("TOTP enrollment is not started"))?
+            .to_vec();
+        let step = service::totp::verify_code(secret.as_slice(), code, Utc::now())
+            .ok_or_else(|| anyhow::anyhow!("Invalid TOTP code"))?;
+
+        Ok(TotpEnabled {
+            admin: self,
+            secret,
+            step,
+            backup_codes: service::totp::gen_backup_codes(),
+        })
+    }
+
+    /// TOTPの登録を解除する。
+    /// 認証アプリを紛失した場合に使う。
+    fn reset_totp(self) -> TotpReset<Self>
+    where
+        Self: Sized,
+    {
+        TotpReset { admin: self }
+    }
+
+    fn require_totp(self, required: bool) -> TotpRequirementChanged<Self>
+    where
+        Self: Sized,
+    {
+        TotpRequirementChanged {
+            admin: self,
+            required,
+        }
+    }
+}
+
+pub trait WithAttrs: Admin {
+    fn _email(&self) -> &str;
+
+    fn _is_disabled(&self) -> bool;
+}
+
+pub trait WithTotp: Admin {
+    fn _totp_secret(&self) -> Option<&[u8]>;
+
+    fn _is_totp_enabled(&self) -> bool;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct AdminId(pub Uuid);
+
+impl AdminId {
+    pub fn new() -> AdminId {
+        AdminId(Uuid::new_v4())
+    }
+}
+
+impl std::str::FromStr for AdminId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(AdminId(Uuid::parse_str(s)?))
+    }
+}
+
+/*
+ * ===========
+ * Role
+ * ===========
+ */
+pub type Role = crop_infra::pg::types::AdminRole;
+
+/// Adminが行える操作。
+/// どの操作が許可されるかはRoleによって決まる。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    CreateContest,
+    OpenContest,
+    CloseContest,
+    ArchiveContest,
+    AddPoll,
+    /// 公開前のPollの下書きを編集する
+    ManageDrafts,
+    ClosePoll,
+    /// 誤ってCloseしたPollをOpenに戻す
+    ReopenPoll,
+    /// Pollの制限時間の延長や、タイマーの一時停止・再開
+    ControlPollTimer,
+    ResolvePoll,
+    /// 採点せずにPollを終了する
+    VoidPoll,
+    /// Resolve済みのPollの正解を訂正する。
+    /// 全員のスコアが変わるため、Superadminのみに許可する。
+    ReResolvePoll,
+    /// Pollにマーケットを開く
+    OpenMarket,
+    /// アカウントにポイントを付与し、残高を調整する。
+    /// ポイントを発行できるため、Superadminのみに許可する。
+    ManagePoints,
+    ModerateComment,
+    /// 終了したContestの結果を書き出す
+    ExportContest,
+    /// Contestの賞品を登録し、当選者を選んで送付する
+    ManageRewards,
+    /// DBの状態をWSのクライアントに再送する
+    SyncContest,
+    ManageAdmins,
+    ViewAuditLog,
+}
+
+impl Permission {
+    pub fn is_granted_to(self, role: Role) -> bool {
+        match role {
+            Role::Superadmin => true,
+            Role::Operator => !matches!(
+                self,
+                Permission::ManageAdmins
+                    | Permission::ViewAuditLog
+                    | Permission::ReResolvePoll
+                    | Permission::ManagePoints
+            ),
+            Role::Moderator => self == Permission::ModerateComment,
+        }
+    }
+}
+
+/*
+ * ===========
+ * AccessToken
+ * ===========
+ */
+const ACCESS_TOKEN_EXPIRE_DAYS: i64 = 30;
+
+/// AccessTokenで行える操作の範囲
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Scope {
+    Full,
+    /// TOTPが必須だが未登録のAdminに発行される。
+    /// TOTPの登録以外の操作は行えない。
+    TotpEnrollment,
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Scope::Full
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AccessToken {
+    pub admin_id: AdminId,
+    pub role: Role,
+    pub scope: Scope,
+    pub expire_at: DateTime<Utc>,
+}
+
+impl AccessToken {
+    pub fn new(admin_id: &AdminId, role: Role, scope: Scope) -> AccessToken {
+        AccessToken {
+            admin_id: *admin_id,
+            role,
+            scope,
+            expire_at: Utc::now() + Duration::days(ACCESS_TOKEN_EXPIRE_DAYS),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let claim = JwtClaim {
+            admin_id: self.admin_id,
+            role: self.role,
+            scope: self.scope,
+            exp: self.expire_at.timestamp() as usize,
+        };
+        jwt::encode(&claim).unwrap()
+    }
+
+    pub fn decode(raw: &str) -> anyhow::Result<Self> {
+        let claim = jwt::decode::<JwtClaim>(raw)?;
+        Ok(AccessToken {
+            admin_id: claim.admin_id,
+            role: claim.role,
+            scope: claim.scope,
+            expire_at: DateTime::<Utc>::from_utc(
+                NaiveDateTime::from_timestamp(claim.exp as i64, 0),
+                Utc,
+            ),
+        })
+    }
+}
+
+impl std::str::FromStr for AccessToken {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        AccessToken::decode(s)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JwtClaim {
+    admin_id: AdminId,
+    role: Role,
+    #[serde(default)]
+    scope: Scope,
+    exp: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_superadmin_can_manage_admins() {
+        assert!(Permission::ManageAdmins.is_granted_to(Role::Superadmin));
+        assert!(!Permission::ManageAdmins.is_granted_to(Role::Operator));
+        assert!(!Permission::ManageAdmins.is_granted_to(Role::Moderator));
+    }
+
+    #[test]
+    fn moderator_can_not_resolve_poll() {
+        assert!(Permission::ResolvePoll.is_granted_to(Role::Operator));
+        assert!(!Permission::ResolvePoll.is_granted_to(Role::Moderator));
+        assert!(Permission::ModerateComment.is_granted_to(Role::Moderator));
+    }
+
+    #[test]
+    fn only_superadmin_can_re_resolve_poll() {
+        assert!(Permission::ReResolvePoll.is_granted_to(Role::Superadmin));
+        assert!(!Permission::ReResolvePoll.is_granted_to(Role::Operator));
+        assert!(!Permission::ReResolvePoll.is_granted_to(Role::Moderator));
+    }
+
+    #[test]
+    fn only_superadmin_can_manage_points() {
+        assert!(Permission::ManagePoints.is_granted_to(Role::Superadmin));
+        assert!(!Permission::ManagePoints.is_granted_to(Role::Operator));
+        assert!(!Permission::ManagePoints.is_granted_to(Role::Moderator));
+    }
+
+    #[test]
+    fn totp_enrollment_scope_has_no_permission() {
+        let admin = AuthenticatedAdmin {
+            id: AdminId::new(),
+            role: Role::Superadmin,
+            scope: Scope::TotpEnrollment,
+        };
+        assert!(!admin.has_permission(Permission::CreateContest));
+        assert!(!admin.has_permission(Permission::ManageAdmins));
+    }
+}
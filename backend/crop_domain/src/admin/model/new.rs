@@ -0,0 +1,46 @@
+use super::{Admin, AdminId, Role, WithAttrs};
+use crate::admin::{repository::Updatable, service::auth::Credentials};
+use crop_infra::pg::{
+    admin::{AdminTable as _, NewAdmin},
+    Connection,
+};
+
+pub struct New {
+    pub(super) id: AdminId,
+    pub(super) email: String,
+    pub(super) role: Role,
+    pub(super) credentials: Credentials,
+}
+
+impl Admin for New {
+    fn id(&self) -> &AdminId {
+        &self.id
+    }
+
+    fn role(&self) -> Role {
+        self.role
+    }
+}
+
+impl WithAttrs for New {
+    fn _email(&self) -> &str {
+        self.email.as_str()
+    }
+
+    fn _is_disabled(&self) -> bool {
+        false
+    }
+}
+
+impl Updatable for New {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let new_admin = NewAdmin {
+            id: &self.id.0,
+            email: self.email(),
+            cred: &self.credentials.cred[..],
+            salt: &self.credentials.salt[..],
+            role: self.role,
+        };
+        conn.save(new_admin)
+    }
+}
@@ -0,0 +1,35 @@
+use super::{Admin, AdminId, Role};
+use crate::admin::{repository::Updatable, service::auth::Credentials};
+use crop_infra::pg::{admin::AdminTable as _, Connection};
+
+#[must_use]
+pub struct PasswordReset<A> {
+    pub(super) admin: A,
+    pub(super) credentials: Credentials,
+}
+
+impl<A> Admin for PasswordReset<A>
+where
+    A: Admin,
+{
+    fn id(&self) -> &AdminId {
+        self.admin.id()
+    }
+
+    fn role(&self) -> Role {
+        self.admin.role()
+    }
+}
+
+impl<A> Updatable for PasswordReset<A>
+where
+    A: Admin,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        conn.update_credentials(
+            &self.id().0,
+            &self.credentials.cred[..],
+            &self.credentials.salt[..],
+        )
+    }
+}
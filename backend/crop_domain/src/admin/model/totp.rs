@@ -0,0 +1,257 @@
+use super::{Admin, AdminId, Role, WithAttrs, WithTotp};
+use crate::admin::{
+    repository::{Queryable, Updatable},
+    service::totp,
+};
+use crop_infra::pg::{
+    self,
+    admin::{AdminTable as _, QueriedAdminTotp},
+    admin_backup_code::{AdminBackupCodeTable as _, NewAdminBackupCode},
+    Connection,
+};
+
+/*
+ * ==========
+ * TotpAdmin
+ * ==========
+ */
+/// TOTPの登録状態を含むAdmin
+pub struct TotpAdmin {
+    id: AdminId,
+    email: String,
+    role: Role,
+    disabled: bool,
+    secret: Option<Vec<u8>>,
+    enabled: bool,
+}
+
+impl Admin for TotpAdmin {
+    fn id(&self) -> &AdminId {
+        &self.id
+    }
+
+    fn role(&self) -> Role {
+        self.role
+    }
+}
+
+impl WithAttrs for TotpAdmin {
+    fn _email(&self) -> &str {
+        self.email.as_str()
+    }
+
+    fn _is_disabled(&self) -> bool {
+        self.disabled
+    }
+}
+
+impl WithTotp for TotpAdmin {
+    fn _totp_secret(&self) -> Option<&[u8]> {
+        self.secret.as_deref()
+    }
+
+    fn _is_totp_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl From<QueriedAdminTotp> for TotpAdmin {
+    fn from(queried: QueriedAdminTotp) -> TotpAdmin {
+        TotpAdmin {
+            id: AdminId(queried.id),
+            email: queried.email,
+            role: queried.role,
+            disabled: queried.disabled,
+            secret: queried.totp_secret,
+            enabled: queried.totp_enabled,
+        }
+    }
+}
+
+impl Queryable for TotpAdmin {
+    fn query_by_id(conn: &Connection, id: &AdminId) -> anyhow::Result<Option<Self>> {
+        Ok(conn.query_totp_by_id(&id.0)?.map(TotpAdmin::from))
+    }
+
+    fn query_by_email(conn: &Connection, email: &str) -> anyhow::Result<Option<Self>> {
+        Ok(conn.query_totp_by_email(email)?.map(TotpAdmin::from))
+    }
+}
+
+/*
+ * ==========
+ * TotpEnrolling
+ * ==========
+ */
+#[must_use]
+pub struct TotpEnrolling<A> {
+    pub(super) admin: A,
+    pub(super) secret: Vec<u8>,
+}
+
+impl<A> TotpEnrolling<A> {
+    pub fn secret(&self) -> String {
+        totp::encode_secret(self.secret.as_slice())
+    }
+
+    pub fn otpauth_uri(&self) -> String
+    where
+        A: WithAttrs,
+    {
+        totp::otpauth_uri(self.secret.as_slice(), self.admin.email())
+    }
+}
+
+impl<A> Admin for TotpEnrolling<A>
+where
+    A: Admin,
+{
+    fn id(&self) -> &AdminId {
+        self.admin.id()
+    }
+
+    fn role(&self) -> Role {
+        self.admin.role()
+    }
+}
+
+impl<A> Updatable for TotpEnrolling<A>
+where
+    A: Admin,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        conn.update_totp(&self.id().0, Some(self.secret.as_slice()), false)
+    }
+}
+
+/*
+ * ==========
+ * TotpEnabled
+ * ==========
+ */
+#[must_use]
+pub struct TotpEnabled<A> {
+    pub(super) admin: A,
+    pub(super) secret: Vec<u8>,
+    /// 登録時に検証したコードのステップ
+    pub(super) step: i64,
+    pub(super) backup_codes: Vec<String>,
+}
+
+impl<A> TotpEnabled<A> {
+    /// 平文のバックアップコード。
+    /// 保存されるのはハッシュのみなので、この時にしか取得できない。
+    pub fn backup_codes(&self) -> &[String] {
+        self.backup_codes.as_slice()
+    }
+}
+
+impl<A> Admin for TotpEnabled<A>
+where
+    A: Admin,
+{
+    fn id(&self) -> &AdminId {
+        self.admin.id()
+    }
+
+    fn role(&self) -> Role {
+        self.admin.role()
+    }
+}
+
+impl<A> Updatable for TotpEnabled<A>
+where
+    A: Admin,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let hashes = self
+            .backup_codes
+            .iter()
+            .map(|code| totp::hash_backup_code(code))
+            .collect::<Vec<_>>();
+        let new_codes = hashes
+            .iter()
+            .map(|hash| NewAdminBackupCode {
+                admin_id: &self.id().0,
+                code_hash: hash.as_slice(),
+            })
+            .collect::<Vec<_>>();
+
+        pg::transaction(conn, || {
+            conn.update_totp(&self.id().0, Some(self.secret.as_slice()), true)?;
+            // 登録に使ったコードで、そのままログインできないようにする
+            if !conn.accept_totp_step(&self.id().0, self.step)? {
+                return Err(anyhow::anyhow!("TOTP code is already used"));
+            }
+            conn.replace_all(&self.id().0, new_codes.as_slice())
+        })
+    }
+}
+
+/*
+ * ==========
+ * TotpReset
+ * ==========
+ */
+#[must_use]
+pub struct TotpReset<A> {
+    pub(super) admin: A,
+}
+
+impl<A> Admin for TotpReset<A>
+where
+    A: Admin,
+{
+    fn id(&self) -> &AdminId {
+        self.admin.id()
+    }
+
+    fn role(&self) -> Role {
+        self.admin.role()
+    }
+}
+
+impl<A> Updatable for TotpReset<A>
+where
+    A: Admin,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        pg::transaction(conn, || {
+            conn.update_totp(&self.id().0, None, false)?;
+            conn.delete_all(&self.id().0)
+        })
+    }
+}
+
+/*
+ * ==========
+ * TotpRequirementChanged
+ * ==========
+ */
+#[must_use]
+pub struct TotpRequirementChanged<A> {
+    pub(super) admin: A,
+    pub(super) required: bool,
+}
+
+impl<A> Admin for TotpRequirementChanged<A>
+where
+    A: Admin,
+{
+    fn id(&self) -> &AdminId {
+        self.admin.id()
+    }
+
+    fn role(&self) -> Role {
+        self.admin.role()
+    }
+}
+
+impl<A> Updatable for TotpRequirementChanged<A>
+where
+    A: Admin,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        conn.update_totp_required(&self.id().0, self.required)
+    }
+}
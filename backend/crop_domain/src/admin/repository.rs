@@ -1,10 +1,97 @@
 use super::{
-    model::{AdminId, AuthenticatedAdmin},
-    service::auth::verify_credentials,
+    audit::{self, AuditLog},
+    model::{AccessToken, AdminId, AuthenticatedAdmin, Role, Scope},
+    service::{auth::verify_credentials, totp},
 };
-use crop_infra::pg::{admin::AdminTable as _, Connection};
+use chrono::Utc;
+use crop_infra::pg::{admin::AdminTable, admin_backup_code::AdminBackupCodeTable as _, Connection};
 use uuid::Uuid;
 
+pub trait AdminRepository {
+    fn conn(&self) -> &Connection;
+
+    fn save<A>(&self, admin: &A) -> anyhow::Result<()>
+    where
+        A: Updatable,
+    {
+        admin.save(self.conn())
+    }
+
+    fn query_by_id<A>(&self, id: &AdminId) -> anyhow::Result<Option<A>>
+    where
+        A: Queryable,
+    {
+        A::query_by_id(self.conn(), id)
+    }
+
+    fn query_by_email<A>(&self, email: &str) -> anyhow::Result<Option<A>>
+    where
+        A: Queryable,
+    {
+        A::query_by_email(self.conn(), email)
+    }
+
+    fn query_all<A>(&self) -> anyhow::Result<Vec<A>>
+    where
+        A: ListQueryable,
+    {
+        A::query_all(self.conn())
+    }
+
+    /// 監査ログを新しい順に取得する
+    fn query_audit_log(&self, filter: &audit::Filter) -> anyhow::Result<Vec<AuditLog>> {
+        audit::query(self.conn(), filter)
+    }
+}
+
+impl AdminRepository for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+pub trait Updatable {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()>;
+}
+
+pub trait Queryable: Sized {
+    fn query_by_id(conn: &Connection, id: &AdminId) -> anyhow::Result<Option<Self>>;
+
+    fn query_by_email(conn: &Connection, email: &str) -> anyhow::Result<Option<Self>>;
+}
+
+pub trait ListQueryable: Sized {
+    fn query_all(conn: &Connection) -> anyhow::Result<Vec<Self>>;
+}
+
+/// AccessTokenを、DBにある現在のAdminの状態と照らし合わせて検証する。
+///
+/// AccessTokenの有効期限は長いため、その間に行われた無効化やRoleの変更、
+/// TOTPの必須化を反映できるように、認証が必要なリクエストのたびに呼び出す。
+pub fn authenticate_token(
+    conn: &Connection,
+    token: &AccessToken,
+) -> anyhow::Result<AuthenticatedAdmin> {
+    let admin = AdminTable::query_by_id(conn, &token.admin_id.0)?
+        .ok_or_else(|| anyhow::anyhow!("Admin is not found"))?;
+    if admin.disabled {
+        return Err(anyhow::anyhow!("Admin is disabled"));
+    }
+
+    // トークンの発行後にTOTPが必須になった場合は、TOTPの登録以外を行えなくする
+    let scope = if admin.totp_required && !admin.totp_enabled {
+        Scope::TotpEnrollment
+    } else {
+        token.scope
+    };
+
+    Ok(AuthenticatedAdmin {
+        id: token.admin_id,
+        role: admin.role,
+        scope,
+    })
+}
+
 pub fn query_unauthenticated(
     conn: &Connection,
     email: &str,
@@ -14,6 +101,11 @@ pub fn query_unauthenticated(
             id: admin.id,
             cred: admin.cred,
             salt: admin.salt,
+            role: admin.role,
+            disabled: admin.disabled,
+            totp_secret: admin.totp_secret,
+            totp_enabled: admin.totp_enabled,
+            totp_required: admin.totp_required,
         }))
     } else {
         Ok(None)
@@ -26,14 +118,72 @@ pub struct Unauthenticated {
     id: Uuid,
     cred: Vec<u8>,
     salt: Vec<u8>,
+    role: Role,
+    disabled: bool,
+    totp_secret: Option<Vec<u8>>,
+    totp_enabled: bool,
+    totp_required: bool,
 }
 
 impl Unauthenticated {
-    pub fn authenticate(&self, pass: &str) -> anyhow::Result<AuthenticatedAdmin> {
+    pub fn admin_id(&self) -> AdminId {
+        AdminId(self.id)
+    }
+
+    /// パスワードを検証し、TOTPが有効なAdminであればTOTPコードも検証する。
+    /// TOTPコードの代わりに未使用のバックアップコードも使える。
+    ///
+    /// TOTPが必須だが未登録のAdminには、TOTPの登録のみ行えるScopeを与える。
+    pub fn authenticate(
+        &self,
+        conn: &Connection,
+        pass: &str,
+        totp_code: Option<&str>,
+    ) -> anyhow::Result<AuthenticatedAdmin> {
         verify_credentials(self.salt.as_slice(), self.cred.as_slice(), pass)?;
 
+        if self.disabled {
+            return Err(anyhow::anyhow!("Admin is disabled"));
+        }
+
+        let scope = if self.totp_enabled {
+            let code = totp_code.ok_or(TotpCodeRequired)?;
+            let secret = self
+                .totp_secret
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("TOTP secret is missing"))?;
+            let now = Utc::now();
+            // 一度受け付けたステップのコードは、有効期間内でも再利用できない
+            let accepted = match totp::verify_code(secret.as_slice(), code, now) {
+                Some(step) => conn.accept_totp_step(&self.id, step)?,
+                None => conn.mark_used(&self.id, totp::hash_backup_code(code).as_slice(), &now)?,
+            };
+            if !accepted {
+                return Err(anyhow::anyhow!("Invalid TOTP code"));
+            }
+            Scope::Full
+        } else if self.totp_required {
+            Scope::TotpEnrollment
+        } else {
+            Scope::Full
+        };
+
         Ok(AuthenticatedAdmin {
             id: AdminId(self.id),
+            role: self.role,
+            scope,
         })
     }
 }
+
+/// パスワードは正しいが、TOTPコードが与えられていない
+#[derive(Debug)]
+pub struct TotpCodeRequired;
+
+impl std::fmt::Display for TotpCodeRequired {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "TOTP code is required")
+    }
+}
+
+impl std::error::Error for TotpCodeRequired {}
@@ -14,6 +14,16 @@ pub fn verify_credentials(salt: &[u8], cred: &[u8], attempted_pass: &str) -> any
         .map_err(|_| anyhow::anyhow!("Authentication failed"))
 }
 
+/// 存在しないemailでの認証を、存在する場合と同じだけ時間をかけて失敗させる。
+/// 応答時間から、emailが登録されているかどうかを推測されないようにする。
+pub fn reject_credentials(attempted_pass: &str) -> anyhow::Error {
+    let dummy = [0u8; CRED_LEN];
+    match verify_credentials(&dummy[..], &dummy[..], attempted_pass) {
+        Ok(()) => anyhow::anyhow!("Authentication failed"),
+        Err(e) => e,
+    }
+}
+
 pub fn derive_credentials(raw_pass: &str) -> Credentials {
     let salt = gen_salt();
 
@@ -62,4 +72,12 @@ mod tests {
         let Credentials { salt, cred } = derive_credentials(pass);
         verify_credentials(&salt[..], &cred[..], pass).unwrap();
     }
+
+    #[test]
+    fn reject_always_fails() {
+        assert_eq!(
+            reject_credentials("hogehoge").to_string(),
+            "Authentication failed"
+        );
+    }
 }
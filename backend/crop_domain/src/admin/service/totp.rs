@@ -0,0 +1,134 @@
+//! RFC 6238 に基づくTOTPの生成と検証
+use chrono::{DateTime, Utc};
+use data_encoding::{BASE32_NOPAD, HEXUPPER};
+use rand::{thread_rng, Rng};
+use ring::{constant_time, digest, hmac};
+
+const SECRET_LEN: usize = 20;
+const STEP_SEC: i64 = 30;
+const DIGITS: usize = 6;
+/// 端末の時計のずれを考慮して、前後1ステップまで許容する
+const SKEW_STEPS: i64 = 1;
+const ISSUER: &str = "PlayPoll";
+
+const BACKUP_CODE_COUNT: usize = 10;
+const BACKUP_CODE_BYTES: usize = 5;
+
+pub fn gen_secret() -> Vec<u8> {
+    let mut secret = vec![0u8; SECRET_LEN];
+    thread_rng().fill(secret.as_mut_slice());
+    secret
+}
+
+/// 認証アプリに手入力するための形式
+pub fn encode_secret(secret: &[u8]) -> String {
+    BASE32_NOPAD.encode(secret)
+}
+
+/// 認証アプリにQRコードで読み込ませるためのURI
+pub fn otpauth_uri(secret: &[u8], account_name: &str) -> String {
+    format!(
+        "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={digits}&period={period}",
+        issuer = ISSUER,
+        account = encode_uri_component(account_name),
+        secret = encode_secret(secret),
+        digits = DIGITS,
+        period = STEP_SEC,
+    )
+}
+
+/// コードを検証し、一致したステップを返す。
+/// 同じコードの再利用を防ぐため、呼び出し側で受け付けたステップを記録する必要がある。
+pub fn verify_code(secret: &[u8], code: &str, now: DateTime<Utc>) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != DIGITS || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let counter = now.timestamp() / STEP_SEC;
+    (-SKEW_STEPS..=SKEW_STEPS)
+        .map(|skew| counter + skew)
+        .find(|step| {
+            let expected = format!("{:0width$}", code_at(secret, *step as u64), width = DIGITS);
+            constant_time::verify_slices_are_equal(expected.as_bytes(), code.as_bytes()).is_ok()
+        })
+}
+
+fn code_at(secret: &[u8], counter: u64) -> u32 {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+    let tag = hmac::sign(&key, &counter.to_be_bytes());
+    let hash = tag.as_ref();
+
+    // dynamic truncation
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let bin = ((hash[offset] as u32 & 0x7f) << 24)
+        | ((hash[offset + 1] as u32) << 16)
+        | ((hash[offset + 2] as u32) << 8)
+        | (hash[offset + 3] as u32);
+    bin % 10u32.pow(DIGITS as u32)
+}
+
+/// 認証アプリを紛失した場合のためのバックアップコードを生成する。
+/// 各コードは1度だけ使える。
+pub fn gen_backup_codes() -> Vec<String> {
+    let mut rng = thread_rng();
+    (0..BACKUP_CODE_COUNT)
+        .map(|_| {
+            let mut bytes = [0u8; BACKUP_CODE_BYTES];
+            rng.fill(&mut bytes);
+            HEXUPPER.encode(&bytes)
+        })
+        .collect()
+}
+
+/// バックアップコードは十分なエントロピーを持つので、saltなしのハッシュで保存する
+pub fn hash_backup_code(code: &str) -> Vec<u8> {
+    let normalized = code.trim().to_ascii_uppercase();
+    digest::digest(&digest::SHA256, normalized.as_bytes())
+        .as_ref()
+        .to_vec()
+}
+
+fn encode_uri_component(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone as _;
+
+    // RFC 6238 Appendix B のテストベクタ（下6桁）
+    #[test]
+    fn rfc6238_test_vectors() {
+        let secret = b"12345678901234567890";
+        assert_eq!(code_at(secret, 59 / 30), 287082);
+        assert_eq!(code_at(secret, 1111111109 / 30), 81804);
+        assert_eq!(code_at(secret, 1234567890 / 30), 5924);
+        assert_eq!(code_at(secret, 2000000000 / 30), 279037);
+    }
+
+    #[test]
+    fn verify_code_allows_one_step_skew() {
+        let secret = b"12345678901234567890";
+        let now = Utc.timestamp(1111111109, 0);
+        let step = 1111111109 / 30;
+        assert_eq!(verify_code(secret, "081804", now), Some(step));
+        assert_eq!(
+            verify_code(secret, "081804", now + chrono::Duration::seconds(30)),
+            Some(step)
+        );
+        assert_eq!(
+            verify_code(secret, "081804", now + chrono::Duration::seconds(90)),
+            None
+        );
+        assert_eq!(verify_code(secret, "81804", now), None);
+    }
+}
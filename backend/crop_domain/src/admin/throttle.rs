@@ -0,0 +1,329 @@
+//! Adminログインの試行回数制限
+//!
+//! email毎・IPアドレス毎にログインの失敗回数を記録し、
+//! 閾値を超えると指数的に長くなる期間ログインをロックする。
+//! 状態はDBに保存されるので、サーバーを再起動してもリセットされない。
+//!
+//! アカウントのログインも、Adminとは別に数えて同じように制限する。
+//! TOTPの登録時のコード検証は、Admin毎に数えて制限する。
+use crate::admin::{
+    audit::{Action, Audited},
+    model::AdminId,
+    repository::Updatable,
+};
+use chrono::{DateTime, Duration, Utc};
+use crop_infra::pg::{admin_login_throttle::AdminLoginThrottleTable as _, Connection};
+use serde_json::Value;
+use std::net::IpAddr;
+
+pub type Kind = crop_infra::pg::types::LoginThrottleKind;
+
+struct Policy {
+    /// この回数失敗するとロックされる
+    threshold: i32,
+    /// 最初のロック期間。以降の失敗毎に倍になる。
+    base_lock_sec: i64,
+    max_lock_sec: i64,
+}
+
+const EMAIL_POLICY: Policy = Policy {
+    threshold: 5,
+    base_lock_sec: 60,
+    max_lock_sec: 60 * 60,
+};
+
+/// 6桁のコードを推測されないよう、emailと同じだけ厳しくする
+const TOTP_POLICY: Policy = Policy {
+    threshold: 5,
+    base_lock_sec: 60,
+    max_lock_sec: 60 * 60,
+};
+
+/// 同じIPから複数のAdminがログインすることもあるので、emailよりも緩くする
+const IP_POLICY: Policy = Policy {
+    threshold: 20,
+    base_lock_sec: 60,
+    max_lock_sec: 60 * 60,
+};
+
+/// 最後の失敗からこの時間が経過すると、失敗回数をリセットする
+const RESET_AFTER_HOURS: i64 = 24;
+
+impl Policy {
+    fn of(kind: Kind) -> &'static Policy {
+        match kind {
+            Kind::Email | Kind::AccountEmail => &EMAIL_POLICY,
+            Kind::Ip | Kind::AccountIp => &IP_POLICY,
+            Kind::Totp => &TOTP_POLICY,
+        }
+    }
+
+    fn lock_duration(&self, failure_count: i32) -> Option<Duration> {
+        if failure_count < self.threshold {
+            return None;
+        }
+        // 2^32秒を超えることはないので、それ以上は計算しない
+        let exp = (failure_count - self.threshold).min(32) as u32;
+        let sec = self
+            .base_lock_sec
+            .saturating_mul(2_i64.pow(exp))
+            .min(self.max_lock_sec);
+        Some(Duration::seconds(sec))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Throttle {
+    kind: Kind,
+    subject: String,
+    failure_count: i32,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+impl Throttle {
+    fn query(conn: &Connection, kind: Kind, subject: String) -> anyhow::Result<Throttle> {
+        let queried = conn.query_by_subject(kind, subject.as_str())?;
+        Ok(Throttle {
+            kind,
+            subject,
+            failure_count: queried.as_ref().map(|q| q.failure_count).unwrap_or(0),
+            locked_until: queried.and_then(|q| q.locked_until),
+        })
+    }
+
+    fn locked_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.locked_until.filter(|until| *until > now)
+    }
+
+    /// 失敗をDBに記録し、記録後の状態を返す
+    fn record_failure(&self, conn: &Connection, now: DateTime<Utc>) -> anyhow::Result<Throttle> {
+        let reset_before = now - Duration::hours(RESET_AFTER_HOURS);
+        let failure_count =
+            conn.increment_failure(self.kind, self.subject.as_str(), &now, &reset_before)?;
+        let failed = self.failed(failure_count, now);
+        if let Some(locked_until) = failed.locked_until.as_ref() {
+            conn.extend_lock(self.kind, self.subject.as_str(), locked_until)?;
+        }
+        Ok(failed)
+    }
+
+    /// `failure_count` 回目の失敗をした後の状態
+    fn failed(&self, failure_count: i32, now: DateTime<Utc>) -> Throttle {
+        Throttle {
+            kind: self.kind,
+            subject: self.subject.clone(),
+            failure_count,
+            locked_until: Policy::of(self.kind)
+                .lock_duration(failure_count)
+                .map(|d| now + d),
+        }
+    }
+}
+
+/// 1回のログイン試行
+pub struct Attempt {
+    /// emailもしくはTOTPを検証するAdmin
+    subject: Throttle,
+    ip: Option<Throttle>,
+}
+
+pub fn query_attempt(
+    conn: &Connection,
+    email: &str,
+    ip: Option<&IpAddr>,
+) -> anyhow::Result<Attempt> {
+    query(conn, (Kind::Email, email), (Kind::Ip, ip))
+}
+
+/// アカウントのログイン試行。
+/// `email` は `account::normalize_email` したものを渡す。
+pub fn query_account_attempt(
+    conn: &Connection,
+    email: &str,
+    ip: Option<&IpAddr>,
+) -> anyhow::Result<Attempt> {
+    query(conn, (Kind::AccountEmail, email), (Kind::AccountIp, ip))
+}
+
+/// TOTPの登録時のコード検証。
+/// ログイン済みのAdminが行うので、IPアドレスでは数えない。
+pub fn query_totp_attempt(conn: &Connection, admin_id: &AdminId) -> anyhow::Result<Attempt> {
+    Ok(Attempt {
+        subject: Throttle::query(conn, Kind::Totp, admin_id.0.to_string())?,
+        ip: None,
+    })
+}
+
+fn query(
+    conn: &Connection,
+    (subject_kind, subject): (Kind, &str),
+    (ip_kind, ip): (Kind, Option<&IpAddr>),
+) -> anyhow::Result<Attempt> {
+    Ok(Attempt {
+        subject: Throttle::query(conn, subject_kind, subject.to_string())?,
+        ip: ip
+            .map(|ip| Throttle::query(conn, ip_kind, ip.to_string()))
+            .transpose()?,
+    })
+}
+
+impl Attempt {
+    /// ロック中であればエラーを返す。
+    /// ロック中はパスワードの検証自体を行わない。
+    pub fn check(&self) -> anyhow::Result<()> {
+        let now = Utc::now();
+        let locked_until = std::iter::once(&self.subject)
+            .chain(self.ip.as_ref())
+            .filter_map(|t| t.locked_until(now))
+            .max();
+        match locked_until {
+            Some(until) => Err(anyhow::anyhow!("Login is locked until {}", until)),
+            None => Ok(()),
+        }
+    }
+
+    /// ログインの失敗を記録する。
+    /// 記録した結果は、監査ログに残すために返す。
+    pub fn fail(self, conn: &Connection) -> anyhow::Result<Failed> {
+        let now = Utc::now();
+        Ok(Failed {
+            subject: self.subject.record_failure(conn, now)?,
+            ip: self.ip.map(|ip| ip.record_failure(conn, now)).transpose()?,
+        })
+    }
+
+    /// ログインに成功したらemail（TOTPの場合はAdmin）の失敗回数をリセットする。
+    /// IPの失敗回数は、他のemailへの試行を含むのでリセットしない。
+    pub fn succeed(self) -> Succeeded {
+        Succeeded {
+            subject: self.subject,
+        }
+    }
+}
+
+pub struct Failed {
+    subject: Throttle,
+    ip: Option<Throttle>,
+}
+
+impl Failed {
+    /// この失敗によってロックされたかどうか
+    pub fn is_locked(&self) -> bool {
+        self.subject.locked_until.is_some() || self.ip.iter().any(|t| t.locked_until.is_some())
+    }
+}
+
+impl Audited for Failed {
+    fn action(&self) -> Action {
+        if self.is_locked() {
+            Action::LoginLocked
+        } else {
+            Action::LoginFailed
+        }
+    }
+
+    fn before_state(&self) -> Option<Value> {
+        None
+    }
+
+    fn after_state(&self) -> Option<Value> {
+        Some(serde_json::json!({
+            "email": self.subject.subject,
+            "email_failure_count": self.subject.failure_count,
+            "ip": self.ip.as_ref().map(|t| &t.subject),
+            "ip_failure_count": self.ip.as_ref().map(|t| t.failure_count),
+            "locked_until": std::iter::once(&self.subject)
+                .chain(self.ip.as_ref())
+                .filter_map(|t| t.locked_until)
+                .max(),
+        }))
+    }
+}
+
+#[must_use]
+pub struct Succeeded {
+    subject: Throttle,
+}
+
+impl Updatable for Succeeded {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        if self.subject.failure_count == 0 {
+            return Ok(());
+        }
+        conn.delete(self.subject.kind, self.subject.subject.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttle(kind: Kind) -> Throttle {
+        Throttle {
+            kind,
+            subject: String::from("admin@example.com"),
+            failure_count: 0,
+            locked_until: None,
+        }
+    }
+
+    #[test]
+    fn lock_duration_doubles_after_threshold() {
+        assert_eq!(EMAIL_POLICY.lock_duration(4), None);
+        assert_eq!(EMAIL_POLICY.lock_duration(5), Some(Duration::seconds(60)));
+        assert_eq!(EMAIL_POLICY.lock_duration(6), Some(Duration::seconds(120)));
+        assert_eq!(EMAIL_POLICY.lock_duration(8), Some(Duration::seconds(480)));
+        assert_eq!(
+            EMAIL_POLICY.lock_duration(100),
+            Some(Duration::seconds(60 * 60))
+        );
+    }
+
+    #[test]
+    fn email_is_locked_after_threshold_failures() {
+        let now = Utc::now();
+        let t = throttle(Kind::Email);
+        assert_eq!(t.failed(4, now).locked_until(now), None);
+
+        let t = t.failed(5, now);
+        assert_eq!(t.locked_until(now), Some(now + Duration::seconds(60)));
+        assert_eq!(t.locked_until(now + Duration::seconds(60)), None);
+    }
+
+    #[test]
+    fn account_login_uses_same_policy_as_admin_login() {
+        let now = Utc::now();
+        assert_eq!(
+            throttle(Kind::AccountEmail)
+                .failed(5, now)
+                .locked_until(now),
+            Some(now + Duration::seconds(60))
+        );
+        assert_eq!(
+            throttle(Kind::AccountIp).failed(19, now).locked_until(now),
+            None
+        );
+    }
+
+    #[test]
+    fn totp_is_locked_after_threshold_failures() {
+        let now = Utc::now();
+        let t = throttle(Kind::Totp);
+        assert_eq!(t.failed(4, now).locked_until(now), None);
+        assert_eq!(
+            t.failed(5, now).locked_until(now),
+            Some(now + Duration::seconds(60))
+        );
+    }
+
+    #[test]
+    fn ip_is_locked_later_than_email() {
+        let now = Utc::now();
+        let t = throttle(Kind::Ip);
+        assert_eq!(t.failed(19, now).locked_until(now), None);
+        assert_eq!(
+            t.failed(20, now).locked_until(now),
+            Some(now + Duration::seconds(60))
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use super::PollDraft;
+use crate::contest::Updatable;
+use crop_infra::pg::{
+    poll_draft::{PollDraftTable as _, UpdatedPollDraft},
+    Connection,
+};
+
+#[must_use]
+pub struct Edited {
+    pub draft: PollDraft,
+}
+
+impl Updatable for Edited {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let choices = serde_json::to_value(self.draft.choices())?;
+        let updated = UpdatedPollDraft {
+            title: self.draft.title(),
+            duration_sec: self.draft.duration().map(|d| d.num_seconds() as i32),
+            choices: &choices,
+        };
+        conn.update(&self.draft.id().0, updated)
+    }
+}
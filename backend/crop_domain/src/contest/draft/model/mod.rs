@@ -0,0 +1,309 @@
+use crate::contest::poll::{self, Choice, PollKind, PollScoring};
+use crate::contest::{ContestId, NewPoll, WithAttrs, WithCurrentPoll};
+use chrono::Duration;
+use crop_infra::pg::{
+    poll_draft::{PollDraftTable as _, QueriedPollDraft},
+    Connection,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+mod edited;
+mod published;
+mod pushed;
+mod removed;
+mod reordered;
+
+pub use edited::Edited;
+pub use published::Published;
+pub use pushed::Pushed;
+pub use removed::Removed;
+pub use reordered::Reordered;
+
+/// 新しいPollの下書きを作成する。
+/// 公開した時にPollとして出題できるよう、ここで内容を確認しておく。
+pub fn new(
+    idx: usize,
+    title: String,
+    duration: Option<Duration>,
+    choices: Vec<Choice>,
+) -> anyhow::Result<PollDraft> {
+    poll::validate(title.as_str(), duration.as_ref(), choices.as_slice())?;
+
+    Ok(PollDraft {
+        id: DraftId::new(),
+        idx,
+        title,
+        duration,
+        choices,
+    })
+}
+
+/// 事前に用意された、まだ公開されていないPoll。
+/// 参加者からは見えない。
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PollDraft {
+    pub(crate) id: DraftId,
+    pub(crate) idx: usize,
+    pub(crate) title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_duration")]
+    #[serde(rename = "duration_sec")]
+    #[schemars(with = "Option<i64>")]
+    pub(crate) duration: Option<Duration>,
+    pub(crate) choices: Vec<Choice>,
+}
+
+fn serialize_duration<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    value
+        .as_ref()
+        .map(|d| d.num_seconds())
+        .serialize(serializer)
+}
+
+impl PollDraft {
+    pub fn id(&self) -> &DraftId {
+        &self.id
+    }
+
+    /// 公開される順番。小さいものから公開される。
+    pub fn idx(&self) -> usize {
+        self.idx
+    }
+
+    pub fn title(&self) -> &str {
+        self.title.as_str()
+    }
+
+    pub fn duration(&self) -> Option<&Duration> {
+        self.duration.as_ref()
+    }
+
+    pub fn choices(&self) -> &[Choice] {
+        self.choices.as_slice()
+    }
+}
+
+impl PollDraft {
+    fn from_queried(queried: QueriedPollDraft) -> anyhow::Result<PollDraft> {
+        Ok(PollDraft {
+            id: DraftId(queried.id),
+            idx: queried.idx as usize,
+            title: queried.title,
+            duration: queried.duration_sec.map(|s| Duration::seconds(s as i64)),
+            choices: serde_json::from_value(queried.choices)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct DraftId(pub Uuid);
+
+impl DraftId {
+    pub fn new() -> DraftId {
+        DraftId(Uuid::new_v4())
+    }
+}
+
+impl std::str::FromStr for DraftId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(DraftId(Uuid::parse_str(s)?))
+    }
+}
+
+/*
+ * ==========
+ * Queue
+ * ==========
+ */
+/// Contestに紐づく下書きを、公開する順に並べたもの
+#[derive(Debug, Clone)]
+pub struct DraftQueue {
+    contest_id: ContestId,
+    drafts: Vec<PollDraft>,
+}
+
+impl DraftQueue {
+    pub(crate) fn query(conn: &Connection, contest_id: &ContestId) -> anyhow::Result<DraftQueue> {
+        let drafts = conn
+            .query_by_contest_id(&contest_id.0)?
+            .into_iter()
+            .map(PollDraft::from_queried)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(DraftQueue {
+            contest_id: *contest_id,
+            drafts,
+        })
+    }
+
+    pub fn contest_id(&self) -> &ContestId {
+        &self.contest_id
+    }
+
+    pub fn drafts(&self) -> &[PollDraft] {
+        self.drafts.as_slice()
+    }
+
+    /// 次に公開される下書き
+    pub fn head(&self) -> Option<&PollDraft> {
+        self.drafts.first()
+    }
+
+    fn get(&self, id: &DraftId) -> anyhow::Result<&PollDraft> {
+        self.drafts
+            .iter()
+            .find(|draft| draft.id() == id)
+            .ok_or_else(|| anyhow::anyhow!("Draft is not found"))
+    }
+
+    /// 末尾に下書きを追加する
+    pub fn push(
+        &self,
+        title: String,
+        duration: Option<Duration>,
+        choices: Vec<Choice>,
+    ) -> anyhow::Result<Pushed> {
+        let idx = self.drafts.last().map(|d| d.idx + 1).unwrap_or(1);
+        let draft = new(idx, title, duration, choices)?;
+        Ok(Pushed {
+            contest_id: self.contest_id,
+            draft,
+        })
+    }
+
+    /// 下書きの内容を書き換える。順番は変わらない。
+    pub fn edit(
+        &self,
+        id: &DraftId,
+        title: String,
+        duration: Option<Duration>,
+        choices: Vec<Choice>,
+    ) -> anyhow::Result<Edited> {
+        let idx = self.get(id)?.idx;
+        poll::validate(title.as_str(), duration.as_ref(), choices.as_slice())?;
+        Ok(Edited {
+            draft: PollDraft {
+                id: *id,
+                idx,
+                title,
+                duration,
+                choices,
+            },
+        })
+    }
+
+    /// 下書きを `position` 番目（0始まり）に移動する。
+    /// 範囲外の場合は末尾に移動する。
+    pub fn move_to(&self, id: &DraftId, position: usize) -> anyhow::Result<Reordered> {
+        let mut drafts = self.drafts.clone();
+        let from = drafts
+            .iter()
+            .position(|draft| draft.id() == id)
+            .ok_or_else(|| anyhow::anyhow!("Draft is not found"))?;
+        let draft = drafts.remove(from);
+        drafts.insert(position.min(drafts.len()), draft);
+
+        // 削除によって空いた番号も詰める
+        for (i, draft) in drafts.iter_mut().enumerate() {
+            draft.idx = i + 1;
+        }
+
+        Ok(Reordered { drafts })
+    }
+
+    pub fn remove(&self, id: &DraftId) -> anyhow::Result<Removed> {
+        let draft = self.get(id)?;
+        Ok(Removed { id: *draft.id() })
+    }
+
+    /// 先頭の下書きをPollとしてContestに追加する。
+    /// 追加できる条件は `Contest::add_poll` と同じ。
+    pub fn publish_head<'a, C>(&self, contest: &'a C) -> anyhow::Result<Published<&'a C>>
+    where
+        C: WithAttrs + WithCurrentPoll,
+        <C as WithCurrentPoll>::Poll: poll::WithAttrs,
+    {
+        if contest.id() != &self.contest_id {
+            return Err(anyhow::anyhow!("Draft queue is not for this contest"));
+        }
+
+        let head = self
+            .head()
+            .ok_or_else(|| anyhow::anyhow!("No draft in the queue"))?;
+        // 下書きは単一選択のPollとして出題する
+        let added = contest.add_poll(NewPoll {
+            title: head.title.clone(),
+            kind: PollKind::Single,
+            scoring: PollScoring::Exact,
+            duration: head.duration,
+            lock_before_close: None,
+            choices: head.choices.clone(),
+            numeric: None,
+            ranking_size: None,
+            is_scored: true,
+            stake_multiplier: None,
+            answer_commitment: None,
+        })?;
+        Ok(Published {
+            added,
+            draft_id: head.id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contest::poll::{ChoiceColor, ChoiceName};
+
+    fn queue(n: usize) -> DraftQueue {
+        let drafts = (1..=n)
+            .map(|idx| {
+                let choices = ["yes", "no"]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| Choice {
+                        idx: i,
+                        name: ChoiceName(name.to_string()),
+                        color: ChoiceColor("#000000".to_string()),
+                    })
+                    .collect();
+                new(idx * 2, format!("Q{}", idx), None, choices).unwrap()
+            })
+            .collect();
+        DraftQueue {
+            contest_id: ContestId::new(),
+            drafts,
+        }
+    }
+
+    #[test]
+    fn move_to_renumbers_drafts() {
+        let queue = queue(3);
+        let last = *queue.drafts()[2].id();
+        let reordered = queue.move_to(&last, 0).unwrap();
+
+        let titles = reordered
+            .drafts
+            .iter()
+            .map(|d| (d.title(), d.idx()))
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec![("Q3", 1), ("Q1", 2), ("Q2", 3)]);
+    }
+
+    #[test]
+    fn push_appends_after_last_draft() {
+        let queue = queue(2);
+        let pushed = queue
+            .push("Q3".to_string(), None, queue.drafts()[0].choices.clone())
+            .unwrap();
+        assert_eq!(pushed.draft.idx(), 5);
+    }
+}
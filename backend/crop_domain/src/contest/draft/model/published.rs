@@ -0,0 +1,20 @@
+use super::DraftId;
+use crate::contest::{Contest, PollAdded, Updatable};
+use crop_infra::pg::{poll_draft::PollDraftTable as _, Connection};
+
+/// 公開された下書きは、Pollとして追加されてキューから取り除かれる
+#[must_use]
+pub struct Published<C> {
+    pub added: PollAdded<C>,
+    pub draft_id: DraftId,
+}
+
+impl<C> Updatable for Published<C>
+where
+    C: Contest,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        self.added.save(conn)?;
+        conn.delete(&self.draft_id.0)
+    }
+}
@@ -0,0 +1,27 @@
+use super::PollDraft;
+use crate::contest::{ContestId, Updatable};
+use crop_infra::pg::{
+    poll_draft::{NewPollDraft, PollDraftTable as _},
+    Connection,
+};
+
+#[must_use]
+pub struct Pushed {
+    pub(super) contest_id: ContestId,
+    pub draft: PollDraft,
+}
+
+impl Updatable for Pushed {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let choices = serde_json::to_value(self.draft.choices())?;
+        let new_draft = NewPollDraft {
+            id: &self.draft.id().0,
+            contest_id: &self.contest_id.0,
+            idx: self.draft.idx() as i32,
+            title: self.draft.title(),
+            duration_sec: self.draft.duration().map(|d| d.num_seconds() as i32),
+            choices: &choices,
+        };
+        conn.save(new_draft)
+    }
+}
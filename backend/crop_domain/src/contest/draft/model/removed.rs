@@ -0,0 +1,14 @@
+use super::DraftId;
+use crate::contest::Updatable;
+use crop_infra::pg::{poll_draft::PollDraftTable as _, Connection};
+
+#[must_use]
+pub struct Removed {
+    pub(super) id: DraftId,
+}
+
+impl Updatable for Removed {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        conn.delete(&self.id.0)
+    }
+}
@@ -0,0 +1,17 @@
+use super::PollDraft;
+use crate::contest::Updatable;
+use crop_infra::pg::{poll_draft::PollDraftTable as _, Connection};
+
+#[must_use]
+pub struct Reordered {
+    pub drafts: Vec<PollDraft>,
+}
+
+impl Updatable for Reordered {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        for draft in self.drafts.iter() {
+            conn.update_idx(&draft.id().0, draft.idx() as i32)?;
+        }
+        Ok(())
+    }
+}
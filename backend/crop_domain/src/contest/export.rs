@@ -0,0 +1,311 @@
+//! 終了したContestの結果を書き出す
+//!
+//! 各Pollの正解と、各アカウントの回答・正誤・得点・最終スコアをまとめる。
+//! CSVでは、複数の選択肢を `;` で区切って1つの列に書き出す。
+//! 数値で答えるPollの正解と回答は、選択肢とは別の列に書き出す。
+//! 賞品の送付などのために、運営が外部のツールで扱うことを想定している。
+use crate::account::AccountId;
+use crate::contest::{
+    poll::{self, ChoiceName, Poll as _, PollKind},
+    Contest, ContestId, ContestStatus, DetailedContest, Queryable as _, WithPolls,
+};
+use crop_infra::pg::{account::AccountTable as _, Connection};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// 省略した場合は、APIでもtoolsでもjsonで書き出す
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format::Json
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(anyhow::anyhow!("Unknown format : {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Export {
+    contest_id: ContestId,
+    title: String,
+    category: String,
+    status: ContestStatus,
+    polls: Vec<PollResult>,
+    /// スコアの高い順
+    accounts: Vec<AccountResult>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PollResult {
+    idx: usize,
+    title: String,
+    kind: PollKind,
+    /// falseの場合は正解の無いアンケートで、スコアに数えない
+    is_scored: bool,
+    /// 選択肢の順に並んだ正解
+    resolved_choices: Option<Vec<ChoiceName>>,
+    /// 数値で答えるPollの正解
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_value: Option<f64>,
+    answers: Vec<Answer>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Answer {
+    account_id: AccountId,
+    choices: Vec<ChoiceName>,
+    /// 数値で答えるPollへの回答
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<f64>,
+    /// 正解と完全に一致したか。正解が設定されていないPollではnull
+    correct: Option<bool>,
+    /// 部分点を含む得点。正解が設定されていないPollではnull
+    points: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AccountResult {
+    account_id: AccountId,
+    account_name: String,
+    score: f64,
+}
+
+/// CSVの1行。Pollに対する1アカウントの回答を表す。
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    poll_idx: usize,
+    poll_title: &'a str,
+    resolved_choice: Option<String>,
+    resolved_value: Option<f64>,
+    account_id: String,
+    account_name: &'a str,
+    choice: String,
+    value: Option<f64>,
+    correct: Option<bool>,
+    points: Option<f64>,
+    score: f64,
+}
+
+impl Export {
+    /// 終了していないContestは書き出せない
+    pub fn build<C>(contest: &C, account_names: &HashMap<AccountId, String>) -> anyhow::Result<Self>
+    where
+        C: Contest + crate::contest::WithAttrs + WithPolls,
+        <C as WithPolls>::Poll: poll::WithAttrs + poll::WithUserChoices,
+    {
+        if !matches!(
+            contest.status(),
+            ContestStatus::Closed | ContestStatus::Archived
+        ) {
+            return Err(NotFinished.into());
+        }
+
+        let scores = contest.compute_account_scores();
+
+        let polls = contest
+            .polls()
+            .iter()
+            .map(|poll| {
+                let resolved = poll.resolved_choices();
+                let resolved_value = poll.resolved_value();
+                let points = poll.compute_points();
+                let choice_answers =
+                    poll.user_choices()
+                        .iter()
+                        .map(|(account_id, choices)| Answer {
+                            account_id: *account_id,
+                            choices: choices.clone(),
+                            value: None,
+                            correct: resolved.map(|r| r == choices.as_slice()),
+                            points: points.get(account_id).copied(),
+                        });
+                let value_answers = poll.user_values().iter().map(|(account_id, value)| Answer {
+                    account_id: *account_id,
+                    choices: Vec::new(),
+                    value: Some(*value),
+                    correct: resolved_value.map(|r| (r - value).abs() < 1e-9),
+                    points: points.get(account_id).copied(),
+                });
+                let mut answers = choice_answers.chain(value_answers).collect::<Vec<_>>();
+                answers.sort_by_key(|a| a.account_id.0);
+                PollResult {
+                    idx: poll.idx(),
+                    title: poll.title().to_string(),
+                    kind: poll.kind(),
+                    is_scored: poll.is_scored(),
+                    resolved_choices: resolved.map(<[ChoiceName]>::to_vec),
+                    resolved_value,
+                    answers,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // 1問も正解していないアカウントも含める
+        let mut account_ids = polls
+            .iter()
+            .flat_map(|poll| poll.answers.iter().map(|a| a.account_id))
+            .collect::<Vec<_>>();
+        account_ids.sort_by_key(|id| id.0);
+        account_ids.dedup();
+
+        let mut accounts = account_ids
+            .into_iter()
+            .map(|account_id| AccountResult {
+                account_id,
+                account_name: account_names.get(&account_id).cloned().unwrap_or_default(),
+                score: scores.get(&account_id).copied().unwrap_or(0.0),
+            })
+            .collect::<Vec<_>>();
+        accounts.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+
+        Ok(Export {
+            contest_id: *contest.id(),
+            title: contest.title().to_string(),
+            category: contest.category().to_string(),
+            status: contest.status(),
+            polls,
+            accounts,
+        })
+    }
+
+    pub fn to_csv(&self) -> anyhow::Result<String> {
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|a| (a.account_id, a))
+            .collect::<HashMap<_, _>>();
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for poll in self.polls.iter() {
+            for answer in poll.answers.iter() {
+                let account = accounts[&answer.account_id];
+                writer.serialize(CsvRow {
+                    poll_idx: poll.idx,
+                    poll_title: poll.title.as_str(),
+                    resolved_choice: poll.resolved_choices.as_deref().map(join_choices),
+                    resolved_value: poll.resolved_value,
+                    account_id: answer.account_id.0.to_string(),
+                    account_name: account.account_name.as_str(),
+                    choice: join_choices(&answer.choices),
+                    value: answer.value,
+                    correct: answer.correct,
+                    points: answer.points,
+                    score: account.score,
+                })?;
+            }
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
+
+fn join_choices(choices: &[ChoiceName]) -> String {
+    choices
+        .iter()
+        .map(|c| c.0.as_str())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// 開催中のContestを書き出そうとした
+#[derive(Debug)]
+pub struct NotFinished;
+
+impl std::fmt::Display for NotFinished {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Contest is not finished")
+    }
+}
+
+impl std::error::Error for NotFinished {}
+
+pub(crate) fn query(conn: &Connection, contest_id: &ContestId) -> anyhow::Result<Option<Export>> {
+    let contest = match DetailedContest::<poll::DetailedPoll>::query_by_id(conn, contest_id)? {
+        Some(contest) => contest,
+        None => return Ok(None),
+    };
+
+    let mut account_ids = contest
+        .polls()
+        .iter()
+        .flat_map(|poll| poll.user_choices().keys().map(|id| id.0))
+        .collect::<Vec<_>>();
+    account_ids.sort();
+    account_ids.dedup();
+
+    let account_names = conn
+        .query_by_ids(account_ids.as_slice())?
+        .into_iter()
+        .map(|account| (AccountId(account.id), account.name))
+        .collect();
+
+    Export::build(&contest, &account_names).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn to_csv_quotes_fields_with_special_characters() {
+        let account_id = AccountId(Uuid::nil());
+        let export = Export {
+            contest_id: ContestId(Uuid::nil()),
+            title: String::from("contest"),
+            category: String::from("category"),
+            status: ContestStatus::Closed,
+            polls: vec![PollResult {
+                idx: 0,
+                title: String::from("Who wins, \"A\" or B?"),
+                kind: PollKind::Multiple,
+                is_scored: true,
+                resolved_choices: Some(vec![ChoiceName(String::from("A"))]),
+                resolved_value: None,
+                answers: vec![Answer {
+                    account_id,
+                    choices: vec![ChoiceName(String::from("A")), ChoiceName(String::from("B"))],
+                    value: None,
+                    correct: Some(false),
+                    points: Some(0.5),
+                }],
+            }],
+            accounts: vec![AccountResult {
+                account_id,
+                account_name: String::from("line1\nline2"),
+                score: 0.5,
+            }],
+        };
+
+        let csv = export.to_csv().unwrap();
+        let mut lines = csv.splitn(2, '\n');
+        assert_eq!(
+            lines.next().unwrap(),
+            "poll_idx,poll_title,resolved_choice,resolved_value,account_id,account_name,choice,value,correct,points,score"
+        );
+        assert_eq!(
+            lines.next().unwrap(),
+            format!(
+                "0,\"Who wins, \"\"A\"\" or B?\",A,,{},\"line1\nline2\",A;B,,false,0.5,0.5\n",
+                Uuid::nil()
+            )
+        );
+    }
+}
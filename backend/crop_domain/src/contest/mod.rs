@@ -1,7 +1,10 @@
 pub mod comment;
+pub mod draft;
+pub mod export;
 pub mod model;
 pub mod poll;
 pub mod repository;
+pub mod script;
 
 pub use model::*;
 pub use repository::*;
@@ -1,10 +1,10 @@
-use super::{Contest, ContestId, ContestStatus, WithAttrs, WithCurrentPoll};
+use super::{Contest, ContestId, ContestStatus, WithAttrs, WithCurrentPoll, WithPolls};
 use crate::contest::poll::{BriefPoll, DetailedPoll, Poll};
 use crate::contest::Queryable;
 use chrono::{DateTime, Utc};
 use crop_infra::pg::{
-    account_choice::AccountChoiceTable, choice::ChoiceTable, comment::CommentTable,
-    contest::ContestTable, poll::PollTable, Connection,
+    account_choice::AccountChoiceTable, account_numeric_answer::AccountNumericAnswerTable,
+    choice::ChoiceTable, comment::CommentTable, contest::ContestTable, poll::PollTable, Connection,
 };
 use fallible_iterator::{convert, FallibleIterator as _};
 use schemars::JsonSchema;
@@ -61,6 +61,17 @@ where
     }
 }
 
+impl<P> WithPolls for DetailedContest<P>
+where
+    P: Poll,
+{
+    type Poll = P;
+
+    fn _polls(&self) -> &[P] {
+        self.polls.as_slice()
+    }
+}
+
 impl Queryable for DetailedContest<BriefPoll> {
     fn query_by_id(conn: &Connection, id: &ContestId) -> anyhow::Result<Option<Self>> {
         let contest = match ContestTable::query_by_id(conn, &id.0)? {
@@ -102,11 +113,14 @@ impl Queryable for DetailedContest<DetailedPoll> {
                 .map::<anyhow::Result<_>, _>(|poll| {
                     let choices = ChoiceTable::query_by_poll_id(conn, &poll.id)?;
                     let account_choices = AccountChoiceTable::query_by_poll_id(conn, &poll.id)?;
+                    let account_values =
+                        AccountNumericAnswerTable::query_by_poll_id(conn, &poll.id)?;
                     let comments = CommentTable::query_recent_by_poll_id(conn, &poll.id)?;
                     Ok(DetailedPoll::from((
                         poll,
                         choices,
                         account_choices,
+                        account_values,
                         comments,
                     )))
                 }),
@@ -1,8 +1,10 @@
 use crate::account::AccountId;
-use crate::contest::poll::{self, Choice, New as NewPoll, Poll, PollId};
+use crate::contest::poll::{
+    self, AnswerCommitment, Choice, NumericSpec, Poll, PollId, PollKind, PollScoring, PollStatus,
+};
 use chrono::{DateTime, Duration, Utc};
 use schemars::JsonSchema;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -12,6 +14,7 @@ mod brief;
 mod closed;
 mod detailed;
 mod new;
+mod opened;
 mod poll_added;
 
 pub use archived::Archived;
@@ -19,6 +22,7 @@ pub use brief::BriefContest;
 pub use closed::Closed;
 pub use detailed::DetailedContest;
 pub use new::New;
+pub use opened::Opened;
 pub use poll_added::PollAdded;
 
 pub fn new(title: String, category: String, event_start_at: Option<DateTime<Utc>>) -> New {
@@ -27,9 +31,30 @@ pub fn new(title: String, category: String, event_start_at: Option<DateTime<Utc>
         title,
         category,
         event_start_at,
+        status: ContestStatus::Open,
     }
 }
 
+/// Contestに追加するPollの内容
+pub struct NewPoll {
+    pub title: String,
+    pub kind: PollKind,
+    pub scoring: PollScoring,
+    pub duration: Option<Duration>,
+    /// 締め切りのこの時間前から、回答を変更できなくする
+    pub lock_before_close: Option<Duration>,
+    pub choices: Vec<Choice>,
+    /// 数値で答えるPollでは、選択肢の代わりに回答できる値の範囲を渡す
+    pub numeric: Option<NumericSpec>,
+    /// 順位を予想するPollで、何位までを予想するか
+    pub ranking_size: Option<usize>,
+    /// falseの場合は正解の無いアンケートで、コミットメントは付けられない
+    pub is_scored: bool,
+    /// 指定したPollでは、選択肢の回答にポイントを賭けられる
+    pub stake_multiplier: Option<f64>,
+    pub answer_commitment: Option<AnswerCommitment>,
+}
+
 pub trait Contest {
     fn id(&self) -> &ContestId;
 
@@ -86,32 +111,116 @@ pub trait Contest {
 
     /// Contestに新しいPollを追加する。
     /// ContestがOpenのときのみ追加できる。
+    /// 正解が事前に決まっている場合は、そのコミットメントを公開できる。
+    /// コミットメントは単一選択のPollにしか付けられない。
     ///
     /// ## TODO
     /// Contestで現在un-resolvedなPollが存在するときには追加できないようにする
-    fn add_poll(
-        &self,
-        title: String,
-        duration: Option<Duration>,
-        choices: Vec<Choice>,
-    ) -> anyhow::Result<PollAdded<&Self>>
+    fn add_poll(&self, new_poll: NewPoll) -> anyhow::Result<PollAdded<&Self>>
     where
         Self: WithAttrs + WithCurrentPoll,
         <Self as WithCurrentPoll>::Poll: poll::WithAttrs,
     {
+        let NewPoll {
+            title,
+            kind,
+            scoring,
+            duration,
+            lock_before_close,
+            choices,
+            numeric,
+            ranking_size,
+            is_scored,
+            stake_multiplier,
+            answer_commitment,
+        } = new_poll;
         if self.status() != ContestStatus::Open {
             return Err(anyhow::anyhow!("You can't add a poll to non-open contest"));
         }
 
+        match (kind, &numeric) {
+            (PollKind::Numeric, Some(numeric)) => {
+                if !choices.is_empty() {
+                    return Err(anyhow::anyhow!("Numeric poll must not have choices"));
+                }
+                poll::validate_numeric(title.as_str(), duration.as_ref(), numeric, scoring)?;
+            }
+            (PollKind::Numeric, None) => {
+                return Err(anyhow::anyhow!("Numeric poll must have a range"));
+            }
+            (_, Some(_)) => return Err(anyhow::anyhow!("Only numeric poll can have a range")),
+            (_, None) => poll::validate(title.as_str(), duration.as_ref(), choices.as_slice())?,
+        }
+
+        poll::validate_lock(duration.as_ref(), lock_before_close.as_ref())?;
+
+        match (kind, ranking_size) {
+            (PollKind::Ranking, Some(n)) if 0 < n && n <= choices.len() => {}
+            (PollKind::Ranking, _) => {
+                return Err(anyhow::anyhow!(
+                    "Ranking size must be between 1 and the number of choices"
+                ))
+            }
+            (_, Some(_)) => {
+                return Err(anyhow::anyhow!("Only ranking poll can have a ranking size"))
+            }
+            (_, None) => {}
+        }
+
+        match (kind, scoring) {
+            (PollKind::Single, PollScoring::Exact)
+            | (PollKind::Multiple, PollScoring::Exact)
+            | (PollKind::Multiple, PollScoring::Partial)
+            | (PollKind::Numeric, PollScoring::Exact)
+            | (PollKind::Numeric, PollScoring::Tolerance)
+            | (PollKind::Numeric, PollScoring::Closest)
+            | (PollKind::Ranking, PollScoring::Exact)
+            | (PollKind::Ranking, PollScoring::Position)
+            | (PollKind::Ranking, PollScoring::Kendall) => {}
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "{:?} scoring is not available for {:?} poll",
+                    scoring,
+                    kind
+                ))
+            }
+        }
+
+        if kind != PollKind::Single && answer_commitment.is_some() {
+            return Err(anyhow::anyhow!(
+                "Answer commitment is only for single choice polls"
+            ));
+        }
+        if !is_scored && answer_commitment.is_some() {
+            return Err(anyhow::anyhow!("Survey poll has no answer to commit"));
+        }
+        if let Some(multiplier) = stake_multiplier {
+            if !is_scored || kind == PollKind::Numeric {
+                return Err(anyhow::anyhow!(
+                    "Stakes are only for scored polls answered with choices"
+                ));
+            } else if !(multiplier.is_finite() && multiplier >= 1.0) {
+                return Err(anyhow::anyhow!("Stake multiplier must be at least 1"));
+            }
+        }
+
         let idx = self.num_polls() + 1;
 
-        let new_poll = NewPoll {
+        let new_poll = poll::New {
             id: PollId::new(),
             title,
+            kind,
+            scoring,
             created_at: Utc::now(),
             duration,
+            lock_before_close,
             idx,
             choices,
+            numeric,
+            ranking_size,
+            is_scored,
+            stake_multiplier,
+            answer_commitment,
         };
         Ok(PollAdded {
             contest: self,
@@ -119,6 +228,18 @@ pub trait Contest {
         })
     }
 
+    /// UpcomingなContestを開始し、Pollを追加できるようにする
+    fn open(self) -> anyhow::Result<Opened<Self>>
+    where
+        Self: WithAttrs + Sized,
+    {
+        if self.status() != ContestStatus::Upcoming {
+            return Err(anyhow::anyhow!("Contest status is not upcoming"));
+        }
+
+        Ok(Opened { contest: self })
+    }
+
     fn close(self) -> anyhow::Result<Closed<Self>>
     where
         Self: WithAttrs + WithCurrentPoll + Sized,
@@ -128,8 +249,14 @@ pub trait Contest {
             return Err(anyhow::anyhow!("Contest status is not open"));
         }
 
+        // アンケートはResolveしないので、Closeしていれば終了できる
         if let Some(poll) = self.current_poll() {
-            if poll.resolved_choice().is_none() {
+            let finished = match poll.status() {
+                PollStatus::Open => false,
+                PollStatus::Closed => poll.is_resolved() || !poll.is_scored(),
+                PollStatus::Voided => true,
+            };
+            if !finished {
                 return Err(anyhow::anyhow!("Contest has active poll"));
             }
         }
@@ -148,19 +275,26 @@ pub trait Contest {
         Ok(Archived { contest: self })
     }
 
-    /// 各アカウントの正解数を計算する
-    fn compute_account_scores(&self) -> HashMap<AccountId, usize>
+    /// 各アカウントの得点を計算する。1問の満点は1点。
+    /// アンケートと中止されたPollは数えず、得点の無いアカウントは含まない。
+    ///
+    /// 回答に賭けたポイントやマーケットの取引は、得点に含めない。
+    /// これらの払い戻しはResolve時にポイントの残高として記帳するので、
+    /// 得点は残高の多さに関わらず、回答の正しさだけで比べられる。
+    fn compute_account_scores(&self) -> HashMap<AccountId, f64>
     where
         Self: WithPolls,
         <Self as WithPolls>::Poll: poll::WithAttrs + poll::WithUserChoices,
     {
-        self.polls().iter().flat_map(Poll::correct_accounts).fold(
-            HashMap::new(),
-            |mut score_map, account| {
-                *score_map.entry(account).or_insert(0) += 1;
+        self.polls()
+            .iter()
+            .filter(|poll| poll.counts_toward_score())
+            .flat_map(Poll::compute_points)
+            .filter(|(_, points)| *points > 0.0)
+            .fold(HashMap::new(), |mut score_map, (account, points)| {
+                *score_map.entry(account).or_insert(0.0) += points;
                 score_map
-            },
-        )
+            })
     }
 }
 
@@ -186,7 +320,7 @@ pub trait WithPolls: Contest {
     fn _polls(&self) -> &[Self::Poll];
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub struct ContestId(pub Uuid);
 
 impl ContestId {
@@ -245,3 +379,216 @@ where
         C::_current_poll(self)
     }
 }
+
+impl<'a, C> WithPolls for &'a C
+where
+    C: WithPolls,
+{
+    type Poll = C::Poll;
+
+    fn _polls(&self) -> &[Self::Poll] {
+        C::_polls(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::model::{AdminId, AuthenticatedAdmin, Role, Scope};
+    use crate::contest::poll::{ChoiceName, DetailedPoll, Resolution};
+    use crop_infra::pg::{
+        account_choice::QueriedAccountChoice, choice::QueriedChoice, poll::QueriedPoll,
+    };
+
+    /// "a" と "b" の2択で、Close済みのPoll
+    fn poll(
+        status: PollStatus,
+        is_scored: bool,
+        resolved: Option<&str>,
+        answers: &[(AccountId, &str)],
+    ) -> DetailedPoll {
+        staked_poll(status, is_scored, resolved, answers, None)
+    }
+
+    /// `stake_multiplier` を指定すると、全員が10ポイントずつ賭けたPollになる
+    fn staked_poll(
+        status: PollStatus,
+        is_scored: bool,
+        resolved: Option<&str>,
+        answers: &[(AccountId, &str)],
+        stake_multiplier: Option<f64>,
+    ) -> DetailedPoll {
+        let id = Uuid::new_v4();
+        let queried = QueriedPoll {
+            id,
+            contest_id: Uuid::new_v4(),
+            status,
+            title: String::from("poll"),
+            created_at: Utc::now(),
+            duration_sec: None,
+            idx: 1,
+            resolved_at: resolved.map(|_| Utc::now()),
+            resolved_choice_name: resolved.map(String::from),
+            answer_commitment: None,
+            answer_nonce: None,
+            paused_at: None,
+            closed_at: Some(Utc::now()),
+            kind: PollKind::Single,
+            scoring: PollScoring::Exact,
+            resolved_choice_names: resolved.map(|name| vec![String::from(name)]),
+            numeric_min: None,
+            numeric_max: None,
+            numeric_step: None,
+            numeric_tolerance: None,
+            resolved_value: None,
+            ranking_size: None,
+            is_scored,
+            stake_multiplier,
+            lock_before_close_sec: None,
+        };
+        let choices = ["a", "b"]
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| QueriedChoice {
+                poll_id: id,
+                name: String::from(*name),
+                color: String::from("#000000"),
+                idx: idx as i32,
+            })
+            .collect();
+        let answers = answers
+            .iter()
+            .map(|(account, choice)| QueriedAccountChoice {
+                poll_id: id,
+                account_id: account.0,
+                choice_name: String::from(*choice),
+                rank: None,
+                stake: stake_multiplier.map(|_| 10),
+            })
+            .collect();
+        DetailedPoll::from((queried, choices, answers, Vec::new(), Vec::new()))
+    }
+
+    fn contest(polls: Vec<DetailedPoll>) -> DetailedContest<DetailedPoll> {
+        DetailedContest {
+            id: ContestId::new(),
+            status: ContestStatus::Open,
+            title: String::from("contest"),
+            category: String::from("category"),
+            event_start_at: None,
+            polls,
+        }
+    }
+
+    #[test]
+    fn voided_poll_is_not_scored() {
+        let account = AccountId(Uuid::new_v4());
+        let voided = poll(PollStatus::Closed, true, None, &[(account, "a")])
+            .void()
+            .unwrap();
+        assert_eq!(voided.status(), PollStatus::Voided);
+        assert!(!voided.counts_toward_score());
+
+        // 中止の状態だけで除外されることを確かめるため、正解も付けておく
+        let resolved = poll(PollStatus::Closed, true, Some("a"), &[(account, "a")]);
+        let reloaded = poll(PollStatus::Voided, true, Some("a"), &[(account, "a")]);
+        let scores = contest(vec![resolved, reloaded]).compute_account_scores();
+        assert_eq!(scores.get(&account), Some(&1.0));
+    }
+
+    fn admin(role: Role) -> AuthenticatedAdmin {
+        AuthenticatedAdmin {
+            id: AdminId::new(),
+            role,
+            scope: Scope::Full,
+        }
+    }
+
+    #[test]
+    fn re_resolve_recomputes_scores() {
+        let (alice, bob) = (AccountId(Uuid::new_v4()), AccountId(Uuid::new_v4()));
+        let resolved = poll(
+            PollStatus::Closed,
+            true,
+            Some("a"),
+            &[(alice, "a"), (bob, "b")],
+        );
+        let before = contest(vec![resolved.clone()]).compute_account_scores();
+        assert_eq!(before.get(&alice), Some(&1.0));
+        assert_eq!(before.get(&bob), None);
+
+        let re_resolved = resolved
+            .re_resolve(
+                &admin(Role::Superadmin),
+                Resolution::Choices(vec![ChoiceName(String::from("b"))]),
+            )
+            .unwrap();
+        let after = re_resolved.compute_points();
+        assert_eq!(after.get(&alice), Some(&0.0));
+        assert_eq!(after.get(&bob), Some(&1.0));
+    }
+
+    #[test]
+    fn re_resolve_recomputes_stake_payouts() {
+        let (alice, bob) = (AccountId(Uuid::new_v4()), AccountId(Uuid::new_v4()));
+        let resolved = staked_poll(
+            PollStatus::Closed,
+            true,
+            Some("a"),
+            &[(alice, "a"), (bob, "b")],
+            Some(2.0),
+        );
+        let stakes = vec![(alice, 10), (bob, 10)].into_iter().collect();
+        let before = resolved.compute_stake_payouts(&stakes);
+        assert_eq!(before.get(&alice), Some(&20));
+        assert_eq!(before.get(&bob), Some(&0));
+
+        // 保存する時には、この払い戻しで精算し直す
+        let re_resolved = resolved
+            .re_resolve(
+                &admin(Role::Superadmin),
+                Resolution::Choices(vec![ChoiceName(String::from("b"))]),
+            )
+            .unwrap();
+        let after = re_resolved.compute_stake_payouts(&stakes);
+        assert_eq!(after.get(&alice), Some(&0));
+        assert_eq!(after.get(&bob), Some(&20));
+    }
+
+    #[test]
+    fn only_superadmin_can_re_resolve() {
+        let resolved = poll(PollStatus::Closed, true, Some("a"), &[]);
+        let answer = || Resolution::Choices(vec![ChoiceName(String::from("b"))]);
+        for role in [Role::Operator, Role::Moderator].iter() {
+            assert!(resolved
+                .clone()
+                .re_resolve(&admin(*role), answer())
+                .is_err());
+        }
+        assert!(resolved
+            .re_resolve(&admin(Role::Superadmin), answer())
+            .is_ok());
+    }
+
+    #[test]
+    fn survey_poll_is_never_scored() {
+        let account = AccountId(Uuid::new_v4());
+        let survey = poll(PollStatus::Closed, false, None, &[(account, "a")]);
+        let answer = Resolution::Choices(vec![ChoiceName(String::from("a"))]);
+        assert!(survey.clone().resolve(answer, None).is_err());
+
+        // 正解が付いていても数えない
+        let resolved = poll(PollStatus::Closed, false, Some("a"), &[(account, "a")]);
+        assert!(!resolved.counts_toward_score());
+        assert!(contest(vec![resolved]).compute_account_scores().is_empty());
+
+        // アンケートはResolveしなくてもContestを終了できる
+        assert!(contest(vec![survey]).close().is_ok());
+    }
+
+    #[test]
+    fn resolved_poll_can_not_be_voided() {
+        let resolved = poll(PollStatus::Closed, true, Some("a"), &[]);
+        assert!(resolved.void().is_err());
+    }
+}
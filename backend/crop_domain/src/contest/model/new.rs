@@ -9,6 +9,17 @@ pub struct New {
     pub(super) title: String,
     pub(super) category: String,
     pub(super) event_start_at: Option<DateTime<Utc>>,
+    pub(super) status: ContestStatus,
+}
+
+impl New {
+    /// 作成時点ではOpenにせず、後で `Contest::open` する
+    pub fn upcoming(self) -> New {
+        New {
+            status: ContestStatus::Upcoming,
+            ..self
+        }
+    }
 }
 
 impl Contest for New {
@@ -19,7 +30,7 @@ impl Contest for New {
 
 impl WithAttrs for New {
     fn _status(&self) -> ContestStatus {
-        ContestStatus::Upcoming
+        self.status
     }
 
     fn _title(&self) -> &str {
@@ -58,9 +69,7 @@ impl Updatable for New {
         let new_contest = NewContest {
             id: &self.id().0,
             title: self.title(),
-            // TODO
-            // 初期値をUpcomingにして、open処理を追加する
-            status: ContestStatus::Open,
+            status: self.status(),
             category: self.category(),
             event_start_at: self.event_start_at(),
         };
@@ -32,9 +32,23 @@ where
             id: &poll_id,
             contest_id: &self.contest.id().0,
             title: self.poll.title(),
+            kind: self.poll.kind(),
+            scoring: self.poll.scoring(),
             created_at: self.poll.created_at(),
             duration_sec: self.poll.duration().map(|d| d.num_seconds() as i32),
             idx: self.poll.idx as i32,
+            answer_commitment: self.poll.answer_commitment().map(|c| c.hash()),
+            numeric_min: self.poll.numeric().map(|n| n.min),
+            numeric_max: self.poll.numeric().map(|n| n.max),
+            numeric_step: self.poll.numeric().map(|n| n.step),
+            numeric_tolerance: self.poll.numeric().and_then(|n| n.tolerance),
+            ranking_size: self.poll.ranking_size().map(|n| n as i32),
+            is_scored: self.poll.is_scored(),
+            stake_multiplier: self.poll.stake_multiplier(),
+            lock_before_close_sec: self
+                .poll
+                .lock_before_close()
+                .map(|d| d.num_seconds() as i32),
         };
         PollTable::save(conn, new_poll)?;
 
@@ -0,0 +1,119 @@
+//! 正解のコミットメント
+//!
+//! 正解が事前に決まっているPollでは、出題時に `SHA-256(正解のChoice名 || ':' || nonce)` を公開する。
+//! Resolveする時にnonceを公開するので、参加者は出題後に正解が選ばれていないことを確かめられる。
+use super::ChoiceName;
+use data_encoding::HEXLOWER;
+use rand::{thread_rng, Rng as _};
+use ring::digest;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::str::FromStr;
+
+const NONCE_LEN: usize = 16;
+
+/// 推測されないよう、十分に長いランダムな値を使う
+pub fn gen_nonce() -> String {
+    let mut nonce = [0u8; NONCE_LEN];
+    thread_rng().fill(&mut nonce);
+    HEXLOWER.encode(&nonce)
+}
+
+pub fn commit(choice: &ChoiceName, nonce: &str) -> AnswerCommitment {
+    AnswerCommitment {
+        hash: hash(choice, nonce),
+        nonce: None,
+    }
+}
+
+fn hash(choice: &ChoiceName, nonce: &str) -> Vec<u8> {
+    let mut ctx = digest::Context::new(&digest::SHA256);
+    ctx.update(choice.0.as_bytes());
+    ctx.update(b":");
+    ctx.update(nonce.as_bytes());
+    ctx.finish().as_ref().to_vec()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct AnswerCommitment {
+    #[serde(serialize_with = "serialize_hex")]
+    #[schemars(with = "String")]
+    hash: Vec<u8>,
+    /// Resolveされるまでは公開しない
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<String>,
+}
+
+fn serialize_hex<S>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    serializer.serialize_str(HEXLOWER.encode(value).as_str())
+}
+
+impl AnswerCommitment {
+    pub fn hash(&self) -> &[u8] {
+        self.hash.as_slice()
+    }
+
+    pub fn nonce(&self) -> Option<&str> {
+        self.nonce.as_deref()
+    }
+
+    /// 正解とnonceがコミットメントと一致するか確認し、nonceを公開する
+    pub(crate) fn reveal(&self, choice: &ChoiceName, nonce: String) -> anyhow::Result<Self> {
+        if self.nonce.is_some() {
+            return Err(anyhow::anyhow!("Answer is already revealed"));
+        }
+        if hash(choice, nonce.as_str()) != self.hash {
+            return Err(anyhow::anyhow!("Answer does not match the commitment"));
+        }
+        Ok(AnswerCommitment {
+            hash: self.hash.clone(),
+            nonce: Some(nonce),
+        })
+    }
+
+    pub(crate) fn from_queried(hash: Option<Vec<u8>>, nonce: Option<String>) -> Option<Self> {
+        hash.map(|hash| AnswerCommitment { hash, nonce })
+    }
+}
+
+/// 16進数で表したハッシュ値から作成する
+impl FromStr for AnswerCommitment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hash = HEXLOWER.decode(s.to_lowercase().as_bytes())?;
+        if hash.len() != digest::SHA256_OUTPUT_LEN {
+            return Err(anyhow::anyhow!("Answer commitment must be a SHA-256 hash"));
+        }
+        Ok(AnswerCommitment { hash, nonce: None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reveal_requires_matching_choice_and_nonce() {
+        let answer = ChoiceName("red".to_string());
+        let nonce = gen_nonce();
+        let committed = commit(&answer, nonce.as_str());
+
+        let parsed = HEXLOWER
+            .encode(committed.hash())
+            .parse::<AnswerCommitment>();
+        assert_eq!(parsed.unwrap(), committed);
+
+        assert!(committed
+            .reveal(&ChoiceName("blue".to_string()), nonce.clone())
+            .is_err());
+        assert!(committed.reveal(&answer, gen_nonce()).is_err());
+
+        let revealed = committed.reveal(&answer, nonce.clone()).unwrap();
+        assert_eq!(revealed.nonce(), Some(nonce.as_str()));
+        assert!(revealed.reveal(&answer, nonce).is_err());
+    }
+}
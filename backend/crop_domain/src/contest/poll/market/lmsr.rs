@@ -0,0 +1,136 @@
+//! LMSR (Logarithmic Market Scoring Rule) によるマーケットメーカー
+//!
+//! 選択肢 i の発行済み株数を q_i、流動性を b とすると、
+//! マーケットの総コストは C(q) = b ln Σ exp(q_i / b) になる。
+//! 株を買う時は、買う前後のコストの差を支払う。
+//! 各選択肢の価格は C の偏微分で、合計は常に1になる。
+//! 正解の選択肢の株は1株につき1ポイントで払い戻されるので、
+//! 価格はその選択肢が正解になる確率の推定値として読める。
+
+/// 各選択肢の価格。`quantities` と同じ順に並ぶ。
+pub fn prices(liquidity: f64, quantities: &[f64]) -> Vec<f64> {
+    // exp のオーバーフローを避けるため、最大値を引いてから計算する
+    let max = quantities
+        .iter()
+        .copied()
+        .fold(std::f64::NEG_INFINITY, f64::max);
+    let weights = quantities
+        .iter()
+        .map(|q| ((q - max) / liquidity).exp())
+        .collect::<Vec<_>>();
+    let sum = weights.iter().sum::<f64>();
+    weights.into_iter().map(|w| w / sum).collect()
+}
+
+/// マーケットの総コスト C(q)
+pub fn cost(liquidity: f64, quantities: &[f64]) -> f64 {
+    let max = quantities
+        .iter()
+        .copied()
+        .fold(std::f64::NEG_INFINITY, f64::max);
+    let sum = quantities
+        .iter()
+        .map(|q| ((q - max) / liquidity).exp())
+        .sum::<f64>();
+    max + liquidity * sum.ln()
+}
+
+/// 各選択肢の価格の対数。
+/// 価格が小さすぎて `prices` では0になる場合も、有限の値になる。
+fn log_prices(liquidity: f64, quantities: &[f64]) -> Vec<f64> {
+    let max = quantities
+        .iter()
+        .copied()
+        .fold(std::f64::NEG_INFINITY, f64::max);
+    let log_sum = quantities
+        .iter()
+        .map(|q| ((q - max) / liquidity).exp())
+        .sum::<f64>()
+        .ln();
+    quantities
+        .iter()
+        .map(|q| (q - max) / liquidity - log_sum)
+        .collect()
+}
+
+/// `idx` 番目の選択肢に `amount` ポイントを支払った時に得られる株数。
+///
+/// C(q + Δ e_i) - C(q) = amount を Δ について解くと
+/// Δ = b ln(1 + (exp(amount / b) - 1) / p_i) になる。
+/// amount / b が大きいと exp がオーバーフローするので、
+/// Δ = amount + b (ln(1 - (1 - p_i) exp(-amount / b)) - ln p_i) として対数のまま計算する。
+/// 計算できないほど大きな値では、有限でない値を返す。
+pub fn shares_for_cost(liquidity: f64, quantities: &[f64], idx: usize, amount: f64) -> f64 {
+    let log_prices = log_prices(liquidity, quantities);
+    // 1 - p_i は、桁落ちしないよう他の選択肢の価格の合計として求める
+    let others = log_prices
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != idx)
+        .map(|(_, log_price)| log_price.exp())
+        .sum::<f64>();
+    let x = amount / liquidity;
+    amount + liquidity * ((-others * (-x).exp()).ln_1p() - log_prices[idx])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f64 = 1e-9;
+
+    #[test]
+    fn prices_start_uniform_and_sum_to_one() {
+        let initial = prices(10.0, &[0.0, 0.0, 0.0, 0.0]);
+        assert!(initial.iter().all(|p| (p - 0.25).abs() < EPSILON));
+
+        let skewed = prices(10.0, &[30.0, 5.0, 0.0]);
+        assert!((skewed.iter().sum::<f64>() - 1.0).abs() < EPSILON);
+        assert!(skewed[0] > skewed[1] && skewed[1] > skewed[2]);
+    }
+
+    #[test]
+    fn buying_costs_exactly_the_amount_paid() {
+        let liquidity = 20.0;
+        let mut quantities = vec![0.0, 12.0, 3.0];
+        let before = cost(liquidity, &quantities);
+        let price_before = prices(liquidity, &quantities)[2];
+
+        let shares = shares_for_cost(liquidity, &quantities, 2, 7.5);
+        quantities[2] += shares;
+        assert!((cost(liquidity, &quantities) - before - 7.5).abs() < EPSILON);
+        // 買った選択肢の価格は上がり、1株あたりの価格は買う前後の価格の間になる
+        let price_after = prices(liquidity, &quantities)[2];
+        assert!(price_after > price_before);
+        assert!(price_before < 7.5 / shares && 7.5 / shares < price_after);
+    }
+
+    #[test]
+    fn large_amount_does_not_overflow() {
+        // exp(1000) は f64 の範囲を超える
+        let liquidity = 1.0;
+        let quantities = [0.0, 0.0];
+        let shares = shares_for_cost(liquidity, &quantities, 0, 1000.0);
+        assert!(shares.is_finite());
+        // ほぼ確実に正解になる価格まで買うので、1株あたりの価格は1に近づく
+        assert!((shares - (1000.0 + 2f64.ln())).abs() < 1e-6);
+        let after = cost(liquidity, &[shares, 0.0]);
+        assert!((after - cost(liquidity, &quantities) - 1000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn cheap_choice_in_skewed_market_does_not_overflow() {
+        // 価格が小さすぎて0に丸められる選択肢
+        let quantities = [2000.0, 0.0];
+        assert_eq!(prices(1.0, &quantities)[1], 0.0);
+        let shares = shares_for_cost(1.0, &quantities, 1, 1.0);
+        assert!(shares.is_finite() && shares > 0.0);
+    }
+
+    #[test]
+    fn large_quantities_do_not_overflow() {
+        let p = prices(1.0, &[2000.0, 0.0]);
+        assert!((p[0] - 1.0).abs() < EPSILON);
+        assert!(cost(1.0, &[2000.0, 0.0]).is_finite());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod lmsr;
+pub mod model;
+
+pub use model::*;
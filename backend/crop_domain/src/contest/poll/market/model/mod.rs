@@ -0,0 +1,365 @@
+use crate::account::ledger::{Book, Settlement, Transaction, TransactionKind};
+use crate::account::{Account, AccountId};
+use crate::contest::poll::{
+    market::lmsr, AnswerLocked, ChoiceName, PollId, PollKind, PollStatus, WithAttrs,
+};
+use chrono::{DateTime, Utc};
+use crop_infra::pg::{
+    choice::ChoiceTable, market_trade::MarketTradeTable, poll_market::PollMarketTable, Connection,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+
+mod opened;
+mod traded;
+
+pub use opened::Opened;
+pub use traded::Traded;
+
+/// 浮動小数点の丸め誤差を無視するための幅
+const EPSILON: f64 = 1e-9;
+
+/// 流動性と予算の上限。
+/// 株数や払い戻しをポイントとして扱える範囲に収める。
+const MAX_LIQUIDITY: f64 = 1_000_000.0;
+const MAX_BUDGET: f64 = 1_000_000.0;
+
+/// Pollにマーケットを開く。
+/// 正解が1つに決まる、採点されるPollにだけ開ける。
+/// 取引はPollがOpenの間だけ行える。
+pub fn open<P>(poll: &P, liquidity: f64, budget: f64) -> anyhow::Result<Opened>
+where
+    P: WithAttrs,
+{
+    if poll.kind() != PollKind::Single {
+        return Err(anyhow::anyhow!("Market is only for single choice polls"));
+    }
+    if !poll.is_scored() {
+        return Err(anyhow::anyhow!("Survey poll has no answer"));
+    }
+    if poll.status() != PollStatus::Open {
+        return Err(anyhow::anyhow!("Poll is already closed"));
+    }
+    if !(liquidity.is_finite() && liquidity > 0.0) {
+        return Err(anyhow::anyhow!("Liquidity must be positive"));
+    } else if liquidity > MAX_LIQUIDITY {
+        return Err(anyhow::anyhow!(
+            "Liquidity must be at most {}",
+            MAX_LIQUIDITY
+        ));
+    }
+    if !(budget.is_finite() && budget > 0.0) {
+        return Err(anyhow::anyhow!("Budget must be positive"));
+    } else if budget > MAX_BUDGET {
+        return Err(anyhow::anyhow!("Budget must be at most {}", MAX_BUDGET));
+    }
+
+    let mut choices = poll.choices().to_vec();
+    choices.sort_by_key(|c| c.idx);
+    Ok(Opened {
+        market: Market {
+            poll_id: *poll.id(),
+            liquidity,
+            budget,
+            num_trades: 0,
+            choices: choices.into_iter().map(|c| c.name).collect(),
+            trades: Vec::new(),
+        },
+    })
+}
+
+/// Pollの選択肢を取引するマーケット。
+/// 参加者は残高のポイントで選択肢の株を買い、正解の選択肢の株は1株1ポイントで払い戻される。
+/// 払い戻しはContestのスコアとは別に扱う。
+#[derive(Debug, Clone)]
+pub struct Market {
+    poll_id: PollId,
+    /// LMSRの流動性パラメータ。大きいほど価格が動きにくい。
+    liquidity: f64,
+    /// 1アカウントが使えるポイントの上限
+    budget: f64,
+    num_trades: usize,
+    /// 選択肢の順に並ぶ
+    choices: Vec<ChoiceName>,
+    /// 成立した順に並ぶ
+    trades: Vec<Trade>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Trade {
+    account_id: AccountId,
+    choice: ChoiceName,
+    /// 支払ったポイント
+    cost: f64,
+    /// 得た株数
+    shares: f64,
+    created_at: DateTime<Utc>,
+}
+
+/// アカウントの持ち分
+#[derive(Debug, Clone, Default, PartialEq, Serialize, JsonSchema)]
+pub struct Position {
+    /// 支払ったポイントの合計
+    spent: f64,
+    /// 選択肢ごとの保有株数
+    shares: HashMap<ChoiceName, f64>,
+}
+
+impl Market {
+    pub fn poll_id(&self) -> &PollId {
+        &self.poll_id
+    }
+
+    pub fn liquidity(&self) -> f64 {
+        self.liquidity
+    }
+
+    pub fn budget(&self) -> f64 {
+        self.budget
+    }
+
+    pub fn num_trades(&self) -> usize {
+        self.num_trades
+    }
+
+    /// 選択肢ごとの発行済み株数
+    fn quantities(&self) -> Vec<f64> {
+        self.choices
+            .iter()
+            .map(|choice| {
+                self.trades
+                    .iter()
+                    .filter(|t| t.choice == *choice)
+                    .map(|t| t.shares)
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// 選択肢ごとの現在の価格。
+    /// その選択肢が正解になる確率の推定値で、合計は1になる。
+    pub fn prices(&self) -> HashMap<ChoiceName, f64> {
+        self.choices
+            .iter()
+            .cloned()
+            .zip(lmsr::prices(self.liquidity, &self.quantities()))
+            .collect()
+    }
+
+    pub fn position(&self, account_id: &AccountId) -> Position {
+        let mut position = Position::default();
+        for trade in self.trades.iter().filter(|t| t.account_id == *account_id) {
+            position.spent += trade.cost;
+            *position.shares.entry(trade.choice.clone()).or_default() += trade.shares;
+        }
+        position
+    }
+
+    /// `amount` ポイントを支払って、選択肢の株を買う。
+    /// 得られる株数は、その時点の価格から決まる。
+    /// 支払うポイントは残高から引き落とす。
+    /// 回答と同じく、締め切りの直前で回答を変更できない間は取引できない。
+    pub fn trade<P, A>(
+        &self,
+        poll: &P,
+        account: &A,
+        choice: ChoiceName,
+        amount: i64,
+    ) -> anyhow::Result<Traded>
+    where
+        P: WithAttrs,
+        A: Account,
+    {
+        if poll.id() != &self.poll_id {
+            return Err(anyhow::anyhow!("Market is not for this poll"));
+        }
+        if poll.status() != PollStatus::Open {
+            return Err(anyhow::anyhow!("Poll is already closed"));
+        } else if poll.is_answer_locked(&Utc::now()) {
+            return Err(AnswerLocked.into());
+        }
+        if amount <= 0 {
+            return Err(anyhow::anyhow!("Amount must be positive"));
+        }
+        let cost = amount as f64;
+        let idx = self
+            .choices
+            .iter()
+            .position(|c| *c == choice)
+            .ok_or_else(|| anyhow::anyhow!("Choice is not found"))?;
+        let spent = self.position(account.id()).spent;
+        if spent + cost > self.budget + EPSILON {
+            return Err(anyhow::anyhow!(
+                "Budget exceeded : {} of {} points are left",
+                self.budget - spent,
+                self.budget
+            ));
+        }
+
+        let shares = lmsr::shares_for_cost(self.liquidity, &self.quantities(), idx, cost);
+        if !(shares.is_finite() && shares > 0.0) {
+            return Err(anyhow::anyhow!("Amount is too large for this market"));
+        }
+        let trade = Trade {
+            account_id: *account.id(),
+            choice,
+            cost,
+            shares,
+            created_at: Utc::now(),
+        };
+        // 取引数はマーケットごとに一意なので、冪等キーに使える
+        let transaction = Transaction::transfer(
+            TransactionKind::Trade,
+            format!("trade:{}:{}", self.poll_id.0, self.num_trades),
+            Book::Wallet(*account.id()),
+            Book::Issuance,
+            amount,
+        )?
+        .with_poll(self.poll_id);
+        let mut market = self.clone();
+        market.trades.push(trade.clone());
+        market.num_trades += 1;
+        Ok(Traded {
+            num_trades: self.num_trades,
+            trade,
+            transaction,
+            market,
+        })
+    }
+
+    /// アカウントごとの払い戻し。
+    /// Resolveされたら正解の選択肢の株を1株1ポイントで、
+    /// 中止されたら支払ったポイントをそのまま払い戻す。
+    /// 1ポイント未満は切り捨てる。
+    /// どちらでもなければ `None` を返す。
+    pub fn settlement<P>(&self, poll: &P) -> Option<HashMap<AccountId, i64>>
+    where
+        P: WithAttrs,
+    {
+        let mut payouts = HashMap::<AccountId, f64>::new();
+        if poll.status() == PollStatus::Voided {
+            for trade in self.trades.iter() {
+                *payouts.entry(trade.account_id).or_default() += trade.cost;
+            }
+        } else {
+            let resolved = poll.resolved_choice()?;
+            for trade in self.trades.iter() {
+                let payout = payouts.entry(trade.account_id).or_default();
+                if trade.choice == *resolved {
+                    *payout += trade.shares;
+                }
+            }
+        }
+        let payouts = payouts
+            .into_iter()
+            .map(|(account_id, payout)| (account_id, (payout + EPSILON).floor() as i64))
+            .collect();
+        Some(payouts)
+    }
+
+    /// 払い戻しを記帳する取引。
+    /// 精算済みのアカウントには払い戻さないので、何度精算しても二重に払い戻すことはない。
+    pub(crate) fn settle<P>(&self, poll: &P, settlement: &Settlement) -> Vec<Transaction>
+    where
+        P: WithAttrs,
+    {
+        let (kind, prefix) = if poll.status() == PollStatus::Voided {
+            (TransactionKind::Refund, "market_refund")
+        } else {
+            (TransactionKind::Payout, "market_payout")
+        };
+        self.settlement(poll)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, payout)| *payout > 0)
+            .filter_map(|(account_id, payout)| {
+                let key =
+                    settlement.key(format!("{}:{}:{}", prefix, self.poll_id.0, account_id.0))?;
+                // 払い戻しは正なので、取引は必ず作れる
+                let transaction = Transaction::transfer(
+                    kind,
+                    key,
+                    Book::Issuance,
+                    Book::Wallet(account_id),
+                    payout,
+                )
+                .unwrap();
+                Some(transaction.with_poll(self.poll_id))
+            })
+            .collect()
+    }
+}
+
+impl Market {
+    pub(crate) fn query(conn: &Connection, poll_id: &PollId) -> anyhow::Result<Option<Market>> {
+        let market = match PollMarketTable::query_by_poll_id(conn, &poll_id.0)? {
+            Some(market) => market,
+            None => return Ok(None),
+        };
+        let mut choices = ChoiceTable::query_by_poll_id(conn, &poll_id.0)?;
+        choices.sort_by_key(|c| c.idx);
+        let trades = MarketTradeTable::query_by_poll_id(conn, &poll_id.0)?
+            .into_iter()
+            .map(|t| Trade {
+                account_id: AccountId(t.account_id),
+                choice: ChoiceName(t.choice_name),
+                cost: t.cost,
+                shares: t.shares,
+                created_at: t.created_at,
+            })
+            .collect();
+        Ok(Some(Market {
+            poll_id: *poll_id,
+            liquidity: market.liquidity,
+            budget: market.budget,
+            num_trades: market.num_trades as usize,
+            choices: choices.into_iter().map(|c| ChoiceName(c.name)).collect(),
+            trades,
+        }))
+    }
+}
+
+impl Trade {
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn choice(&self) -> &ChoiceName {
+        &self.choice
+    }
+
+    pub fn cost(&self) -> f64 {
+        self.cost
+    }
+
+    pub fn shares(&self) -> f64 {
+        self.shares
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+}
+
+impl Position {
+    pub fn spent(&self) -> f64 {
+        self.spent
+    }
+
+    pub fn shares(&self) -> &HashMap<ChoiceName, f64> {
+        &self.shares
+    }
+}
+
+/// 他の取引が先に成立し、価格が変わっていた
+#[derive(Debug)]
+pub struct Conflict;
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Market was updated by another trade")
+    }
+}
+
+impl std::error::Error for Conflict {}
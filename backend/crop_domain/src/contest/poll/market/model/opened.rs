@@ -0,0 +1,22 @@
+use super::Market;
+use crate::contest::Updatable;
+use crop_infra::pg::{
+    poll_market::{NewPollMarket, PollMarketTable as _},
+    Connection,
+};
+
+#[must_use]
+pub struct Opened {
+    pub market: Market,
+}
+
+impl Updatable for Opened {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let new_market = NewPollMarket {
+            poll_id: &self.market.poll_id.0,
+            liquidity: self.market.liquidity,
+            budget: self.market.budget,
+        };
+        conn.save(new_market)
+    }
+}
@@ -0,0 +1,49 @@
+use super::{Conflict, Market, Trade};
+use crate::account::ledger::{self, Transaction};
+use crate::contest::poll::lock_open;
+use crate::contest::Updatable;
+use crop_infra::pg::{
+    self,
+    market_trade::{MarketTradeTable as _, NewMarketTrade},
+    Connection,
+};
+
+#[must_use]
+pub struct Traded {
+    /// 取引する前の取引数
+    pub(super) num_trades: usize,
+    pub trade: Trade,
+    /// 支払うポイントを残高から引き落とす取引
+    pub(super) transaction: Transaction,
+    /// 取引した後のマーケット
+    pub market: Market,
+}
+
+impl Updatable for Traded {
+    /// 取引を計算した後に他の取引が成立していた場合は、
+    /// 価格が変わっているので `Conflict` エラーになる。
+    /// 残高が足りない場合は `Overdrawn` エラーになり、取引も記録しない。
+    ///
+    /// 取引を計算した後にCloseやResolveされていると、精算の後に取引が残ってしまう。
+    /// そのためPollをロックして、まだ取引できることを確かめ直してから保存する。
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let new_trade = NewMarketTrade {
+            poll_id: &self.market.poll_id.0,
+            account_id: &self.trade.account_id.0,
+            choice_name: self.trade.choice.0.as_str(),
+            cost: self.trade.cost,
+            shares: self.trade.shares,
+            created_at: &self.trade.created_at,
+        };
+        pg::transaction(conn, || {
+            lock_open(conn, &self.market.poll_id)?;
+            if !conn.save(self.num_trades as i32, new_trade)? {
+                return Err(Conflict.into());
+            }
+            if !ledger::post(conn, &self.transaction)? {
+                return Err(Conflict.into());
+            }
+            Ok(())
+        })
+    }
+}
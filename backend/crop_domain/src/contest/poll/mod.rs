@@ -1,3 +1,7 @@
+pub mod commitment;
+pub mod market;
 pub mod model;
+pub mod scoring;
 
+pub use commitment::AnswerCommitment;
 pub use model::*;
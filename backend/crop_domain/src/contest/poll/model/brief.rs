@@ -1,4 +1,8 @@
-use super::{Choice, ChoiceColor, ChoiceName, Poll, PollId, PollStatus, WithAttrs};
+use super::{
+    Choice, ChoiceColor, ChoiceName, NumericSpec, Poll, PollId, PollKind, PollScoring, PollStatus,
+    WithAttrs,
+};
+use crate::contest::poll::AnswerCommitment;
 use chrono::{DateTime, Duration, Utc};
 use crop_infra::pg::{choice::QueriedChoice, poll::QueriedPoll};
 use schemars::JsonSchema;
@@ -9,6 +13,8 @@ pub struct BriefPoll {
     pub(super) id: PollId,
     pub(super) status: PollStatus,
     pub(super) title: String,
+    pub(super) kind: PollKind,
+    pub(super) scoring: PollScoring,
     pub(super) created_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(serialize_with = "serialize_duration")]
@@ -16,10 +22,41 @@ pub struct BriefPoll {
     // https://github.com/GREsau/schemars/issues/15#issuecomment-593006526
     #[schemars(with = "Option<i64>")]
     pub(super) duration: Option<Duration>,
+    /// 締め切りの何秒前から回答を変更できなくするか
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_duration")]
+    #[serde(rename = "lock_before_close_sec")]
+    #[schemars(with = "Option<i64>")]
+    pub(super) lock_before_close: Option<Duration>,
     pub(super) idx: usize,
     pub(super) choices: Vec<Choice>,
+    /// 数値で答えるPollの回答の範囲
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) numeric: Option<NumericSpec>,
+    /// 順位を予想するPollで、何位までを予想するか
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) ranking_size: Option<usize>,
+    /// falseの場合は正解の無いアンケートで、Resolveせずスコアにも数えない
+    pub(super) is_scored: bool,
+    /// 回答にポイントを賭けられるPollで、正解したときに払い戻す倍率
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) stake_multiplier: Option<f64>,
+    /// 単一選択のPollの正解
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(super) resolved_choice: Option<ChoiceName>,
+    /// 複数選択のPollの正解や、順位を予想するPollの実際の順位
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) resolved_choices: Option<Vec<ChoiceName>>,
+    /// 数値で答えるPollの正解
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) resolved_value: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) answer_commitment: Option<AnswerCommitment>,
+    /// タイマーを一時停止した日時
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) paused_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) closed_at: Option<DateTime<Utc>>,
 }
 
 fn serialize_duration<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
@@ -43,6 +80,14 @@ impl WithAttrs for BriefPoll {
         self.status
     }
 
+    fn _kind(&self) -> PollKind {
+        self.kind
+    }
+
+    fn _scoring(&self) -> PollScoring {
+        self.scoring
+    }
+
     fn _title(&self) -> &str {
         self.title.as_str()
     }
@@ -55,6 +100,10 @@ impl WithAttrs for BriefPoll {
         self.duration.as_ref()
     }
 
+    fn _lock_before_close(&self) -> Option<&Duration> {
+        self.lock_before_close.as_ref()
+    }
+
     fn _idx(&self) -> usize {
         self.idx
     }
@@ -63,8 +112,44 @@ impl WithAttrs for BriefPoll {
         self.choices.as_slice()
     }
 
-    fn _resolved_choice(&self) -> Option<&ChoiceName> {
-        self.resolved_choice.as_ref()
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]> {
+        match self.kind {
+            PollKind::Single => self.resolved_choice.as_ref().map(std::slice::from_ref),
+            PollKind::Multiple | PollKind::Ranking => self.resolved_choices.as_deref(),
+            PollKind::Numeric => None,
+        }
+    }
+
+    fn _numeric(&self) -> Option<&NumericSpec> {
+        self.numeric.as_ref()
+    }
+
+    fn _ranking_size(&self) -> Option<usize> {
+        self.ranking_size
+    }
+
+    fn _is_scored(&self) -> bool {
+        self.is_scored
+    }
+
+    fn _stake_multiplier(&self) -> Option<f64> {
+        self.stake_multiplier
+    }
+
+    fn _resolved_value(&self) -> Option<f64> {
+        self.resolved_value
+    }
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment> {
+        self.answer_commitment.as_ref()
+    }
+
+    fn _paused_at(&self) -> Option<&DateTime<Utc>> {
+        self.paused_at.as_ref()
+    }
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>> {
+        self.closed_at.as_ref()
     }
 }
 
@@ -75,10 +160,34 @@ impl From<(QueriedPoll, Vec<QueriedChoice>)> for BriefPoll {
             id: PollId(poll.id),
             status: poll.status,
             title: poll.title,
+            kind: poll.kind,
+            scoring: poll.scoring,
             created_at: poll.created_at,
             duration: poll.duration_sec.map(|s| Duration::seconds(s as i64)),
+            lock_before_close: poll
+                .lock_before_close_sec
+                .map(|s| Duration::seconds(s as i64)),
             idx: poll.idx as usize,
             resolved_choice: poll.resolved_choice_name.map(ChoiceName),
+            resolved_choices: poll
+                .resolved_choice_names
+                .map(|names| names.into_iter().map(ChoiceName).collect()),
+            numeric: NumericSpec::from_queried(
+                poll.numeric_min,
+                poll.numeric_max,
+                poll.numeric_step,
+                poll.numeric_tolerance,
+            ),
+            resolved_value: poll.resolved_value,
+            ranking_size: poll.ranking_size.map(|n| n as usize),
+            is_scored: poll.is_scored,
+            stake_multiplier: poll.stake_multiplier,
+            answer_commitment: AnswerCommitment::from_queried(
+                poll.answer_commitment,
+                poll.answer_nonce,
+            ),
+            paused_at: poll.paused_at,
+            closed_at: poll.closed_at,
             choices: choices
                 .into_iter()
                 .map(|choice| Choice {
@@ -1,27 +1,60 @@
-use crate::account::AccountId;
-use crate::contest::poll::{ChoiceName, Poll};
-use crate::contest::Updatable;
-use crop_infra::pg::{
-    account_choice::{AccountChoiceTable, NewAccountChoice},
-    Connection,
+use super::lock_open;
+use crate::account::{
+    ledger::{self, Conflict, Escrow},
+    AccountId,
 };
+use crate::contest::poll::{ChoiceName, PollKind, WithAttrs};
+use crate::contest::Updatable;
+use crop_infra::pg::{self, account_choice::AccountChoiceTable, Connection};
 
 pub struct ChoiceUpdated<P> {
     pub(super) poll: P,
     pub(super) account_id: AccountId,
-    pub(super) choice: ChoiceName,
+    /// 選択肢の順に並んだ回答。順位を予想するPollでは、上位から順に並ぶ
+    pub(super) choices: Vec<ChoiceName>,
+    /// 回答に賭けるポイント
+    pub(super) stake: Option<i64>,
 }
 
 impl<P> Updatable for ChoiceUpdated<P>
 where
-    P: Poll,
+    P: WithAttrs,
 {
+    /// 賭けるポイントを変更した場合は、差額を残高から預かる、または残高に戻す。
+    /// 残高が足りない場合は `Overdrawn` エラー、
+    /// 同時に他の変更が行われた場合は `Conflict` エラーになり、回答も更新しない。
+    ///
+    /// 回答を確認した後にCloseやResolveされていると、精算の後に賭けが残ってしまう。
+    /// そのためPollをロックして、まだ回答を変更できることを確かめ直してから保存する。
     fn save(&self, conn: &Connection) -> anyhow::Result<()> {
-        let record = NewAccountChoice {
-            poll_id: &self.poll.id().0,
-            account_id: &self.account_id,
-            choice_name: self.choice.0.as_str(),
-        };
-        AccountChoiceTable::upsert(conn, record)
+        pg::transaction(conn, || {
+            lock_open(conn, self.poll.id())?;
+
+            if self.poll.stake_multiplier().is_some() {
+                let escrow = Escrow::query(conn, self.poll.id())?;
+                if let Some(transaction) =
+                    escrow.restake(&self.account_id, self.stake.unwrap_or(0))?
+                {
+                    if !ledger::post(conn, &transaction)? {
+                        return Err(Conflict.into());
+                    }
+                }
+            }
+
+            let names = self
+                .choices
+                .iter()
+                .map(|c| c.0.as_str())
+                .collect::<Vec<_>>();
+            let ranked = self.poll.kind() == PollKind::Ranking;
+            AccountChoiceTable::replace(
+                conn,
+                &self.poll.id().0,
+                &self.account_id,
+                &names,
+                ranked,
+                self.stake,
+            )
+        })
     }
 }
@@ -1,7 +1,9 @@
 use super::{
-    Choice, ChoiceName, Poll, PollId, PollStatus, WithAttrs, WithComments, WithUserChoices,
+    duration_sec, Choice, ChoiceName, NumericSpec, Poll, PollId, PollKind, PollScoring, PollStatus,
+    WithAttrs, WithComments, WithUserChoices,
 };
 use crate::account::AccountId;
+use crate::contest::poll::AnswerCommitment;
 use crate::contest::Updatable;
 use chrono::{DateTime, Duration, Utc};
 use crop_infra::pg::{poll::PollTable, Connection};
@@ -10,6 +12,9 @@ use std::collections::HashMap;
 #[must_use]
 pub struct Closed<P> {
     pub poll: P,
+    pub(crate) closed_at: DateTime<Utc>,
+    /// 一時停止中にCloseした場合は、停止していた時間を加えた制限時間
+    pub(crate) duration: Option<Duration>,
 }
 
 impl<P> Poll for Closed<P>
@@ -29,6 +34,14 @@ where
         PollStatus::Closed
     }
 
+    fn _kind(&self) -> PollKind {
+        self.poll._kind()
+    }
+
+    fn _scoring(&self) -> PollScoring {
+        self.poll._scoring()
+    }
+
     fn _title(&self) -> &str {
         self.poll._title()
     }
@@ -38,7 +51,11 @@ where
     }
 
     fn _duration(&self) -> Option<&Duration> {
-        self.poll._duration()
+        self.duration.as_ref()
+    }
+
+    fn _lock_before_close(&self) -> Option<&Duration> {
+        self.poll._lock_before_close()
     }
 
     fn _idx(&self) -> usize {
@@ -49,8 +66,41 @@ where
         self.poll._choices()
     }
 
-    fn _resolved_choice(&self) -> Option<&ChoiceName> {
-        self.poll._resolved_choice()
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]> {
+        self.poll._resolved_choices()
+    }
+
+    fn _numeric(&self) -> Option<&NumericSpec> {
+        self.poll._numeric()
+    }
+
+    fn _ranking_size(&self) -> Option<usize> {
+        self.poll._ranking_size()
+    }
+
+    fn _is_scored(&self) -> bool {
+        self.poll._is_scored()
+    }
+
+    fn _stake_multiplier(&self) -> Option<f64> {
+        self.poll._stake_multiplier()
+    }
+
+    fn _resolved_value(&self) -> Option<f64> {
+        self.poll._resolved_value()
+    }
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment> {
+        self.poll._answer_commitment()
+    }
+
+    /// 終了したPollのタイマーは止まっている扱いにしない
+    fn _paused_at(&self) -> Option<&DateTime<Utc>> {
+        None
+    }
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>> {
+        Some(&self.closed_at)
     }
 }
 
@@ -58,9 +108,13 @@ impl<P> WithUserChoices for Closed<P>
 where
     P: WithUserChoices,
 {
-    fn _user_choices(&self) -> &HashMap<AccountId, ChoiceName> {
+    fn _user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>> {
         self.poll._user_choices()
     }
+
+    fn _user_values(&self) -> &HashMap<AccountId, f64> {
+        self.poll._user_values()
+    }
 }
 
 impl<P> WithComments for Closed<P>
@@ -76,9 +130,13 @@ where
 
 impl<P> Updatable for Closed<P>
 where
-    P: Poll,
+    P: WithAttrs,
 {
     fn save(&self, conn: &Connection) -> anyhow::Result<()> {
-        PollTable::update_status(conn, &self.id().0, PollStatus::Closed)
+        if self.poll.paused_at().is_some() {
+            // 一時停止中に強制的にCloseした場合は、停止状態を解除しておく
+            PollTable::update_timer(conn, &self.id().0, duration_sec(self.duration())?, None)?;
+        }
+        PollTable::update_closed(conn, &self.id().0, &self.closed_at)
     }
 }
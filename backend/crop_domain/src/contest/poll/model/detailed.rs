@@ -1,13 +1,14 @@
 use super::{
-    BriefPoll, Choice, ChoiceName, Poll, PollId, PollStatus, WithAttrs, WithComments,
-    WithUserChoices,
+    BriefPoll, Choice, ChoiceName, NumericSpec, Poll, PollId, PollKind, PollScoring, PollStatus,
+    WithAttrs, WithComments, WithUserChoices,
 };
 use crate::account::AccountId;
 use crate::contest::comment::BriefComment;
+use crate::contest::poll::AnswerCommitment;
 use chrono::{DateTime, Duration, Utc};
 use crop_infra::pg::{
-    account_choice::QueriedAccountChoice, choice::QueriedChoice, comment::QueriedComment,
-    poll::QueriedPoll,
+    account_choice::QueriedAccountChoice, account_numeric_answer::QueriedAccountNumericAnswer,
+    choice::QueriedChoice, comment::QueriedComment, poll::QueriedPoll,
 };
 use schemars::JsonSchema;
 use serde::Serialize;
@@ -17,7 +18,11 @@ use std::collections::HashMap;
 pub struct DetailedPoll {
     #[serde(flatten)]
     inner: BriefPoll,
-    account_choices: HashMap<AccountId, ChoiceName>,
+    account_choices: HashMap<AccountId, Vec<ChoiceName>>,
+    /// 数値で答えるPollへの回答
+    account_values: HashMap<AccountId, f64>,
+    /// 回答に賭けているポイント
+    account_stakes: HashMap<AccountId, i64>,
     comments: Vec<BriefComment>,
 }
 
@@ -32,6 +37,14 @@ impl WithAttrs for DetailedPoll {
         self.inner._status()
     }
 
+    fn _kind(&self) -> PollKind {
+        self.inner._kind()
+    }
+
+    fn _scoring(&self) -> PollScoring {
+        self.inner._scoring()
+    }
+
     fn _title(&self) -> &str {
         self.inner._title()
     }
@@ -44,6 +57,10 @@ impl WithAttrs for DetailedPoll {
         self.inner._duration()
     }
 
+    fn _lock_before_close(&self) -> Option<&Duration> {
+        self.inner._lock_before_close()
+    }
+
     fn _idx(&self) -> usize {
         self.inner._idx()
     }
@@ -52,15 +69,51 @@ impl WithAttrs for DetailedPoll {
         &self.inner._choices()
     }
 
-    fn _resolved_choice(&self) -> Option<&ChoiceName> {
-        self.inner._resolved_choice()
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]> {
+        self.inner._resolved_choices()
+    }
+
+    fn _numeric(&self) -> Option<&NumericSpec> {
+        self.inner._numeric()
+    }
+
+    fn _ranking_size(&self) -> Option<usize> {
+        self.inner._ranking_size()
+    }
+
+    fn _is_scored(&self) -> bool {
+        self.inner._is_scored()
+    }
+
+    fn _stake_multiplier(&self) -> Option<f64> {
+        self.inner._stake_multiplier()
+    }
+
+    fn _resolved_value(&self) -> Option<f64> {
+        self.inner._resolved_value()
+    }
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment> {
+        self.inner._answer_commitment()
+    }
+
+    fn _paused_at(&self) -> Option<&DateTime<Utc>> {
+        self.inner._paused_at()
+    }
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>> {
+        self.inner._closed_at()
     }
 }
 
 impl WithUserChoices for DetailedPoll {
-    fn _user_choices(&self) -> &HashMap<AccountId, ChoiceName> {
+    fn _user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>> {
         &self.account_choices
     }
+
+    fn _user_values(&self) -> &HashMap<AccountId, f64> {
+        &self.account_values
+    }
 }
 
 impl WithComments for DetailedPoll {
@@ -76,6 +129,7 @@ impl
         QueriedPoll,
         Vec<QueriedChoice>,
         Vec<QueriedAccountChoice>,
+        Vec<QueriedAccountNumericAnswer>,
         Vec<QueriedComment>,
     )> for DetailedPoll
 {
@@ -84,18 +138,49 @@ impl
             QueriedPoll,
             Vec<QueriedChoice>,
             Vec<QueriedAccountChoice>,
+            Vec<QueriedAccountNumericAnswer>,
             Vec<QueriedComment>,
         ),
     ) -> Self {
-        let (poll, choices, account_choices, comments) = queried;
+        let (poll, choices, account_choices, account_values, comments) = queried;
         let brief_poll = BriefPoll::from((poll, choices));
+        let account_stakes = account_choices
+            .iter()
+            .filter_map(|record| Some((AccountId(record.account_id), record.stake?)))
+            .collect();
         DetailedPoll {
+            account_choices: group_account_choices(&brief_poll.choices, account_choices),
+            account_stakes,
             inner: brief_poll,
-            account_choices: account_choices
+            account_values: account_values
                 .into_iter()
-                .map(|record| (AccountId(record.account_id), ChoiceName(record.choice_name)))
+                .map(|record| (AccountId(record.account_id), record.value))
                 .collect(),
             comments: comments.into_iter().map(BriefComment::from).collect(),
         }
     }
 }
+
+/// アカウントごとに回答をまとめ、選択肢の順に並べる。
+/// 順位を予想するPollの回答は、上位から順に並べる。
+fn group_account_choices(
+    choices: &[Choice],
+    records: Vec<QueriedAccountChoice>,
+) -> HashMap<AccountId, Vec<ChoiceName>> {
+    let mut grouped = HashMap::<AccountId, Vec<(Option<i32>, ChoiceName)>>::new();
+    for record in records {
+        grouped
+            .entry(AccountId(record.account_id))
+            .or_default()
+            .push((record.rank, ChoiceName(record.choice_name)));
+    }
+    let idx_of = |name: &ChoiceName| choices.iter().position(|c| c.name == *name);
+    grouped
+        .into_iter()
+        .map(|(account_id, mut answer)| {
+            answer.sort_by_key(|(rank, name)| (*rank, idx_of(name)));
+            let answer = answer.into_iter().map(|(_, name)| name).collect();
+            (account_id, answer)
+        })
+        .collect()
+}
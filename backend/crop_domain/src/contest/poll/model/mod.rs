@@ -1,9 +1,13 @@
 use crate::account::{Account, AccountId};
+use crate::admin::model::{Admin, Permission};
 use crate::contest::comment::{BriefComment, Comment, CommentId};
+use crate::contest::poll::{scoring, AnswerCommitment};
 use chrono::{DateTime, Duration, Utc};
+use crop_infra::pg::{poll::PollTable, Connection};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use uuid::Uuid;
 
 mod brief;
@@ -11,18 +15,34 @@ mod choice_updated;
 mod closed;
 mod comment_added;
 mod detailed;
+mod extended;
 mod new;
+mod paused;
+mod re_resolved;
+mod reopened;
 mod resolved;
+mod resumed;
+mod stakes_settled;
+mod value_updated;
+mod voided;
 
 pub use brief::BriefPoll;
 pub use choice_updated::ChoiceUpdated;
 pub use closed::Closed;
 pub use comment_added::CommentAdded;
 pub use detailed::DetailedPoll;
+pub use extended::Extended;
 pub use new::New;
-pub use resolved::Resolved;
+pub use paused::Paused;
+pub use re_resolved::ReResolved;
+pub use reopened::Reopened;
+pub use resolved::{Resolution, Resolved};
+pub use resumed::Resumed;
+pub use stakes_settled::StakesSettled;
+pub use value_updated::ValueUpdated;
+pub use voided::Voided;
 
-pub use crop_infra::pg::types::PollStatus;
+pub use crop_infra::pg::types::{PollKind, PollScoring, PollStatus};
 
 pub trait Poll {
     fn id(&self) -> &PollId;
@@ -34,6 +54,22 @@ pub trait Poll {
         self._status()
     }
 
+    /// 単一選択か、複数選択か
+    fn kind(&self) -> PollKind
+    where
+        Self: WithAttrs,
+    {
+        self._kind()
+    }
+
+    /// 複数選択のPollの採点方法
+    fn scoring(&self) -> PollScoring
+    where
+        Self: WithAttrs,
+    {
+        self._scoring()
+    }
+
     fn title(&self) -> &str
     where
         Self: WithAttrs,
@@ -57,6 +93,14 @@ pub trait Poll {
         self._duration()
     }
 
+    /// 締め切りの何秒前から回答を変更できなくするか
+    fn lock_before_close(&self) -> Option<&Duration>
+    where
+        Self: WithAttrs,
+    {
+        self._lock_before_close()
+    }
+
     fn idx(&self) -> usize
     where
         Self: WithAttrs,
@@ -71,45 +115,225 @@ pub trait Poll {
         self._choices()
     }
 
+    /// 単一選択のPollの正解
     fn resolved_choice(&self) -> Option<&ChoiceName>
     where
         Self: WithAttrs,
     {
-        self._resolved_choice()
+        match self.kind() {
+            PollKind::Single => self.resolved_choices().and_then(|c| c.first()),
+            PollKind::Multiple | PollKind::Numeric | PollKind::Ranking => None,
+        }
+    }
+
+    /// Pollの正解。単一選択のPollでは、要素は1つだけ。
+    /// 順位を予想するPollでは、実際の上位から順に並ぶ。
+    fn resolved_choices(&self) -> Option<&[ChoiceName]>
+    where
+        Self: WithAttrs,
+    {
+        self._resolved_choices()
+    }
+
+    /// 数値で答えるPollの回答の範囲
+    fn numeric(&self) -> Option<&NumericSpec>
+    where
+        Self: WithAttrs,
+    {
+        self._numeric()
+    }
+
+    /// 数値で答えるPollの正解
+    fn resolved_value(&self) -> Option<f64>
+    where
+        Self: WithAttrs,
+    {
+        self._resolved_value()
+    }
+
+    /// 順位を予想するPollで、何位までを予想するか
+    fn ranking_size(&self) -> Option<usize>
+    where
+        Self: WithAttrs,
+    {
+        self._ranking_size()
+    }
+
+    /// falseの場合は正解の無いアンケートで、Resolveせずスコアにも数えない
+    fn is_scored(&self) -> bool
+    where
+        Self: WithAttrs,
+    {
+        self._is_scored()
+    }
+
+    /// 回答にポイントを賭けられるPollで、正解したときに払い戻す倍率
+    fn stake_multiplier(&self) -> Option<f64>
+    where
+        Self: WithAttrs,
+    {
+        self._stake_multiplier()
+    }
+
+    /// Contestのスコアに数えるPollか。アンケートと、中止されたPollは数えない
+    fn counts_toward_score(&self) -> bool
+    where
+        Self: WithAttrs,
+    {
+        self.is_scored() && self.status() != PollStatus::Voided
+    }
+
+    fn is_resolved(&self) -> bool
+    where
+        Self: WithAttrs,
+    {
+        self.resolved_choices().is_some() || self.resolved_value().is_some()
+    }
+
+    fn resolution(&self) -> Option<Resolution>
+    where
+        Self: WithAttrs,
+    {
+        match (self.resolved_choices(), self.resolved_value()) {
+            (Some(choices), _) => Some(Resolution::Choices(choices.to_vec())),
+            (None, Some(value)) => Some(Resolution::Value(value)),
+            (None, None) => None,
+        }
+    }
+
+    /// 出題時に公開した、正解のコミットメント
+    fn answer_commitment(&self) -> Option<&AnswerCommitment>
+    where
+        Self: WithAttrs,
+    {
+        self._answer_commitment()
+    }
+
+    /// タイマーを一時停止した日時
+    fn paused_at(&self) -> Option<&DateTime<Utc>>
+    where
+        Self: WithAttrs,
+    {
+        self._paused_at()
+    }
+
+    /// 制限時間で自動的にCloseされる日時。
+    /// 制限時間が無いPollや、一時停止中のPollでは決まらない。
+    fn closes_at(&self) -> Option<DateTime<Utc>>
+    where
+        Self: WithAttrs,
+    {
+        if self.paused_at().is_some() {
+            return None;
+        }
+        self.duration().map(|d| *self.created_at() + *d)
+    }
+
+    /// 回答を変更できなくなる日時。
+    /// 一時停止中は、停止した日時から時間が進んでいないものとして扱う。
+    /// そのため、締め切りと同じく `created_at` と `duration` から決まる。
+    fn locks_at(&self) -> Option<DateTime<Utc>>
+    where
+        Self: WithAttrs,
+    {
+        match (self.duration(), self.lock_before_close()) {
+            (Some(duration), Some(lock)) => Some(*self.created_at() + *duration - *lock),
+            _ => None,
+        }
+    }
+
+    /// `now` の時点で、回答を変更できなくなっているか
+    fn is_answer_locked(&self, now: &DateTime<Utc>) -> bool
+    where
+        Self: WithAttrs,
+    {
+        let now = self.paused_at().unwrap_or(now);
+        self.locks_at().map_or(false, |locks_at| locks_at <= *now)
+    }
+
+    /// Pollが最後にCloseされた日時
+    fn closed_at(&self) -> Option<&DateTime<Utc>>
+    where
+        Self: WithAttrs,
+    {
+        self._closed_at()
     }
 
-    fn user_choices(&self) -> &HashMap<AccountId, ChoiceName>
+    fn user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>>
     where
         Self: WithUserChoices,
     {
         self._user_choices()
     }
 
-    fn correct_accounts<'a>(&'a self) -> Box<dyn Iterator<Item = AccountId> + 'a>
+    /// 数値で答えるPollへの回答
+    fn user_values(&self) -> &HashMap<AccountId, f64>
+    where
+        Self: WithUserChoices,
+    {
+        self._user_values()
+    }
+
+    /// Resolve済みのPollで、各アカウントが得た点数を計算する。
+    /// 未回答のアカウントは含まない。
+    fn compute_points(&self) -> HashMap<AccountId, f64>
     where
         Self: WithAttrs + WithUserChoices,
     {
-        let iter = self
-            .resolved_choice()
-            .into_iter()
-            .flat_map(move |resolved| {
-                self.user_choices()
-                    .iter()
-                    .filter_map(move |(account, choice)| {
-                        if choice == resolved {
-                            Some(*account)
-                        } else {
-                            None
-                        }
-                    })
-            });
-        Box::new(iter)
+        if let Some(resolved) = self.resolved_value() {
+            let tolerance = self.numeric().and_then(|n| n.tolerance);
+            return scoring::score_numeric(self.scoring(), tolerance, resolved, self.user_values());
+        }
+
+        let resolved = match self.resolved_choices() {
+            Some(resolved) => resolved,
+            None => return HashMap::new(),
+        };
+        self.user_choices()
+            .iter()
+            .map(|(account, choices)| {
+                let points = match self.kind() {
+                    PollKind::Ranking => scoring::score_ranking(self.scoring(), resolved, choices),
+                    kind => scoring::score(kind, self.scoring(), resolved, choices),
+                };
+                (*account, points)
+            })
+            .collect()
+    }
+
+    /// Resolve済みのPollで、各アカウントに払い戻すポイントを計算する。
+    /// 得点は `compute_points` と同じで、賭けていないアカウントは含まない。
+    fn compute_stake_payouts(&self, stakes: &HashMap<AccountId, i64>) -> HashMap<AccountId, i64>
+    where
+        Self: WithAttrs + WithUserChoices,
+    {
+        let multiplier = match self.stake_multiplier() {
+            Some(multiplier) => multiplier,
+            None => return HashMap::new(),
+        };
+        let points = self.compute_points();
+        stakes
+            .iter()
+            .map(|(account, stake)| {
+                let points = points.get(account).copied().unwrap_or(0.0);
+                (*account, scoring::stake_payout(*stake, multiplier, points))
+            })
+            .collect()
     }
 
     fn compute_stats(&self) -> Stats
     where
         Self: WithAttrs + WithUserChoices,
     {
+        if let Some(numeric) = self.numeric() {
+            return Stats {
+                total_votes: self.user_values().len(),
+                vote_per_choice: None,
+                vote_per_rank: None,
+                histogram: Some(numeric.histogram(self.user_values().values())),
+            };
+        }
+
         let mut vote_per_choice = self
             .choices()
             .iter()
@@ -119,11 +343,29 @@ pub trait Poll {
         // 各Choiceの総得票数を計算
         self.user_choices()
             .values()
+            .flatten()
             .for_each(|choice| *vote_per_choice.get_mut(choice).unwrap() += 1);
 
+        // 順位を予想するPollでは、各Choiceが何位に選ばれたかも数える
+        let vote_per_rank = self.ranking_size().map(|ranking_size| {
+            let mut vote_per_rank = self
+                .choices()
+                .iter()
+                .map(|c| (c.name.clone(), vec![0; ranking_size]))
+                .collect::<HashMap<ChoiceName, Vec<usize>>>();
+            for answer in self.user_choices().values() {
+                for (rank, choice) in answer.iter().enumerate() {
+                    vote_per_rank.get_mut(choice).unwrap()[rank] += 1;
+                }
+            }
+            vote_per_rank
+        });
+
         Stats {
             total_votes: self.user_choices().len(),
-            vote_per_choice,
+            vote_per_choice: Some(vote_per_choice),
+            vote_per_rank,
+            histogram: None,
         }
     }
 
@@ -142,57 +384,406 @@ pub trait Poll {
             return Err(anyhow::anyhow!("Poll is not open"));
         }
 
-        if let Some(duration) = self.duration() {
-            if Utc::now() < (*self.created_at() + *duration) {
+        if self.paused_at().is_some() {
+            return Err(anyhow::anyhow!("Poll is paused"));
+        }
+
+        if let Some(closes_at) = self.closes_at() {
+            if Utc::now() < closes_at {
                 // まだ開催期間が終わっていない
                 return Err(anyhow::anyhow!("Before closing time"));
             }
         }
 
-        Ok(Closed { poll: self })
+        let duration = self.duration().copied();
+        Ok(Closed {
+            poll: self,
+            closed_at: Utc::now(),
+            duration,
+        })
+    }
+
+    /// 制限時間や一時停止に関わらず、Adminの判断でCloseする。
+    /// 一時停止中であれば、再開した時と同じく停止していた時間だけ制限時間を延ばす。
+    /// そうしないと、Openに戻した時に停止していた時間が使われたことになってしまう。
+    fn force_close(self) -> anyhow::Result<Closed<Self>>
+    where
+        Self: WithAttrs + Sized,
+    {
+        if self.status() != PollStatus::Open {
+            return Err(anyhow::anyhow!("Poll is not open"));
+        }
+
+        let closed_at = Utc::now();
+        let duration = match (self.duration(), self.paused_at()) {
+            (Some(duration), Some(paused_at)) => Some(*duration + (closed_at - *paused_at)),
+            (duration, _) => duration.copied(),
+        };
+        Ok(Closed {
+            poll: self,
+            closed_at,
+            duration,
+        })
     }
 
-    fn resolve(self, choice: ChoiceName) -> anyhow::Result<Resolved<Self>>
+    /// 誤ってCloseしたPollをOpenに戻す。Resolveや中止をした後は戻せない。
+    ///
+    /// `duration` を指定した場合は、今からその長さだけOpenにする。
+    /// 省略した場合は、Closeした時点の残り時間でOpenにする。
+    /// 回答を変更できなくする時間は、Openにする長さより短くする必要がある。
+    fn reopen(self, duration: Option<Duration>) -> anyhow::Result<Reopened<Self>>
+    where
+        Self: WithAttrs + Sized,
+    {
+        if self.status() != PollStatus::Closed {
+            return Err(anyhow::anyhow!("Poll is not closed"));
+        } else if self.is_resolved() {
+            return Err(anyhow::anyhow!("Poll is already resolved"));
+        }
+
+        let now = Utc::now();
+        let remaining = match (duration, self.duration()) {
+            (Some(duration), _) => {
+                if duration <= Duration::zero() {
+                    return Err(anyhow::anyhow!("Poll duration must be positive"));
+                }
+                Some(duration)
+            }
+            (None, Some(duration)) => {
+                let closed_at = self
+                    .closed_at()
+                    .ok_or_else(|| anyhow::anyhow!("Closing time of this poll is unknown"))?;
+                let remaining = *self.created_at() + *duration - *closed_at;
+                if remaining <= Duration::zero() {
+                    return Err(anyhow::anyhow!("Poll has no remaining time"));
+                }
+                Some(remaining)
+            }
+            // 制限時間の無いPollは、そのまま制限時間無しで再開する
+            (None, None) => None,
+        };
+        // 再開した直後から回答を変更できない状態にはしない
+        validate_lock(remaining.as_ref(), self.lock_before_close())?;
+
+        // 締め切りが now + remaining になるよう、作成時からの長さに直す
+        let duration = remaining.map(|remaining| now + remaining - *self.created_at());
+        Ok(Reopened {
+            poll: self,
+            duration,
+        })
+    }
+
+    /// 制限時間を延長する。
+    /// 一時停止中でも延長できる。
+    /// 一度に延長できるのは `MAX_EXTENSION_SEC` 秒まで。
+    fn extend(self, by: Duration) -> anyhow::Result<Extended<Self>>
+    where
+        Self: WithAttrs + Sized,
+    {
+        if self.status() != PollStatus::Open {
+            return Err(anyhow::anyhow!("Poll is not open"));
+        } else if by <= Duration::zero() {
+            return Err(anyhow::anyhow!("Extension must be positive"));
+        } else if by > Duration::seconds(MAX_EXTENSION_SEC) {
+            return Err(anyhow::anyhow!("Extension is too long"));
+        }
+        let duration = match self.duration() {
+            Some(duration) => *duration + by,
+            None => return Err(anyhow::anyhow!("Poll has no time limit")),
+        };
+        duration_sec(Some(&duration))?;
+        if let Some(closes_at) = self.closes_at() {
+            if closes_at <= Utc::now() {
+                // 自動Closeのタイマーが既に動いている
+                return Err(anyhow::anyhow!("Poll is already past closing time"));
+            }
+        }
+
+        Ok(Extended {
+            poll: self,
+            duration,
+        })
+    }
+
+    fn pause(self) -> anyhow::Result<Paused<Self>>
+    where
+        Self: WithAttrs + Sized,
+    {
+        if self.status() != PollStatus::Open {
+            return Err(anyhow::anyhow!("Poll is not open"));
+        } else if self.paused_at().is_some() {
+            return Err(anyhow::anyhow!("Poll is already paused"));
+        }
+        let now = Utc::now();
+        match self.closes_at() {
+            None => return Err(anyhow::anyhow!("Poll has no time limit")),
+            Some(closes_at) if closes_at <= now => {
+                return Err(anyhow::anyhow!("Poll is already past closing time"))
+            }
+            Some(_) => {}
+        }
+
+        Ok(Paused {
+            poll: self,
+            paused_at: now,
+        })
+    }
+
+    /// 一時停止していた時間だけ、制限時間を延ばして再開する
+    fn resume(self) -> anyhow::Result<Resumed<Self>>
+    where
+        Self: WithAttrs + Sized,
+    {
+        if self.status() != PollStatus::Open {
+            return Err(anyhow::anyhow!("Poll is not open"));
+        }
+        let paused_for = match self.paused_at() {
+            Some(paused_at) => Utc::now() - *paused_at,
+            None => return Err(anyhow::anyhow!("Poll is not paused")),
+        };
+        // 一時停止できるのは制限時間があるPollだけ
+        let duration = match self.duration() {
+            Some(duration) => *duration + paused_for,
+            None => return Err(anyhow::anyhow!("Poll has no time limit")),
+        };
+
+        Ok(Resumed {
+            poll: self,
+            duration,
+        })
+    }
+
+    /// 正解のコミットメントがあるPollでは、一致するnonceが必要
+    fn resolve(
+        self,
+        resolution: Resolution,
+        nonce: Option<String>,
+    ) -> anyhow::Result<Resolved<Self>>
     where
         Self: WithAttrs + Sized,
     {
         if self.status() != PollStatus::Closed {
             // CloseしてないPollはResolveできない
             // まずCloseする必要がある
-            Err(anyhow::anyhow!("Poll is not closed"))
-        } else if self.resolved_choice().is_some() {
+            return Err(anyhow::anyhow!("Poll is not closed"));
+        } else if !self.is_scored() {
+            return Err(anyhow::anyhow!("Survey poll has no answer"));
+        } else if self.is_resolved() {
+            return Err(anyhow::anyhow!("Poll is already resolved"));
+        }
+        let resolution = self.validate_resolution(resolution)?;
+
+        // 正解のコミットメントは単一選択のPollにしか付けられない
+        let commitment = match (self.answer_commitment(), nonce, &resolution) {
+            (Some(commitment), Some(nonce), Resolution::Choices(choices)) => {
+                Some(commitment.reveal(&choices[0], nonce)?)
+            }
+            (Some(_), None, _) => return Err(anyhow::anyhow!("Answer nonce is required")),
+            (_, Some(_), _) => return Err(anyhow::anyhow!("Poll has no answer commitment")),
+            (None, None, _) => None,
+        };
+        Ok(Resolved {
+            poll: self,
+            resolved: resolution,
+            commitment,
+        })
+    }
+
+    /// 誤ってResolveしたPollの正解を訂正する。
+    /// 正解のコミットメントとは一致しなくなりうるが、訂正の記録は監査ログに残る。
+    /// 全員のスコアが変わるため、Superadminしか訂正できない。
+    /// 賭けや取引のあったPollは、保存する時に訂正前の精算を取り消して精算し直す。
+    fn re_resolve<A>(self, admin: &A, resolution: Resolution) -> anyhow::Result<ReResolved<Self>>
+    where
+        Self: WithAttrs + Sized,
+        A: Admin,
+    {
+        if !admin.has_permission(Permission::ReResolvePoll) {
+            return Err(anyhow::anyhow!("Only superadmin can re-resolve a poll"));
+        }
+        let resolution = self.validate_resolution(resolution)?;
+        match self.resolution() {
+            None => return Err(anyhow::anyhow!("Poll is not resolved yet")),
+            Some(resolved) if resolved == resolution => {
+                return Err(anyhow::anyhow!("Given answer is already the resolved one"))
+            }
+            Some(_) => {}
+        }
+
+        Ok(ReResolved {
+            poll: self,
+            resolved: resolution,
+        })
+    }
+
+    /// 予想の対象が中止になった場合などに、採点せずにPollを終了する。
+    /// Resolve済みのPollは中止できない。
+    fn void(self) -> anyhow::Result<Voided<Self>>
+    where
+        Self: WithAttrs + Sized,
+    {
+        if self.status() == PollStatus::Voided {
+            Err(anyhow::anyhow!("Poll is already voided"))
+        } else if self.is_resolved() {
             Err(anyhow::anyhow!("Poll is already resolved"))
-        } else if self.choices().iter().find(|c| c.name == choice).is_none() {
-            Err(anyhow::anyhow!("Given choice is not a part of this poll"))
         } else {
-            Ok(Resolved {
-                poll: self,
-                resolved: choice,
-            })
+            Ok(Voided { poll: self })
+        }
+    }
+
+    /// Resolveまたは中止したPollで、賭けられたポイントやマーケットの取引を精算する。
+    /// 精算の取引は冪等なので、何度精算しても二重に払い戻すことはない。
+    /// 正解を訂正した場合は、`ReResolved` を保存する時に精算し直す。
+    fn settle_stakes(self) -> anyhow::Result<StakesSettled<Self>>
+    where
+        Self: WithAttrs + WithUserChoices + Sized,
+    {
+        if self.status() != PollStatus::Voided && !self.is_resolved() {
+            return Err(anyhow::anyhow!("Poll is neither resolved nor voided"));
         }
+        Ok(StakesSettled { poll: self })
     }
 
+    /// 回答を変更する。
+    /// 賭けられるPollでは、回答にポイントを賭けられる。
+    /// 賭けるポイントは回答と同じく置き換えるので、 `None` の場合は賭けていたポイントが残高に戻る。
     fn update_account_choice<A>(
         self,
         account: &A,
-        choice: ChoiceName,
+        choices: Vec<ChoiceName>,
+        stake: Option<i64>,
     ) -> anyhow::Result<ChoiceUpdated<Self>>
     where
         Self: WithAttrs + Sized,
         A: Account,
     {
         if self.status() != PollStatus::Open {
-            // OpenしていないPollで、選択を変更することはできない
-            Err(anyhow::anyhow!("Poll is already closed"))
-        } else if self.choices().iter().find(|c| c.name == choice).is_none() {
-            Err(anyhow::anyhow!("Given choice is not a part of this poll"))
-        } else {
-            Ok(ChoiceUpdated {
-                poll: self,
-                account_id: *account.id(),
-                choice,
-            })
+            // OpenしていないPollで、選択や賭けを変更することはできない
+            return Err(anyhow::anyhow!("Poll is already closed"));
+        } else if self.is_answer_locked(&Utc::now()) {
+            return Err(AnswerLocked.into());
+        }
+        let choices = self.validate_selection(choices)?;
+        match stake {
+            Some(_) if self.stake_multiplier().is_none() => {
+                return Err(anyhow::anyhow!("This poll does not accept stakes"))
+            }
+            Some(stake) if stake <= 0 => return Err(anyhow::anyhow!("Stake must be positive")),
+            _ => {}
+        }
+        Ok(ChoiceUpdated {
+            poll: self,
+            account_id: *account.id(),
+            choices,
+            stake,
+        })
+    }
+
+    /// 数値で答えるPollに回答する
+    fn update_account_value<A>(self, account: &A, value: f64) -> anyhow::Result<ValueUpdated<Self>>
+    where
+        Self: WithAttrs + Sized,
+        A: Account,
+    {
+        if self.status() != PollStatus::Open {
+            return Err(anyhow::anyhow!("Poll is already closed"));
+        } else if self.is_answer_locked(&Utc::now()) {
+            return Err(AnswerLocked.into());
+        }
+        let value = self.validate_value(value)?;
+        Ok(ValueUpdated {
+            poll: self,
+            account_id: *account.id(),
+            value,
+        })
+    }
+
+    fn validate_resolution(&self, resolution: Resolution) -> anyhow::Result<Resolution>
+    where
+        Self: WithAttrs,
+    {
+        match resolution {
+            Resolution::Choices(choices) => {
+                self.validate_selection(choices).map(Resolution::Choices)
+            }
+            Resolution::Value(value) => self.validate_value(value).map(Resolution::Value),
+        }
+    }
+
+    /// 回答や正解として選ばれた選択肢を確認し、選択肢の順に並べる。
+    /// 単一選択のPollでは1つだけ、複数選択のPollでは1つ以上選ぶ必要がある。
+    /// 順位を予想するPollでは、並び順をそのまま保つ。
+    fn validate_selection(&self, choices: Vec<ChoiceName>) -> anyhow::Result<Vec<ChoiceName>>
+    where
+        Self: WithAttrs,
+    {
+        if self.kind() == PollKind::Numeric {
+            return Err(anyhow::anyhow!("This poll is answered with a number"));
+        }
+
+        if let Some(ranking_size) = self.ranking_size() {
+            return self.validate_ranking(choices, ranking_size);
+        }
+
+        let mut indexed = Vec::with_capacity(choices.len());
+        for choice in choices {
+            let idx = match self.choices().iter().find(|c| c.name == choice) {
+                Some(c) => c.idx,
+                None => return Err(anyhow::anyhow!("Given choice is not a part of this poll")),
+            };
+            if indexed.iter().all(|(i, _)| *i != idx) {
+                indexed.push((idx, choice));
+            }
+        }
+        indexed.sort_by_key(|(idx, _)| *idx);
+
+        match (self.kind(), indexed.len()) {
+            (_, 0) => Err(anyhow::anyhow!("At least one choice is required")),
+            (PollKind::Single, n) if n > 1 => {
+                Err(anyhow::anyhow!("Only one choice is allowed for this poll"))
+            }
+            _ => Ok(indexed.into_iter().map(|(_, choice)| choice).collect()),
+        }
+    }
+
+    /// 順位の回答や正解は、上位から順に `ranking_size` 個の選択肢を重複なく並べる
+    fn validate_ranking(
+        &self,
+        choices: Vec<ChoiceName>,
+        ranking_size: usize,
+    ) -> anyhow::Result<Vec<ChoiceName>>
+    where
+        Self: WithAttrs,
+    {
+        if choices.len() != ranking_size {
+            return Err(anyhow::anyhow!(
+                "Exactly {} choices are required for this poll",
+                ranking_size
+            ));
+        }
+        for (i, choice) in choices.iter().enumerate() {
+            if self.choices().iter().all(|c| c.name != *choice) {
+                return Err(anyhow::anyhow!("Given choice is not a part of this poll"));
+            }
+            if choices[..i].contains(choice) {
+                return Err(anyhow::anyhow!("Same choice is ranked twice"));
+            }
+        }
+        Ok(choices)
+    }
+
+    /// 回答や正解として入力された値を確認し、刻みに合わせて丸める
+    fn validate_value(&self, value: f64) -> anyhow::Result<f64>
+    where
+        Self: WithAttrs,
+    {
+        let numeric = self
+            .numeric()
+            .ok_or_else(|| anyhow::anyhow!("This poll is answered with choices"))?;
+        if !numeric.contains(value) {
+            return Err(anyhow::anyhow!("Given value is out of range of this poll"));
         }
+        Ok(numeric.round(value))
     }
 
     fn add_comment<A>(self, account: &A, comment_str: String) -> CommentAdded<Self>
@@ -200,7 +791,11 @@ pub trait Poll {
         Self: WithAttrs + WithUserChoices + Sized,
         A: Account,
     {
-        let choice = self.user_choices().get(account.id()).cloned();
+        // 複数選択や順位の回答は、コメントには表示しない
+        let choice = match self.user_choices().get(account.id()).map(Vec::as_slice) {
+            Some([choice]) if self.kind() != PollKind::Ranking => Some(choice.clone()),
+            _ => None,
+        };
 
         let comment = BriefComment {
             id: CommentId::new(),
@@ -217,7 +812,148 @@ pub trait Poll {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+/// Pollとして出題できる内容かを確認する。
+/// Contestに直接追加する場合と、事前に用意しておく場合で共通のルール。
+pub fn validate(
+    title: &str,
+    duration: Option<&Duration>,
+    choices: &[Choice],
+) -> anyhow::Result<()> {
+    validate_title_and_duration(title, duration)?;
+
+    if choices.len() < 2 {
+        return Err(anyhow::anyhow!("Poll must have at least 2 choices"));
+    }
+
+    for (i, choice) in choices.iter().enumerate() {
+        if choice.name.0.trim().is_empty() || choice.color.0.trim().is_empty() {
+            return Err(anyhow::anyhow!("Choice name and color must not be empty"));
+        }
+        if choices[..i].iter().any(|c| c.name == choice.name) {
+            return Err(anyhow::anyhow!("Duplicate choice name : {}", choice.name.0));
+        }
+    }
+
+    Ok(())
+}
+
+/// 数値で答えるPollとして出題できる内容かを確認する
+pub fn validate_numeric(
+    title: &str,
+    duration: Option<&Duration>,
+    numeric: &NumericSpec,
+    scoring: PollScoring,
+) -> anyhow::Result<()> {
+    validate_title_and_duration(title, duration)?;
+
+    let NumericSpec {
+        min,
+        max,
+        step,
+        tolerance,
+    } = *numeric;
+    if !(min.is_finite() && max.is_finite() && step.is_finite()) || min >= max {
+        return Err(anyhow::anyhow!("Numeric range is invalid"));
+    } else if step <= 0.0 {
+        return Err(anyhow::anyhow!("Numeric step must be positive"));
+    } else if !is_integral((max - min) / step) {
+        return Err(anyhow::anyhow!(
+            "Numeric range must be a multiple of the step"
+        ));
+    }
+
+    match (scoring, tolerance) {
+        (PollScoring::Tolerance, Some(tolerance)) if tolerance.is_finite() && tolerance >= 0.0 => {}
+        (PollScoring::Tolerance, _) => {
+            return Err(anyhow::anyhow!("Tolerance must be a non-negative number"))
+        }
+        (_, Some(_)) => return Err(anyhow::anyhow!("Tolerance is only for tolerance scoring")),
+        (_, None) => {}
+    }
+
+    Ok(())
+}
+
+fn validate_title_and_duration(title: &str, duration: Option<&Duration>) -> anyhow::Result<()> {
+    if title.trim().is_empty() {
+        return Err(anyhow::anyhow!("Poll title is empty"));
+    }
+
+    if let Some(duration) = duration {
+        if *duration <= Duration::zero() {
+            return Err(anyhow::anyhow!("Poll duration must be positive"));
+        }
+    }
+
+    Ok(())
+}
+
+/// 一度に延長できる制限時間の上限 (1日)
+pub const MAX_EXTENSION_SEC: i64 = 24 * 60 * 60;
+
+/// DBに保存する制限時間の秒数。保存できない長さはエラーにする。
+pub(crate) fn duration_sec(duration: Option<&Duration>) -> anyhow::Result<Option<i32>> {
+    duration
+        .map(|d| {
+            i32::try_from(d.num_seconds()).map_err(|_| anyhow::anyhow!("Poll duration is too long"))
+        })
+        .transpose()
+}
+
+/// 回答を変更できなくする時間は、制限時間より短くする必要がある
+pub fn validate_lock(duration: Option<&Duration>, lock: Option<&Duration>) -> anyhow::Result<()> {
+    match (duration, lock) {
+        (_, None) => Ok(()),
+        (None, Some(_)) => Err(anyhow::anyhow!("Poll without time limit can not be locked")),
+        (Some(_), Some(lock)) if *lock <= Duration::zero() => {
+            Err(anyhow::anyhow!("Lock before close must be positive"))
+        }
+        (Some(duration), Some(lock)) if lock >= duration => Err(anyhow::anyhow!(
+            "Lock before close must be shorter than the duration"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Pollをロックして、まだ回答を変更できることを確かめる。
+/// トランザクションの中で呼ぶと、終わるまでCloseやResolveを待たせる。
+pub(crate) fn lock_open(conn: &Connection, poll_id: &PollId) -> anyhow::Result<()> {
+    let poll = PollTable::query_by_id_for_update(conn, &poll_id.0)?
+        .ok_or_else(|| anyhow::anyhow!("Poll is not found"))?;
+    let poll = BriefPoll::from((poll, Vec::new()));
+    if poll.status() != PollStatus::Open {
+        return Err(NotOpen.into());
+    } else if poll.is_answer_locked(&Utc::now()) {
+        return Err(AnswerLocked.into());
+    }
+    Ok(())
+}
+
+/// 保存する前に、PollがCloseされていた
+#[derive(Debug)]
+pub struct NotOpen;
+
+impl std::fmt::Display for NotOpen {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Poll is already closed")
+    }
+}
+
+impl std::error::Error for NotOpen {}
+
+/// 締め切りの直前で、回答を変更できなくなっている
+#[derive(Debug)]
+pub struct AnswerLocked;
+
+impl std::fmt::Display for AnswerLocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Answer is locked before close")
+    }
+}
+
+impl std::error::Error for AnswerLocked {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
 pub struct PollId(pub Uuid);
 
@@ -250,31 +986,152 @@ pub struct ChoiceName(pub String);
 #[serde(transparent)]
 pub struct ChoiceColor(pub String);
 
+/// 数値で答えるPollの、回答できる値の範囲。
+/// 回答は `min` から `max` までの `step` 刻みの値に限る。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct NumericSpec {
+    pub min: f64,
+    pub max: f64,
+    pub step: f64,
+    /// `Tolerance` で採点する場合に、正解とみなす誤差
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<f64>,
+}
+
+/// ヒストグラムの区間の最大数
+const MAX_HISTOGRAM_BINS: usize = 20;
+
+impl NumericSpec {
+    pub(crate) fn from_queried(
+        min: Option<f64>,
+        max: Option<f64>,
+        step: Option<f64>,
+        tolerance: Option<f64>,
+    ) -> Option<NumericSpec> {
+        match (min, max, step) {
+            (Some(min), Some(max), Some(step)) => Some(NumericSpec {
+                min,
+                max,
+                step,
+                tolerance,
+            }),
+            _ => None,
+        }
+    }
+
+    /// 範囲内で、刻みに乗っている値か
+    pub fn contains(&self, value: f64) -> bool {
+        value.is_finite()
+            && self.min <= value + 1e-9
+            && value <= self.max + 1e-9
+            && is_integral((value - self.min) / self.step)
+    }
+
+    /// 丸め誤差を除いて、刻みに乗った値にする
+    fn round(&self, value: f64) -> f64 {
+        self.min + ((value - self.min) / self.step).round() * self.step
+    }
+
+    /// 回答できる値の数
+    fn num_values(&self) -> usize {
+        ((self.max - self.min) / self.step).round() as usize + 1
+    }
+
+    /// 回答の分布。
+    /// 回答できる値が多い場合は、隣り合う値をまとめて `MAX_HISTOGRAM_BINS` 個以内の区間にする。
+    pub fn histogram<'a>(&self, values: impl Iterator<Item = &'a f64>) -> Vec<HistogramBin> {
+        let num_values = self.num_values();
+        let values_per_bin = (num_values + MAX_HISTOGRAM_BINS - 1) / MAX_HISTOGRAM_BINS;
+        let num_bins = (num_values + values_per_bin - 1) / values_per_bin;
+
+        let mut bins = (0..num_bins)
+            .map(|i| {
+                let lower = self.min + self.step * (i * values_per_bin) as f64;
+                let upper = lower + self.step * (values_per_bin - 1) as f64;
+                HistogramBin {
+                    lower,
+                    upper: upper.min(self.max),
+                    count: 0,
+                }
+            })
+            .collect::<Vec<_>>();
+        for value in values {
+            let idx = ((value - self.min) / self.step).round() as usize / values_per_bin;
+            bins[idx.min(num_bins - 1)].count += 1;
+        }
+        bins
+    }
+}
+
+fn is_integral(x: f64) -> bool {
+    (x - x.round()).abs() < 1e-6
+}
+
+/// `lower` 以上 `upper` 以下の回答の数
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct HistogramBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+}
+
 #[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Stats {
     pub total_votes: usize,
-    pub vote_per_choice: HashMap<ChoiceName, usize>,
+    /// 選択肢で答えるPollの、各選択肢の得票数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vote_per_choice: Option<HashMap<ChoiceName, usize>>,
+    /// 順位を予想するPollの、各選択肢が1位から順に何票ずつ得たか
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vote_per_rank: Option<HashMap<ChoiceName, Vec<usize>>>,
+    /// 数値で答えるPollの、回答の分布
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub histogram: Option<Vec<HistogramBin>>,
 }
 
 pub trait WithAttrs: Poll {
     fn _status(&self) -> PollStatus;
 
+    fn _kind(&self) -> PollKind;
+
+    fn _scoring(&self) -> PollScoring;
+
     fn _title(&self) -> &str;
 
     fn _created_at(&self) -> &DateTime<Utc>;
 
     fn _duration(&self) -> Option<&Duration>;
 
+    fn _lock_before_close(&self) -> Option<&Duration>;
+
     fn _idx(&self) -> usize;
 
     fn _choices(&self) -> &[Choice];
 
-    fn _resolved_choice(&self) -> Option<&ChoiceName>;
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]>;
+
+    fn _numeric(&self) -> Option<&NumericSpec>;
+
+    fn _ranking_size(&self) -> Option<usize>;
+
+    fn _is_scored(&self) -> bool;
+
+    fn _stake_multiplier(&self) -> Option<f64>;
+
+    fn _resolved_value(&self) -> Option<f64>;
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment>;
+
+    fn _paused_at(&self) -> Option<&DateTime<Utc>>;
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>>;
 }
 
 pub trait WithUserChoices: Poll {
-    fn _user_choices(&self) -> &HashMap<AccountId, ChoiceName>;
+    fn _user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>>;
+
+    fn _user_values(&self) -> &HashMap<AccountId, f64>;
 }
 
 pub trait WithComments: Poll {
@@ -300,6 +1157,14 @@ where
         P::_status(self)
     }
 
+    fn _kind(&self) -> PollKind {
+        P::_kind(self)
+    }
+
+    fn _scoring(&self) -> PollScoring {
+        P::_scoring(self)
+    }
+
     fn _title(&self) -> &str {
         P::_title(self)
     }
@@ -312,6 +1177,10 @@ where
         P::_duration(self)
     }
 
+    fn _lock_before_close(&self) -> Option<&Duration> {
+        P::_lock_before_close(self)
+    }
+
     fn _idx(&self) -> usize {
         P::_idx(self)
     }
@@ -320,8 +1189,40 @@ where
         P::_choices(self)
     }
 
-    fn _resolved_choice(&self) -> Option<&ChoiceName> {
-        P::_resolved_choice(self)
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]> {
+        P::_resolved_choices(self)
+    }
+
+    fn _numeric(&self) -> Option<&NumericSpec> {
+        P::_numeric(self)
+    }
+
+    fn _ranking_size(&self) -> Option<usize> {
+        P::_ranking_size(self)
+    }
+
+    fn _is_scored(&self) -> bool {
+        P::_is_scored(self)
+    }
+
+    fn _stake_multiplier(&self) -> Option<f64> {
+        P::_stake_multiplier(self)
+    }
+
+    fn _resolved_value(&self) -> Option<f64> {
+        P::_resolved_value(self)
+    }
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment> {
+        P::_answer_commitment(self)
+    }
+
+    fn _paused_at(&self) -> Option<&DateTime<Utc>> {
+        P::_paused_at(self)
+    }
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>> {
+        P::_closed_at(self)
     }
 }
 
@@ -329,9 +1230,13 @@ impl<'a, P> WithUserChoices for &'a P
 where
     P: WithUserChoices,
 {
-    fn _user_choices(&self) -> &HashMap<AccountId, ChoiceName> {
+    fn _user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>> {
         P::_user_choices(self)
     }
+
+    fn _user_values(&self) -> &HashMap<AccountId, f64> {
+        P::_user_values(self)
+    }
 }
 
 impl<'a, P> WithComments for &'a P
@@ -344,3 +1249,220 @@ where
         P::_comments(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 今から `duration` 秒後に締め切る、"a" と "b" の2択のPoll
+    fn poll(duration: Option<i64>, lock: Option<i64>) -> BriefPoll {
+        let choice = |idx: usize, name: &str| Choice {
+            idx,
+            name: ChoiceName(String::from(name)),
+            color: ChoiceColor(String::from("#000000")),
+        };
+        BriefPoll {
+            id: PollId::new(),
+            status: PollStatus::Open,
+            title: String::from("poll"),
+            kind: PollKind::Single,
+            scoring: PollScoring::Exact,
+            created_at: Utc::now(),
+            duration: duration.map(Duration::seconds),
+            lock_before_close: lock.map(Duration::seconds),
+            idx: 1,
+            choices: vec![choice(0, "a"), choice(1, "b")],
+            numeric: None,
+            ranking_size: None,
+            is_scored: true,
+            stake_multiplier: None,
+            resolved_choice: None,
+            resolved_choices: None,
+            resolved_value: None,
+            answer_commitment: None,
+            paused_at: None,
+            closed_at: None,
+        }
+    }
+
+    /// `duration` 秒の制限時間のうち、 `remaining` 秒を残してCloseしたPoll
+    fn closed(duration: Option<i64>, remaining: i64, lock: Option<i64>) -> BriefPoll {
+        let mut poll = poll(duration, lock);
+        let closed_at = Utc::now() - Duration::seconds(60);
+        poll.status = PollStatus::Closed;
+        poll.closed_at = Some(closed_at);
+        poll.created_at = closed_at - Duration::seconds(duration.unwrap_or(0) - remaining);
+        poll
+    }
+
+    #[test]
+    fn reopen_keeps_remaining_time() {
+        let reopened = closed(Some(300), 120, None).reopen(None).unwrap();
+        assert_eq!(reopened.status(), PollStatus::Open);
+        let remaining = reopened.closes_at().unwrap() - Utc::now();
+        assert!(Duration::seconds(119) < remaining && remaining <= Duration::seconds(120));
+    }
+
+    #[test]
+    fn reopen_with_duration_opens_for_given_time() {
+        let reopened = closed(Some(300), 0, None)
+            .reopen(Some(Duration::seconds(30)))
+            .unwrap();
+        assert_eq!(reopened.status(), PollStatus::Open);
+        let remaining = reopened.closes_at().unwrap() - Utc::now();
+        assert!(Duration::seconds(29) < remaining && remaining <= Duration::seconds(30));
+
+        let untimed = closed(None, 0, None).reopen(None).unwrap();
+        assert_eq!(untimed.duration(), None);
+    }
+
+    #[test]
+    fn reopen_is_rejected_without_time_to_answer() {
+        assert!(closed(Some(300), 0, None).reopen(None).is_err());
+        assert!(closed(Some(300), 120, None)
+            .reopen(Some(Duration::zero()))
+            .is_err());
+        // 残り時間より長くロックすると、再開した直後から回答できない
+        assert!(closed(Some(300), 120, Some(120)).reopen(None).is_err());
+        assert!(closed(Some(300), 0, Some(60))
+            .reopen(Some(Duration::seconds(60)))
+            .is_err());
+        assert!(closed(Some(300), 120, Some(60)).reopen(None).is_ok());
+    }
+
+    #[test]
+    fn reopen_is_rejected_unless_closed_and_unresolved() {
+        assert!(poll(Some(60), None).reopen(None).is_err());
+
+        let mut resolved = closed(Some(300), 120, None);
+        resolved.resolved_choice = Some(ChoiceName(String::from("a")));
+        assert!(resolved.reopen(None).is_err());
+    }
+
+    #[test]
+    fn extend_adds_to_duration() {
+        let poll = poll(Some(60), None);
+        let closes_at = poll.closes_at().unwrap();
+        let extended = poll.extend(Duration::seconds(30)).unwrap();
+        assert_eq!(extended.duration(), Some(&Duration::seconds(90)));
+        assert_eq!(
+            extended.closes_at(),
+            Some(closes_at + Duration::seconds(30))
+        );
+    }
+
+    #[test]
+    fn extend_is_rejected_without_positive_time_limit() {
+        assert!(poll(Some(60), None).extend(Duration::zero()).is_err());
+        assert!(poll(None, None).extend(Duration::seconds(30)).is_err());
+
+        let mut past = poll(Some(60), None);
+        past.created_at = Utc::now() - Duration::seconds(120);
+        assert!(past.extend(Duration::seconds(30)).is_err());
+    }
+
+    #[test]
+    fn extend_is_capped() {
+        let max = Duration::seconds(MAX_EXTENSION_SEC);
+        assert!(poll(Some(60), None).extend(max).is_ok());
+        assert!(poll(Some(60), None)
+            .extend(max + Duration::seconds(1))
+            .is_err());
+    }
+
+    #[test]
+    fn duration_too_long_to_save_is_rejected() {
+        let max = Duration::seconds(std::i32::MAX as i64);
+        assert_eq!(duration_sec(Some(&max)).unwrap(), Some(std::i32::MAX));
+        assert!(duration_sec(Some(&(max + Duration::seconds(1)))).is_err());
+    }
+
+    #[test]
+    fn force_closing_paused_poll_keeps_remaining_time() {
+        // 作成から10秒後に一時停止し、その10秒後に強制的にCloseする
+        let mut paused = poll(Some(60), None);
+        paused.created_at = Utc::now() - Duration::seconds(20);
+        paused.paused_at = Some(paused.created_at + Duration::seconds(10));
+        let closed = paused.force_close().unwrap();
+        assert_eq!(closed.paused_at(), None);
+        let duration = *closed.duration().unwrap();
+        assert!(Duration::seconds(70) <= duration && duration < Duration::seconds(71));
+
+        // 停止していた時間は使っていないので、残りの50秒でOpenに戻る
+        let reopened = closed.reopen(None).unwrap();
+        let remaining = reopened.closes_at().unwrap() - Utc::now();
+        assert!(Duration::seconds(49) < remaining && remaining <= Duration::seconds(50));
+    }
+
+    #[test]
+    fn paused_poll_has_no_closing_time() {
+        let paused = poll(Some(60), None).pause().unwrap();
+        assert!(paused.paused_at().is_some());
+        assert_eq!(paused.closes_at(), None);
+    }
+
+    #[test]
+    fn pause_is_rejected_twice_or_without_time_limit() {
+        let mut paused = poll(Some(60), None);
+        paused.paused_at = Some(Utc::now());
+        assert!(paused.pause().is_err());
+        assert!(poll(None, None).pause().is_err());
+    }
+
+    #[test]
+    fn resume_extends_duration_by_paused_time() {
+        let mut paused = poll(Some(60), None);
+        paused.paused_at = Some(Utc::now() - Duration::seconds(10));
+        let resumed = paused.resume().unwrap();
+        assert_eq!(resumed.paused_at(), None);
+        let duration = *resumed.duration().unwrap();
+        assert!(Duration::seconds(70) <= duration && duration < Duration::seconds(71));
+    }
+
+    #[test]
+    fn resume_is_rejected_unless_paused_with_time_limit() {
+        assert!(poll(Some(60), None).resume().is_err());
+
+        // 制限時間の無いPollは一時停止できないが、壊れたデータでもpanicしない
+        let mut untimed = poll(None, None);
+        untimed.paused_at = Some(Utc::now());
+        assert!(untimed.resume().is_err());
+    }
+
+    #[test]
+    fn answer_is_locked_from_lock_time() {
+        let poll = poll(Some(60), Some(10));
+        let locks_at = poll.locks_at().unwrap();
+        assert_eq!(locks_at, poll.closes_at().unwrap() - Duration::seconds(10));
+
+        assert!(!poll.is_answer_locked(&(locks_at - Duration::seconds(1))));
+        assert!(poll.is_answer_locked(&locks_at));
+        assert!(poll.is_answer_locked(&(locks_at + Duration::seconds(1))));
+    }
+
+    #[test]
+    fn answer_is_never_locked_without_time_limit_or_lock() {
+        let far = Utc::now() + Duration::days(1);
+
+        let untimed = poll(None, Some(10));
+        assert_eq!(untimed.locks_at(), None);
+        assert!(!untimed.is_answer_locked(&far));
+
+        let unlocked = poll(Some(60), None);
+        assert_eq!(unlocked.locks_at(), None);
+        assert!(!unlocked.is_answer_locked(&far));
+    }
+
+    #[test]
+    fn paused_poll_is_locked_as_of_pause() {
+        // ロックの5秒前に一時停止したPollは、時間が経ってもロックされない
+        let mut before = poll(Some(60), Some(10));
+        before.paused_at = Some(before.created_at + Duration::seconds(45));
+        assert!(!before.is_answer_locked(&(Utc::now() + Duration::days(1))));
+
+        // ロックの後に一時停止したPollは、ロックされたまま
+        let mut after = poll(Some(60), Some(10));
+        after.paused_at = Some(after.created_at + Duration::seconds(55));
+        assert!(after.is_answer_locked(&Utc::now()));
+    }
+}
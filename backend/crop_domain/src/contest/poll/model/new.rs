@@ -1,15 +1,27 @@
-use super::{Choice, ChoiceName, Poll, PollId, PollStatus, WithAttrs, WithUserChoices};
+use super::{
+    Choice, ChoiceName, NumericSpec, Poll, PollId, PollKind, PollScoring, PollStatus, WithAttrs,
+    WithUserChoices,
+};
 use crate::account::AccountId;
+use crate::contest::poll::AnswerCommitment;
 use chrono::{DateTime, Duration, Utc};
 use std::collections::HashMap;
 
 pub struct New {
     pub id: PollId,
     pub title: String,
+    pub kind: PollKind,
+    pub scoring: PollScoring,
     pub created_at: DateTime<Utc>,
     pub duration: Option<Duration>,
+    pub lock_before_close: Option<Duration>,
     pub idx: usize,
     pub choices: Vec<Choice>,
+    pub numeric: Option<NumericSpec>,
+    pub ranking_size: Option<usize>,
+    pub is_scored: bool,
+    pub stake_multiplier: Option<f64>,
+    pub answer_commitment: Option<AnswerCommitment>,
 }
 
 impl Poll for New {
@@ -23,6 +35,14 @@ impl WithAttrs for New {
         PollStatus::Open
     }
 
+    fn _kind(&self) -> PollKind {
+        self.kind
+    }
+
+    fn _scoring(&self) -> PollScoring {
+        self.scoring
+    }
+
     fn _title(&self) -> &str {
         self.title.as_str()
     }
@@ -35,6 +55,10 @@ impl WithAttrs for New {
         self.duration.as_ref()
     }
 
+    fn _lock_before_close(&self) -> Option<&Duration> {
+        self.lock_before_close.as_ref()
+    }
+
     fn _idx(&self) -> usize {
         self.idx
     }
@@ -43,15 +67,55 @@ impl WithAttrs for New {
         &self.choices.as_slice()
     }
 
-    fn _resolved_choice(&self) -> Option<&ChoiceName> {
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]> {
+        None
+    }
+
+    fn _numeric(&self) -> Option<&NumericSpec> {
+        self.numeric.as_ref()
+    }
+
+    fn _ranking_size(&self) -> Option<usize> {
+        self.ranking_size
+    }
+
+    fn _is_scored(&self) -> bool {
+        self.is_scored
+    }
+
+    fn _stake_multiplier(&self) -> Option<f64> {
+        self.stake_multiplier
+    }
+
+    fn _resolved_value(&self) -> Option<f64> {
+        None
+    }
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment> {
+        self.answer_commitment.as_ref()
+    }
+
+    fn _paused_at(&self) -> Option<&DateTime<Utc>> {
+        None
+    }
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>> {
         None
     }
 }
 
 impl WithUserChoices for New {
-    fn _user_choices(&self) -> &HashMap<AccountId, ChoiceName> {
+    fn _user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>> {
+        lazy_static::lazy_static! {
+            static ref EMPTY: HashMap<AccountId, Vec<ChoiceName>> = HashMap::new();
+        }
+
+        &EMPTY
+    }
+
+    fn _user_values(&self) -> &HashMap<AccountId, f64> {
         lazy_static::lazy_static! {
-            static ref EMPTY: HashMap<AccountId, ChoiceName> = HashMap::new();
+            static ref EMPTY: HashMap<AccountId, f64> = HashMap::new();
         }
 
         &EMPTY
@@ -0,0 +1,167 @@
+use super::{
+    Choice, ChoiceName, NumericSpec, Poll, PollId, PollKind, PollScoring, PollStatus,
+    StakesSettled, WithAttrs, WithComments, WithUserChoices,
+};
+use crate::account::{
+    ledger::{self, Settlement},
+    AccountId,
+};
+use crate::contest::poll::{AnswerCommitment, Resolution};
+use crate::contest::Updatable;
+use chrono::{DateTime, Duration, Utc};
+use crop_infra::pg::{self, Connection};
+use std::collections::HashMap;
+
+/// 正解を訂正したPoll。
+/// 訂正前の正解は `poll` 側に残っている。
+#[must_use]
+pub struct ReResolved<P> {
+    pub poll: P,
+    pub(crate) resolved: Resolution,
+}
+
+impl<P> ReResolved<P>
+where
+    P: WithAttrs,
+{
+    /// 訂正前の正解
+    pub fn previous(&self) -> Resolution {
+        // re_resolve の時点でResolve済みであることを確認している
+        self.poll.resolution().unwrap()
+    }
+}
+
+impl<P> Poll for ReResolved<P>
+where
+    P: Poll,
+{
+    fn id(&self) -> &PollId {
+        self.poll.id()
+    }
+}
+
+impl<P> WithAttrs for ReResolved<P>
+where
+    P: WithAttrs,
+{
+    fn _status(&self) -> PollStatus {
+        PollStatus::Closed
+    }
+
+    fn _kind(&self) -> PollKind {
+        self.poll._kind()
+    }
+
+    fn _scoring(&self) -> PollScoring {
+        self.poll._scoring()
+    }
+
+    fn _title(&self) -> &str {
+        self.poll._title()
+    }
+
+    fn _created_at(&self) -> &DateTime<Utc> {
+        self.poll._created_at()
+    }
+
+    fn _duration(&self) -> Option<&Duration> {
+        self.poll._duration()
+    }
+
+    fn _lock_before_close(&self) -> Option<&Duration> {
+        self.poll._lock_before_close()
+    }
+
+    fn _idx(&self) -> usize {
+        self.poll._idx()
+    }
+
+    fn _choices(&self) -> &[Choice] {
+        self.poll._choices()
+    }
+
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]> {
+        self.resolved.choices()
+    }
+
+    fn _numeric(&self) -> Option<&NumericSpec> {
+        self.poll._numeric()
+    }
+
+    fn _ranking_size(&self) -> Option<usize> {
+        self.poll._ranking_size()
+    }
+
+    fn _is_scored(&self) -> bool {
+        self.poll._is_scored()
+    }
+
+    fn _stake_multiplier(&self) -> Option<f64> {
+        self.poll._stake_multiplier()
+    }
+
+    fn _resolved_value(&self) -> Option<f64> {
+        self.resolved.value()
+    }
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment> {
+        self.poll._answer_commitment()
+    }
+
+    fn _paused_at(&self) -> Option<&DateTime<Utc>> {
+        self.poll._paused_at()
+    }
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>> {
+        self.poll._closed_at()
+    }
+}
+
+impl<P> WithUserChoices for ReResolved<P>
+where
+    P: WithUserChoices,
+{
+    fn _user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>> {
+        self.poll._user_choices()
+    }
+
+    fn _user_values(&self) -> &HashMap<AccountId, f64> {
+        self.poll._user_values()
+    }
+}
+
+impl<P> WithComments for ReResolved<P>
+where
+    P: WithComments,
+{
+    type Comment = P::Comment;
+
+    fn _comments(&self) -> &[Self::Comment] {
+        self.poll._comments()
+    }
+}
+
+impl<P> Updatable for ReResolved<P>
+where
+    P: WithAttrs + WithUserChoices,
+{
+    /// 公開済みのnonceはそのまま残す。
+    ///
+    /// 訂正前の正解で賭けやマーケットを精算していた場合は、その払い戻しを取り消してから、
+    /// 訂正後の正解で精算し直す。払い戻したポイントを使っていた場合は、残高が負になる。
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        pg::transaction(conn, || {
+            super::resolved::save_resolution(
+                conn,
+                &self.poll,
+                &self.resolved,
+                self.answer_commitment().and_then(|c| c.nonce()),
+            )?;
+
+            for transaction in Settlement::query(conn, self.poll.id())?.reverse() {
+                ledger::post(conn, &transaction)?;
+            }
+            StakesSettled { poll: self }.save(conn)
+        })
+    }
+}
@@ -0,0 +1,137 @@
+use super::{
+    duration_sec, Choice, ChoiceName, NumericSpec, Poll, PollId, PollKind, PollScoring, PollStatus,
+    WithAttrs, WithComments, WithUserChoices,
+};
+use crate::account::AccountId;
+use crate::contest::poll::AnswerCommitment;
+use crate::contest::Updatable;
+use chrono::{DateTime, Duration, Utc};
+use crop_infra::pg::{poll::PollTable, Connection};
+use std::collections::HashMap;
+
+/// 誤ってCloseしたPollを、Openに戻したもの。
+/// Closeする前の投票はそのまま残る。
+#[must_use]
+pub struct Reopened<P> {
+    pub poll: P,
+    pub(crate) duration: Option<Duration>,
+}
+
+impl<P> Poll for Reopened<P>
+where
+    P: Poll,
+{
+    fn id(&self) -> &PollId {
+        self.poll.id()
+    }
+}
+
+impl<P> WithAttrs for Reopened<P>
+where
+    P: WithAttrs,
+{
+    fn _status(&self) -> PollStatus {
+        PollStatus::Open
+    }
+
+    fn _kind(&self) -> PollKind {
+        self.poll._kind()
+    }
+
+    fn _scoring(&self) -> PollScoring {
+        self.poll._scoring()
+    }
+
+    fn _title(&self) -> &str {
+        self.poll._title()
+    }
+
+    fn _created_at(&self) -> &DateTime<Utc> {
+        self.poll._created_at()
+    }
+
+    fn _duration(&self) -> Option<&Duration> {
+        self.duration.as_ref()
+    }
+
+    fn _lock_before_close(&self) -> Option<&Duration> {
+        self.poll._lock_before_close()
+    }
+
+    fn _idx(&self) -> usize {
+        self.poll._idx()
+    }
+
+    fn _choices(&self) -> &[Choice] {
+        self.poll._choices()
+    }
+
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]> {
+        None
+    }
+
+    fn _numeric(&self) -> Option<&NumericSpec> {
+        self.poll._numeric()
+    }
+
+    fn _ranking_size(&self) -> Option<usize> {
+        self.poll._ranking_size()
+    }
+
+    fn _is_scored(&self) -> bool {
+        self.poll._is_scored()
+    }
+
+    fn _stake_multiplier(&self) -> Option<f64> {
+        self.poll._stake_multiplier()
+    }
+
+    fn _resolved_value(&self) -> Option<f64> {
+        None
+    }
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment> {
+        self.poll._answer_commitment()
+    }
+
+    fn _paused_at(&self) -> Option<&DateTime<Utc>> {
+        None
+    }
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>> {
+        None
+    }
+}
+
+impl<P> WithUserChoices for Reopened<P>
+where
+    P: WithUserChoices,
+{
+    fn _user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>> {
+        self.poll._user_choices()
+    }
+
+    fn _user_values(&self) -> &HashMap<AccountId, f64> {
+        self.poll._user_values()
+    }
+}
+
+impl<P> WithComments for Reopened<P>
+where
+    P: WithComments,
+{
+    type Comment = P::Comment;
+
+    fn _comments(&self) -> &[Self::Comment] {
+        self.poll._comments()
+    }
+}
+
+impl<P> Updatable for Reopened<P>
+where
+    P: WithAttrs,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        PollTable::update_reopened(conn, &self.id().0, duration_sec(self.duration.as_ref())?)
+    }
+}
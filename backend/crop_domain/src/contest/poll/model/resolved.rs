@@ -1,24 +1,90 @@
 use crate::account::AccountId;
 use crate::contest::poll::{
-    Choice, ChoiceName, Poll, PollId, PollStatus, WithAttrs, WithUserChoices,
+    AnswerCommitment, Choice, ChoiceName, NumericSpec, Poll, PollId, PollKind, PollScoring,
+    PollStatus, WithAttrs, WithUserChoices,
 };
 use crate::contest::Updatable;
 use chrono::{DateTime, Duration, Utc};
 use crop_infra::pg::{poll::PollTable as _, Connection};
 use std::collections::HashMap;
 
+/// Pollの正解。選択肢で答えるPollと、数値で答えるPollで形が異なる。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution {
+    /// 選択肢の順に並んだ正解
+    Choices(Vec<ChoiceName>),
+    Value(f64),
+}
+
+impl Resolution {
+    pub fn choices(&self) -> Option<&[ChoiceName]> {
+        match self {
+            Resolution::Choices(choices) => Some(choices.as_slice()),
+            Resolution::Value(_) => None,
+        }
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            Resolution::Choices(_) => None,
+            Resolution::Value(value) => Some(*value),
+        }
+    }
+}
+
+impl std::fmt::Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Resolution::Choices(choices) => {
+                let names = choices.iter().map(|c| c.0.as_str()).collect::<Vec<_>>();
+                write!(f, "{}", names.join(", "))
+            }
+            Resolution::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
+
 #[must_use]
 pub struct Resolved<P> {
-    pub(super) poll: P,
-    pub(super) resolved: ChoiceName,
+    pub(crate) poll: P,
+    pub(crate) resolved: Resolution,
+    /// nonceを公開したコミットメント
+    pub(crate) commitment: Option<AnswerCommitment>,
 }
 
 impl<P> Updatable for Resolved<P>
 where
-    P: Poll,
+    P: WithAttrs,
 {
     fn save(&self, conn: &Connection) -> anyhow::Result<()> {
-        conn.update_resolved_choice_name(&self.poll.id().0, self.resolved.0.as_str())
+        save_resolution(
+            conn,
+            &self.poll,
+            &self.resolved,
+            self.commitment.as_ref().and_then(|c| c.nonce()),
+        )
+    }
+}
+
+/// Pollの種類によって、正解を保存するカラムが異なる
+pub(super) fn save_resolution<P>(
+    conn: &Connection,
+    poll: &P,
+    resolved: &Resolution,
+    answer_nonce: Option<&str>,
+) -> anyhow::Result<()>
+where
+    P: WithAttrs,
+{
+    match (resolved, poll.kind()) {
+        (Resolution::Value(value), _) => conn.update_resolved_value(&poll.id().0, *value),
+        (Resolution::Choices(choices), PollKind::Single) => {
+            conn.update_resolved_choice_name(&poll.id().0, choices[0].0.as_str(), answer_nonce)
+        }
+        (Resolution::Choices(choices), _) => {
+            let names = choices.iter().map(|c| c.0.as_str()).collect::<Vec<_>>();
+            conn.update_resolved_choice_names(&poll.id().0, &names)
+        }
     }
 }
 
@@ -39,6 +105,14 @@ where
         PollStatus::Closed
     }
 
+    fn _kind(&self) -> PollKind {
+        self.poll._kind()
+    }
+
+    fn _scoring(&self) -> PollScoring {
+        self.poll._scoring()
+    }
+
     fn _title(&self) -> &str {
         self.poll._title()
     }
@@ -51,6 +125,10 @@ where
         self.poll._duration()
     }
 
+    fn _lock_before_close(&self) -> Option<&Duration> {
+        self.poll._lock_before_close()
+    }
+
     fn _idx(&self) -> usize {
         self.poll._idx()
     }
@@ -59,8 +137,40 @@ where
         self.poll._choices()
     }
 
-    fn _resolved_choice(&self) -> Option<&ChoiceName> {
-        Some(&self.resolved)
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]> {
+        self.resolved.choices()
+    }
+
+    fn _numeric(&self) -> Option<&NumericSpec> {
+        self.poll._numeric()
+    }
+
+    fn _ranking_size(&self) -> Option<usize> {
+        self.poll._ranking_size()
+    }
+
+    fn _is_scored(&self) -> bool {
+        self.poll._is_scored()
+    }
+
+    fn _stake_multiplier(&self) -> Option<f64> {
+        self.poll._stake_multiplier()
+    }
+
+    fn _resolved_value(&self) -> Option<f64> {
+        self.resolved.value()
+    }
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment> {
+        self.commitment.as_ref()
+    }
+
+    fn _paused_at(&self) -> Option<&DateTime<Utc>> {
+        self.poll._paused_at()
+    }
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>> {
+        self.poll._closed_at()
     }
 }
 
@@ -68,7 +178,11 @@ impl<P> WithUserChoices for Resolved<P>
 where
     P: WithUserChoices,
 {
-    fn _user_choices(&self) -> &HashMap<AccountId, ChoiceName> {
+    fn _user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>> {
         self.poll._user_choices()
     }
+
+    fn _user_values(&self) -> &HashMap<AccountId, f64> {
+        self.poll._user_values()
+    }
 }
@@ -0,0 +1,142 @@
+use super::{
+    duration_sec, Choice, ChoiceName, NumericSpec, Poll, PollId, PollKind, PollScoring, PollStatus,
+    WithAttrs, WithComments, WithUserChoices,
+};
+use crate::account::AccountId;
+use crate::contest::poll::AnswerCommitment;
+use crate::contest::Updatable;
+use chrono::{DateTime, Duration, Utc};
+use crop_infra::pg::{poll::PollTable, Connection};
+use std::collections::HashMap;
+
+/// タイマーを再開したPoll。
+/// 停止していた時間だけ、制限時間が延びる。
+#[must_use]
+pub struct Resumed<P> {
+    pub poll: P,
+    pub(crate) duration: Duration,
+}
+
+impl<P> Poll for Resumed<P>
+where
+    P: Poll,
+{
+    fn id(&self) -> &PollId {
+        self.poll.id()
+    }
+}
+
+impl<P> WithAttrs for Resumed<P>
+where
+    P: WithAttrs,
+{
+    fn _status(&self) -> PollStatus {
+        self.poll._status()
+    }
+
+    fn _kind(&self) -> PollKind {
+        self.poll._kind()
+    }
+
+    fn _scoring(&self) -> PollScoring {
+        self.poll._scoring()
+    }
+
+    fn _title(&self) -> &str {
+        self.poll._title()
+    }
+
+    fn _created_at(&self) -> &DateTime<Utc> {
+        self.poll._created_at()
+    }
+
+    fn _duration(&self) -> Option<&Duration> {
+        Some(&self.duration)
+    }
+
+    fn _lock_before_close(&self) -> Option<&Duration> {
+        self.poll._lock_before_close()
+    }
+
+    fn _idx(&self) -> usize {
+        self.poll._idx()
+    }
+
+    fn _choices(&self) -> &[Choice] {
+        self.poll._choices()
+    }
+
+    fn _resolved_choices(&self) -> Option<&[ChoiceName]> {
+        self.poll._resolved_choices()
+    }
+
+    fn _numeric(&self) -> Option<&NumericSpec> {
+        self.poll._numeric()
+    }
+
+    fn _ranking_size(&self) -> Option<usize> {
+        self.poll._ranking_size()
+    }
+
+    fn _is_scored(&self) -> bool {
+        self.poll._is_scored()
+    }
+
+    fn _stake_multiplier(&self) -> Option<f64> {
+        self.poll._stake_multiplier()
+    }
+
+    fn _resolved_value(&self) -> Option<f64> {
+        self.poll._resolved_value()
+    }
+
+    fn _answer_commitment(&self) -> Option<&AnswerCommitment> {
+        self.poll._answer_commitment()
+    }
+
+    fn _paused_at(&self) -> Option<&DateTime<Utc>> {
+        None
+    }
+
+    fn _closed_at(&self) -> Option<&DateTime<Utc>> {
+        self.poll._closed_at()
+    }
+}
+
+impl<P> WithUserChoices for Resumed<P>
+where
+    P: WithUserChoices,
+{
+    fn _user_choices(&self) -> &HashMap<AccountId, Vec<ChoiceName>> {
+        self.poll._user_choices()
+    }
+
+    fn _user_values(&self) -> &HashMap<AccountId, f64> {
+        self.poll._user_values()
+    }
+}
+
+impl<P> WithComments for Resumed<P>
+where
+    P: WithComments,
+{
+    type Comment = P::Comment;
+
+    fn _comments(&self) -> &[Self::Comment] {
+        self.poll._comments()
+    }
+}
+
+impl<P> Updatable for Resumed<P>
+where
+    P: WithAttrs,
+{
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        PollTable::update_timer(
+            conn,
+            &self.id().0,
+            duration_sec(self.duration())?,
+            self.paused_at(),
+        )
+    }
+}
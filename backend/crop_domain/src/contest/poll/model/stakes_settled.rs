@@ -0,0 +1,40 @@
+use super::{PollStatus, WithAttrs, WithUserChoices};
+use crate::account::ledger::{self, Escrow, Settlement};
+use crate::contest::poll::market::Market;
+use crate::contest::Updatable;
+use crop_infra::pg::Connection;
+
+/// 賭けられたポイントや、マーケットの取引を精算するPoll
+#[must_use]
+pub struct StakesSettled<P> {
+    pub poll: P,
+}
+
+impl<P> Updatable for StakesSettled<P>
+where
+    P: WithAttrs + WithUserChoices,
+{
+    /// 中止したPollでは、賭けたポイントをそのまま払い戻す。
+    /// マーケットが開かれていれば、取引で買った株も払い戻す。
+    /// 既に精算済みの取引は記帳しない。
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let settlement = Settlement::query(conn, self.poll.id())?;
+        let mut transactions = Vec::new();
+        if self.poll.stake_multiplier().is_some() {
+            let escrow = Escrow::query(conn, self.poll.id())?;
+            if self.poll.status() == PollStatus::Voided {
+                transactions.extend(escrow.refund(&settlement));
+            } else {
+                let payouts = self.poll.compute_stake_payouts(escrow.amounts());
+                transactions.extend(escrow.settle(&payouts, &settlement));
+            }
+        }
+        if let Some(market) = Market::query(conn, self.poll.id())? {
+            transactions.extend(market.settle(&self.poll, &settlement));
+        }
+        for transaction in transactions.iter() {
+            ledger::post(conn, transaction)?;
+        }
+        Ok(())
+    }
+}
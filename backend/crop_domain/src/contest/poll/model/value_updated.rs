@@ -0,0 +1,34 @@
+use super::lock_open;
+use crate::account::AccountId;
+use crate::contest::poll::Poll;
+use crate::contest::Updatable;
+use crop_infra::pg::{
+    self,
+    account_numeric_answer::{AccountNumericAnswerTable, NewAccountNumericAnswer},
+    Connection,
+};
+
+/// 数値で答えるPollへの回答
+pub struct ValueUpdated<P> {
+    pub(super) poll: P,
+    pub(super) account_id: AccountId,
+    pub(super) value: f64,
+}
+
+impl<P> Updatable for ValueUpdated<P>
+where
+    P: Poll,
+{
+    /// 回答を確認した後にCloseされていないか、Pollをロックして確かめ直してから保存する
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let record = NewAccountNumericAnswer {
+            poll_id: &self.poll.id().0,
+            account_id: &self.account_id,
+            value: self.value,
+        };
+        pg::transaction(conn, || {
+            lock_open(conn, self.poll.id())?;
+            AccountNumericAnswerTable::upsert(conn, record)
+        })
+    }
+}
@@ -0,0 +1,248 @@
+//! Pollの採点
+//!
+//! 1問の満点は、Pollの種類に関わらず1点。
+//! 複数選択のPollで部分点を与える場合や、数値で答えるPollで順位を付ける場合、
+//! 順位を予想するPollで一部だけ当たった場合は、0点から1点の間の得点になる。
+use super::{ChoiceName, PollKind, PollScoring};
+use crate::account::AccountId;
+use std::collections::HashMap;
+
+/// 浮動小数点の丸め誤差を無視するための幅
+const EPSILON: f64 = 1e-9;
+
+/// 1アカウントの回答を採点する。
+/// `resolved` と `answer` は、どちらも重複の無い選択肢の集合として扱う。
+pub fn score(
+    kind: PollKind,
+    scoring: PollScoring,
+    resolved: &[ChoiceName],
+    answer: &[ChoiceName],
+) -> f64 {
+    let hits = answer.iter().filter(|c| resolved.contains(c)).count();
+    let misses = answer.len() - hits;
+    let exact = hits == resolved.len() && misses == 0;
+
+    match (kind, scoring) {
+        (PollKind::Multiple, PollScoring::Partial) if !resolved.is_empty() => {
+            // 不正解の選択肢を選ぶと減点する。全て選ぶだけで点を取れないようにするため。
+            let points = (hits as f64 - misses as f64) / resolved.len() as f64;
+            points.max(0.0)
+        }
+        _ => {
+            if exact {
+                1.0
+            } else {
+                0.0
+            }
+        }
+    }
+}
+
+/// 順位を予想するPollで、1アカウントの回答を採点する。
+/// `resolved` と `answer` は、どちらも上位から順に並んだ同じ長さの選択肢。
+///
+/// - `Position` : 順位が一致した選択肢の割合
+/// - `Kendall` : 実際の順位で上下関係にある選択肢の組のうち、回答でも同じ上下関係になっている割合。
+///   回答に含まれない選択肢は、回答したどの選択肢よりも下位とみなす。
+pub fn score_ranking(scoring: PollScoring, resolved: &[ChoiceName], answer: &[ChoiceName]) -> f64 {
+    let n = resolved.len();
+    if n == 0 {
+        return 0.0;
+    }
+    let position_hits = resolved.iter().zip(answer).filter(|(r, a)| r == a).count();
+
+    match scoring {
+        PollScoring::Position => position_hits as f64 / n as f64,
+        PollScoring::Kendall if n > 1 => {
+            let rank_of = |c: &ChoiceName| answer.iter().position(|a| a == c).unwrap_or(n);
+            let pairs = n * (n - 1) / 2;
+            let concordant = (0..n)
+                .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                .filter(|(i, j)| rank_of(&resolved[*i]) < rank_of(&resolved[*j]))
+                .count();
+            concordant as f64 / pairs as f64
+        }
+        _ => {
+            if position_hits == n {
+                1.0
+            } else {
+                0.0
+            }
+        }
+    }
+}
+
+/// 数値で答えるPollで、各アカウントの回答を実際の値との距離で採点する。
+///
+/// `Closest` では実際の値に近い順に順位を付け、自分より近い回答が k 個ある場合は
+/// `(回答数 - k) / 回答数` 点を与える。最も近い回答は、同着も含めて1点になる。
+pub fn score_numeric(
+    scoring: PollScoring,
+    tolerance: Option<f64>,
+    resolved: f64,
+    answers: &HashMap<AccountId, f64>,
+) -> HashMap<AccountId, f64> {
+    let distance = |value: f64| (value - resolved).abs();
+    let total = answers.len();
+
+    answers
+        .iter()
+        .map(|(account, value)| {
+            let d = distance(*value);
+            let points = match scoring {
+                PollScoring::Tolerance => {
+                    if d <= tolerance.unwrap_or(0.0) + EPSILON {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+                PollScoring::Closest => {
+                    let closer = answers
+                        .values()
+                        .filter(|other| distance(**other) < d - EPSILON)
+                        .count();
+                    (total - closer) as f64 / total as f64
+                }
+                _ => {
+                    if d < EPSILON {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+            };
+            (*account, points)
+        })
+        .collect()
+}
+
+/// 回答に賭けたポイントの払い戻し。
+/// 得点に応じて `stake * multiplier` を払い戻し、1ポイント未満は切り捨てる。
+/// 部分点の場合は、賭けたポイントより少なくなることもある。
+pub fn stake_payout(stake: i64, multiplier: f64, points: f64) -> i64 {
+    let payout = stake as f64 * multiplier * points.max(0.0).min(1.0);
+    (payout + EPSILON).floor() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn answers(list: &[(u128, f64)]) -> HashMap<AccountId, f64> {
+        list.iter()
+            .map(|(id, value)| (AccountId(Uuid::from_u128(*id)), *value))
+            .collect()
+    }
+
+    fn points_of(points: &HashMap<AccountId, f64>, id: u128) -> f64 {
+        points[&AccountId(Uuid::from_u128(id))]
+    }
+
+    fn choices(names: &[&str]) -> Vec<ChoiceName> {
+        names.iter().map(|n| ChoiceName(n.to_string())).collect()
+    }
+
+    #[test]
+    fn single_choice_is_all_or_nothing() {
+        let resolved = choices(&["a"]);
+        let score = |answer: &[&str]| {
+            score(
+                PollKind::Single,
+                PollScoring::Exact,
+                &resolved,
+                &choices(answer),
+            )
+        };
+        assert_eq!(score(&["a"]), 1.0);
+        assert_eq!(score(&["b"]), 0.0);
+    }
+
+    #[test]
+    fn exact_scoring_requires_the_same_set() {
+        let resolved = choices(&["a", "b"]);
+        let score = |answer: &[&str]| {
+            score(
+                PollKind::Multiple,
+                PollScoring::Exact,
+                &resolved,
+                &choices(answer),
+            )
+        };
+        assert_eq!(score(&["b", "a"]), 1.0);
+        assert_eq!(score(&["a"]), 0.0);
+        assert_eq!(score(&["a", "b", "c"]), 0.0);
+    }
+
+    #[test]
+    fn partial_scoring_penalizes_wrong_choices() {
+        let resolved = choices(&["a", "b"]);
+        let score = |answer: &[&str]| {
+            score(
+                PollKind::Multiple,
+                PollScoring::Partial,
+                &resolved,
+                &choices(answer),
+            )
+        };
+        assert_eq!(score(&["a", "b"]), 1.0);
+        assert_eq!(score(&["a"]), 0.5);
+        assert_eq!(score(&["a", "c"]), 0.0);
+        assert_eq!(score(&["c", "d"]), 0.0);
+        assert_eq!(score(&["a", "b", "c"]), 0.5);
+    }
+
+    #[test]
+    fn numeric_exact_and_tolerance() {
+        let answers = answers(&[(1, 3.0), (2, 2.5), (3, 4.0)]);
+
+        let exact = score_numeric(PollScoring::Exact, None, 3.0, &answers);
+        assert_eq!(points_of(&exact, 1), 1.0);
+        assert_eq!(points_of(&exact, 2), 0.0);
+
+        let tolerance = score_numeric(PollScoring::Tolerance, Some(0.5), 3.0, &answers);
+        assert_eq!(points_of(&tolerance, 1), 1.0);
+        assert_eq!(points_of(&tolerance, 2), 1.0);
+        assert_eq!(points_of(&tolerance, 3), 0.0);
+    }
+
+    #[test]
+    fn numeric_closest_ranks_by_distance() {
+        let answers = answers(&[(1, 10.0), (2, 12.0), (3, 8.0), (4, 20.0)]);
+        let points = score_numeric(PollScoring::Closest, None, 11.0, &answers);
+        // 1と2は同着で最も近い
+        assert_eq!(points_of(&points, 1), 1.0);
+        assert_eq!(points_of(&points, 2), 1.0);
+        assert_eq!(points_of(&points, 3), 0.5);
+        assert_eq!(points_of(&points, 4), 0.25);
+    }
+
+    #[test]
+    fn ranking_by_position_and_pairs() {
+        let resolved = choices(&["a", "b", "c"]);
+        let score = |scoring, answer: &[&str]| score_ranking(scoring, &resolved, &choices(answer));
+
+        assert_eq!(score(PollScoring::Exact, &["a", "b", "c"]), 1.0);
+        assert_eq!(score(PollScoring::Exact, &["a", "c", "b"]), 0.0);
+
+        assert_eq!(score(PollScoring::Position, &["a", "c", "b"]), 1.0 / 3.0);
+        assert_eq!(score(PollScoring::Position, &["c", "a", "b"]), 0.0);
+
+        // (a, b), (a, c) は一致し、(b, c) は逆
+        assert_eq!(score(PollScoring::Kendall, &["a", "c", "b"]), 2.0 / 3.0);
+        assert_eq!(score(PollScoring::Kendall, &["c", "b", "a"]), 0.0);
+        // 回答に無い b は最下位とみなすので、(a, b) だけが一致する
+        assert_eq!(score(PollScoring::Kendall, &["c", "d", "a"]), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn stake_payout_is_floored_by_points() {
+        assert_eq!(stake_payout(10, 2.5, 1.0), 25);
+        assert_eq!(stake_payout(10, 2.5, 0.0), 0);
+        assert_eq!(stake_payout(3, 1.5, 1.0), 4);
+        // 部分点では、賭けたポイントを下回ることがある
+        assert_eq!(stake_payout(10, 1.5, 0.5), 7);
+        assert_eq!(stake_payout(10, 3.0, 1.0 / 3.0), 10);
+    }
+}
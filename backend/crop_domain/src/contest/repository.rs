@@ -1,4 +1,9 @@
-use crate::contest::ContestId;
+use crate::contest::{
+    draft::DraftQueue,
+    export::Export,
+    poll::{market::Market, PollId},
+    ContestId,
+};
 use crop_infra::pg::Connection;
 
 pub trait ContestRepository {
@@ -28,6 +33,22 @@ pub trait ContestRepository {
     {
         C::query_by_id(self.conn(), id)
     }
+
+    /// まだ公開されていないPollの下書きを、公開する順に取得する
+    fn query_drafts(&self, contest_id: &ContestId) -> anyhow::Result<DraftQueue> {
+        DraftQueue::query(self.conn(), contest_id)
+    }
+
+    /// Pollに開かれたマーケットを、全ての取引と共に取得する
+    fn query_market(&self, poll_id: &PollId) -> anyhow::Result<Option<Market>> {
+        Market::query(self.conn(), poll_id)
+    }
+
+    /// 終了したContestの結果を取得する。
+    /// Contestが終了していない場合は `NotFinished` エラーになる。
+    fn query_export(&self, contest_id: &ContestId) -> anyhow::Result<Option<Export>> {
+        crate::contest::export::query(self.conn(), contest_id)
+    }
 }
 
 impl ContestRepository for Connection {
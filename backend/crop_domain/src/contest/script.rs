@@ -0,0 +1,154 @@
+//! 事前に用意したContestの台本
+//!
+//! Contestの情報と、出題するPollの一覧を1つのファイルにまとめたもの。
+//! 読み込むとUpcomingなContestとして作成され、各PollはContestの下書きとして保存される。
+use crate::contest::{
+    self,
+    draft::{self, PollDraft},
+    poll::{Choice, ChoiceColor, ChoiceName},
+    Contest as _, Updatable,
+};
+use chrono::{DateTime, Duration, Utc};
+use crop_infra::pg::{
+    poll_draft::{NewPollDraft, PollDraftTable as _},
+    Connection,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Script {
+    pub title: String,
+    pub category: String,
+    #[serde(default)]
+    pub event_start_at: Option<DateTime<Utc>>,
+    /// 出題する順に並べる
+    pub polls: Vec<ScriptPoll>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScriptPoll {
+    pub title: String,
+    #[serde(default)]
+    pub duration_sec: Option<i32>,
+    pub choices: Vec<ScriptChoice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScriptChoice {
+    pub name: ChoiceName,
+    pub color: ChoiceColor,
+}
+
+impl Script {
+    /// 台本の内容を確認し、ContestとPollの下書きを作成する。
+    /// 1つでも不正なPollがあれば、何も作成しない。
+    pub fn import(self) -> anyhow::Result<Imported> {
+        if self.title.trim().is_empty() {
+            return Err(anyhow::anyhow!("Contest title is empty"));
+        }
+        if self.polls.is_empty() {
+            return Err(anyhow::anyhow!("Script has no poll"));
+        }
+
+        let drafts = self
+            .polls
+            .into_iter()
+            .enumerate()
+            .map(|(i, poll)| {
+                let choices = poll
+                    .choices
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, c)| Choice {
+                        idx,
+                        name: c.name,
+                        color: c.color,
+                    })
+                    .collect();
+                let duration = poll.duration_sec.map(|s| Duration::seconds(s as i64));
+                draft::new(i + 1, poll.title, duration, choices)
+                    .map_err(|e| anyhow::anyhow!("polls[{}] : {}", i, e))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let contest = contest::new(self.title, self.category, self.event_start_at).upcoming();
+
+        Ok(Imported { contest, drafts })
+    }
+}
+
+#[must_use]
+pub struct Imported {
+    pub contest: contest::New,
+    pub drafts: Vec<PollDraft>,
+}
+
+impl Updatable for Imported {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        self.contest.save(conn)?;
+
+        let choices = self
+            .drafts
+            .iter()
+            .map(|draft| serde_json::to_value(draft.choices()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let new_drafts = self
+            .drafts
+            .iter()
+            .zip(choices.iter())
+            .map(|(draft, choices)| NewPollDraft {
+                id: &draft.id().0,
+                contest_id: &self.contest.id().0,
+                idx: draft.idx() as i32,
+                title: draft.title(),
+                duration_sec: draft.duration().map(|d| d.num_seconds() as i32),
+                choices,
+            })
+            .collect();
+        conn.save_all(&new_drafts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(choices: &[&str]) -> Script {
+        Script {
+            title: "Final".to_string(),
+            category: "quiz".to_string(),
+            event_start_at: None,
+            polls: vec![ScriptPoll {
+                title: "Who wins?".to_string(),
+                duration_sec: Some(30),
+                choices: choices
+                    .iter()
+                    .map(|name| ScriptChoice {
+                        name: ChoiceName(name.to_string()),
+                        color: ChoiceColor("#000000".to_string()),
+                    })
+                    .collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn import_creates_upcoming_contest_with_drafts() {
+        let imported = script(&["red", "blue"]).import().unwrap();
+        assert_eq!(imported.contest.status(), contest::ContestStatus::Upcoming);
+        assert_eq!(imported.drafts.len(), 1);
+        assert_eq!(imported.drafts[0].idx(), 1);
+        assert_eq!(imported.drafts[0].choices()[1].idx, 1);
+    }
+
+    #[test]
+    fn import_rejects_invalid_poll() {
+        assert!(script(&["red"]).import().is_err());
+        assert!(script(&["red", "red"]).import().is_err());
+
+        let mut zero_duration = script(&["red", "blue"]);
+        zero_duration.polls[0].duration_sec = Some(0);
+        assert!(zero_duration.import().is_err());
+    }
+}
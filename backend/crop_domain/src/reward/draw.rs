@@ -0,0 +1,214 @@
+//! 当選者の選び方
+//!
+//! 抽選はseedから決定的に計算する。
+//! 同じseedと同じスコアからは、常に同じ当選者が選ばれる。
+//!
+//! seedのハッシュ値はContestが終了する前に公開し、seed自体は当選者を選んだ後に公開する。
+//! 参加者は公開されたseedとスコアから `verify` で抽選をやり直し、結果を確かめられる。
+//!
+//! スコアには部分点による端数がありうるので、抽選の重みにはスコアを
+//! `WEIGHT_SCALE` 倍して四捨五入した整数を使う。
+//! 当選者を選んだ時点のスコアは記録しておき、検証にはその値を使う。
+use crate::account::AccountId;
+use rand::{thread_rng, Rng as _};
+use ring::digest;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+pub const SEED_LEN: usize = 32;
+
+/// 部分点は小数点以下3桁までの精度で重みに反映する
+const WEIGHT_SCALE: f64 = 1000.0;
+
+pub fn gen_seed() -> Vec<u8> {
+    let mut seed = vec![0u8; SEED_LEN];
+    thread_rng().fill(seed.as_mut_slice());
+    seed
+}
+
+/// seedを公開する前に、先に公開しておく値。
+/// `SHA-256(seed)` で計算する。
+pub fn hash_seed(seed: &[u8]) -> Vec<u8> {
+    digest::digest(&digest::SHA256, seed).as_ref().to_vec()
+}
+
+/// 抽選の元になった、各アカウントのスコア
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AccountScore {
+    pub account_id: AccountId,
+    pub score: f64,
+}
+
+/// 公開されたseedとスコアから、抽選をやり直す。
+/// seedが事前に公開されたハッシュ値と一致しない場合は `None` を返す。
+pub fn verify(
+    seed: &[u8],
+    seed_hash: &[u8],
+    scores: &[AccountScore],
+    n: usize,
+) -> Option<Vec<AccountId>> {
+    if hash_seed(seed).as_slice() != seed_hash {
+        return None;
+    }
+    let scores = scores
+        .iter()
+        .map(|s| (s.account_id, s.score))
+        .collect::<HashMap<_, _>>();
+    Some(lottery(&scores, n, seed))
+}
+
+/// スコアの高い順にn人を選ぶ。
+/// 同点の場合はAccountIdの順に選ぶ。
+/// スコアが0のアカウントは選ばない。
+pub fn top(scores: &HashMap<AccountId, f64>, n: usize) -> Vec<AccountId> {
+    let mut candidates = scores
+        .iter()
+        .filter(|(_, score)| **score > 0.0)
+        .map(|(id, score)| (*id, *score))
+        .collect::<Vec<_>>();
+    candidates.sort_by(|(a_id, a_score), (b_id, b_score)| {
+        b_score
+            .partial_cmp(a_score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a_id.0.cmp(&b_id.0))
+    });
+    candidates.into_iter().take(n).map(|(id, _)| id).collect()
+}
+
+/// スコアに比例した重みで、重複なしでn人を抽選する。
+/// 重みが0のアカウントは選ばない。
+///
+/// 候補をAccountIdの順に並べ、i人目（0始まり）は
+/// `SHA-256(seed || i)` の先頭8byteをビッグエンディアンの整数として、
+/// 残っている候補の重みの合計で割った余りが累積の重みのどこに入るかで選ぶ。
+/// 重みの合計は2^64に比べて十分小さいので、剰余による偏りは無視できる。
+pub fn lottery(scores: &HashMap<AccountId, f64>, n: usize, seed: &[u8]) -> Vec<AccountId> {
+    let mut candidates = scores
+        .iter()
+        .map(|(id, score)| (*id, weight(*score)))
+        .filter(|(_, weight)| *weight > 0)
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(id, _)| id.0);
+
+    let mut winners = Vec::with_capacity(n.min(candidates.len()));
+    for i in 0..n {
+        let total = candidates.iter().map(|(_, weight)| *weight).sum::<u64>();
+        if total == 0 {
+            break;
+        }
+
+        let mut point = random_u64(seed, i as u64) % total;
+        let picked = candidates
+            .iter()
+            .position(|(_, weight)| {
+                if point < *weight {
+                    true
+                } else {
+                    point -= *weight;
+                    false
+                }
+            })
+            .unwrap();
+        winners.push(candidates.remove(picked).0);
+    }
+    winners
+}
+
+/// 負のスコアやNaNは0として扱う
+fn weight(score: f64) -> u64 {
+    (score * WEIGHT_SCALE).round() as u64
+}
+
+fn random_u64(seed: &[u8], i: u64) -> u64 {
+    let mut ctx = digest::Context::new(&digest::SHA256);
+    ctx.update(seed);
+    ctx.update(&i.to_be_bytes());
+    let hash = ctx.finish();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.as_ref()[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn scores(list: &[(u128, f64)]) -> HashMap<AccountId, f64> {
+        list.iter()
+            .map(|(id, score)| (AccountId(Uuid::from_u128(*id)), *score))
+            .collect()
+    }
+
+    fn ids(list: &[u128]) -> Vec<AccountId> {
+        list.iter()
+            .map(|id| AccountId(Uuid::from_u128(*id)))
+            .collect()
+    }
+
+    #[test]
+    fn top_breaks_ties_by_account_id() {
+        let scores = scores(&[(3, 2.0), (1, 5.0), (2, 2.0), (4, 0.0)]);
+        assert_eq!(top(&scores, 3), ids(&[1, 2, 3]));
+        assert_eq!(top(&scores, 10), ids(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn lottery_is_deterministic_and_skips_zero_score() {
+        let scores = scores(&[(1, 1.0), (2, 3.0), (3, 0.0), (4, 2.0), (5, 1.0)]);
+        let seed = b"seed";
+
+        let winners = lottery(&scores, 3, seed);
+        assert_eq!(winners, lottery(&scores, 3, seed));
+        assert_eq!(winners.len(), 3);
+        assert!(!winners.contains(&AccountId(Uuid::from_u128(3))));
+
+        let mut unique = winners.clone();
+        unique.sort_by_key(|id| id.0);
+        unique.dedup();
+        assert_eq!(unique.len(), 3);
+
+        assert_eq!(lottery(&scores, 10, seed).len(), 4);
+    }
+
+    #[test]
+    fn partial_score_is_weighed_without_truncation() {
+        let scores = scores(&[(1, 1.5), (2, 1.75), (3, 0.5), (4, 0.0)]);
+        assert_eq!(top(&scores, 4), ids(&[2, 1, 3]));
+
+        // 1点に満たないアカウントも抽選の対象になる
+        let winners = lottery(&scores, 4, b"seed");
+        assert_eq!(winners.len(), 3);
+        assert!(winners.contains(&AccountId(Uuid::from_u128(3))));
+
+        assert_eq!(weight(1.9), 1900);
+        assert_eq!(weight(1.0), 1000);
+        assert_eq!(weight(0.0005), 1);
+        assert_eq!(weight(-1.0), 0);
+    }
+
+    #[test]
+    fn verify_rejects_seed_not_matching_hash() {
+        let scores = vec![
+            AccountScore {
+                account_id: AccountId(Uuid::from_u128(1)),
+                score: 2.0,
+            },
+            AccountScore {
+                account_id: AccountId(Uuid::from_u128(2)),
+                score: 1.0,
+            },
+        ];
+        let seed = gen_seed();
+        let seed_hash = hash_seed(seed.as_slice());
+
+        let map = scores.iter().map(|s| (s.account_id, s.score)).collect();
+        assert_eq!(
+            verify(seed.as_slice(), seed_hash.as_slice(), &scores, 1),
+            Some(lottery(&map, 1, seed.as_slice()))
+        );
+        assert_eq!(verify(b"other", seed_hash.as_slice(), &scores, 1), None);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod draw;
+pub mod model;
+pub mod notifier;
+pub mod repository;
+pub mod verification;
+
+pub use model::*;
+pub use repository::*;
@@ -0,0 +1,33 @@
+use super::RewardId;
+use crate::reward::Updatable;
+use crop_infra::pg::{
+    reward_code::{NewRewardCode, RewardCodeTable as _},
+    Connection,
+};
+
+#[must_use]
+pub struct CodesAdded {
+    pub(super) reward_id: RewardId,
+    pub(super) codes: Vec<String>,
+}
+
+impl CodesAdded {
+    pub fn codes(&self) -> &[String] {
+        self.codes.as_slice()
+    }
+}
+
+impl Updatable for CodesAdded {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let new_codes = self
+            .codes
+            .iter()
+            .map(|code| NewRewardCode {
+                reward_id: &self.reward_id.0,
+                code: code.as_str(),
+            })
+            .collect::<Vec<_>>();
+        conn.save_all(new_codes.as_slice())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use super::DeliveryStatus;
+use crate::reward::Updatable;
+use chrono::{DateTime, Utc};
+use crop_infra::pg::{reward_delivery::RewardDeliveryTable as _, Connection};
+
+#[must_use]
+pub struct Delivered {
+    pub(super) delivery_id: i32,
+    /// 失敗した場合はエラーの内容
+    pub(super) result: Result<(), String>,
+    pub(super) delivered_at: DateTime<Utc>,
+}
+
+impl Delivered {
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.result.as_ref().err().map(String::as_str)
+    }
+}
+
+impl Updatable for Delivered {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        match &self.result {
+            Ok(()) => conn.update_status(
+                self.delivery_id,
+                DeliveryStatus::Sent,
+                None,
+                Some(&self.delivered_at),
+            ),
+            Err(e) => conn.update_status(
+                self.delivery_id,
+                DeliveryStatus::Failed,
+                Some(e.as_str()),
+                None,
+            ),
+        }
+    }
+}
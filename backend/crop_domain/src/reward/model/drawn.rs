@@ -0,0 +1,77 @@
+use super::{RewardId, Selection};
+use crate::account::AccountId;
+use crate::contest::ContestId;
+use crate::reward::{draw::AccountScore, Updatable};
+use chrono::{DateTime, Utc};
+use crop_infra::pg::{
+    reward::RewardTable as _,
+    reward_delivery::{NewRewardDelivery, RewardDeliveryTable},
+    reward_draw_score::{NewRewardDrawScore, RewardDrawScoreTable},
+    Connection,
+};
+
+#[must_use]
+pub struct Drawn {
+    pub(super) reward_id: RewardId,
+    pub(super) contest_id: ContestId,
+    pub(super) selection: Selection,
+    /// 抽選の場合は、ここで公開される
+    pub(super) seed: Option<Vec<u8>>,
+    pub(super) drawn_at: DateTime<Utc>,
+    /// 当選順に並んだ、当選者と割り当てられたギフトコード
+    pub(super) winners: Vec<(AccountId, i32)>,
+    /// 当選者を選んだ時点の各アカウントのスコア。AccountIdの順
+    pub(super) scores: Vec<AccountScore>,
+}
+
+impl Drawn {
+    pub fn reward_id(&self) -> &RewardId {
+        &self.reward_id
+    }
+
+    pub fn contest_id(&self) -> &ContestId {
+        &self.contest_id
+    }
+
+    pub fn selection(&self) -> Selection {
+        self.selection
+    }
+
+    pub fn seed(&self) -> Option<&[u8]> {
+        self.seed.as_deref()
+    }
+
+    pub fn winners(&self) -> impl Iterator<Item = &AccountId> {
+        self.winners.iter().map(|(account_id, _)| account_id)
+    }
+}
+
+impl Updatable for Drawn {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        conn.update_drawn(&self.reward_id.0, &self.drawn_at)?;
+
+        let deliveries = self
+            .winners
+            .iter()
+            .enumerate()
+            .map(|(i, (account_id, code_id))| NewRewardDelivery {
+                reward_id: &self.reward_id.0,
+                account_id: &account_id.0,
+                code_id: *code_id,
+                rank: i as i32 + 1,
+            })
+            .collect::<Vec<_>>();
+        RewardDeliveryTable::save_all(conn, deliveries.as_slice())?;
+
+        let scores = self
+            .scores
+            .iter()
+            .map(|s| NewRewardDrawScore {
+                reward_id: &self.reward_id.0,
+                account_id: &s.account_id.0,
+                score: s.score,
+            })
+            .collect::<Vec<_>>();
+        RewardDrawScoreTable::save_all(conn, scores.as_slice())
+    }
+}
@@ -0,0 +1,440 @@
+use crate::account::AccountId;
+use crate::contest::{poll, ContestId, ContestStatus, WithAttrs, WithPolls};
+use crate::reward::{
+    draw::{self, AccountScore},
+    notifier::{Notice, Notifier, Recipient},
+};
+use chrono::{DateTime, Utc};
+use crop_infra::pg::{
+    reward::QueriedReward, reward_code::QueriedRewardCode, reward_delivery::QueriedRewardDelivery,
+    reward_draw_score::QueriedRewardDrawScore,
+};
+use data_encoding::HEXLOWER;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+mod codes_added;
+mod delivered;
+mod drawn;
+mod new;
+mod seed_committed;
+
+pub use codes_added::CodesAdded;
+pub use delivered::Delivered;
+pub use drawn::Drawn;
+pub use new::New;
+pub use seed_committed::SeedCommitted;
+
+pub type Selection = crop_infra::pg::types::RewardSelection;
+pub type DeliveryStatus = crop_infra::pg::types::RewardDeliveryStatus;
+
+pub fn new(
+    contest_id: ContestId,
+    name: String,
+    quantity: usize,
+    selection: Selection,
+) -> anyhow::Result<New> {
+    if name.trim().is_empty() {
+        return Err(anyhow::anyhow!("Reward name is empty"));
+    }
+    if quantity == 0 {
+        return Err(anyhow::anyhow!("Reward quantity must be positive"));
+    }
+
+    Ok(New {
+        id: RewardId::new(),
+        contest_id,
+        name,
+        quantity,
+        selection,
+    })
+}
+
+/// Contestの賞品。
+/// 当選者を選んだ後、登録されたギフトコードを1人に1つずつ送付する。
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Reward {
+    id: RewardId,
+    contest_id: ContestId,
+    name: String,
+    quantity: usize,
+    selection: Selection,
+    /// 当選者を選ぶまでは公開しない
+    #[serde(skip)]
+    seed: Option<Vec<u8>>,
+    /// 抽選に使うseedのハッシュ値。Contestが終了する前に公開する。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(serialize_with = "serialize_hex")]
+    #[schemars(with = "Option<String>")]
+    seed_hash: Option<Vec<u8>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed_committed_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drawn_at: Option<DateTime<Utc>>,
+    /// 登録されたギフトコードの数。コード自体は公開しない
+    #[serde(rename = "num_codes", serialize_with = "serialize_len")]
+    #[schemars(with = "usize")]
+    codes: Vec<GiftCode>,
+    winners: Vec<Winner>,
+    /// 当選者を選んだ時点の各アカウントのスコア。AccountIdの順
+    #[serde(skip)]
+    drawn_scores: Vec<AccountScore>,
+}
+
+pub(crate) fn serialize_hex<S>(value: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    value
+        .as_ref()
+        .map(|bytes| HEXLOWER.encode(bytes))
+        .serialize(serializer)
+}
+
+fn serialize_len<S>(codes: &[GiftCode], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    codes.len().serialize(serializer)
+}
+
+#[derive(Debug, Clone)]
+struct GiftCode {
+    id: i32,
+    code: String,
+}
+
+/// 当選者と、ギフトコードの送付状況
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Winner {
+    #[serde(skip)]
+    delivery_id: i32,
+    #[serde(skip)]
+    code_id: i32,
+    rank: usize,
+    account_id: AccountId,
+    status: DeliveryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delivered_at: Option<DateTime<Utc>>,
+}
+
+impl Reward {
+    pub fn id(&self) -> &RewardId {
+        &self.id
+    }
+
+    pub fn contest_id(&self) -> &ContestId {
+        &self.contest_id
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn quantity(&self) -> usize {
+        self.quantity
+    }
+
+    pub fn selection(&self) -> Selection {
+        self.selection
+    }
+
+    /// 抽選に使うseed。
+    /// 当選者を選ぶまでは公開してはならない。
+    pub fn seed(&self) -> Option<&[u8]> {
+        self.seed.as_deref()
+    }
+
+    pub fn seed_hash(&self) -> Option<&[u8]> {
+        self.seed_hash.as_deref()
+    }
+
+    pub fn seed_committed_at(&self) -> Option<&DateTime<Utc>> {
+        self.seed_committed_at.as_ref()
+    }
+
+    pub fn drawn_at(&self) -> Option<&DateTime<Utc>> {
+        self.drawn_at.as_ref()
+    }
+
+    pub fn num_codes(&self) -> usize {
+        self.codes.len()
+    }
+
+    pub fn winners(&self) -> &[Winner] {
+        self.winners.as_slice()
+    }
+
+    /// 当選者を選んだ時点の各アカウントのスコア。
+    /// 抽選の検証には、現在のスコアではなくこの値を使う。
+    pub fn drawn_scores(&self) -> &[AccountScore] {
+        self.drawn_scores.as_slice()
+    }
+
+    /// まだギフトコードを受け取っていない当選者。
+    /// 送付に失敗した当選者も含む。
+    pub fn undelivered_winners(&self) -> impl Iterator<Item = &Winner> {
+        self.winners
+            .iter()
+            .filter(|w| w.status != DeliveryStatus::Sent)
+    }
+
+    /// ギフトコードを登録する。
+    /// 空行は無視し、登録済みのコードは重複して登録しない。
+    pub fn add_codes(&self, codes: Vec<String>) -> anyhow::Result<CodesAdded> {
+        if self.drawn_at.is_some() {
+            return Err(anyhow::anyhow!("Reward is already drawn"));
+        }
+
+        let mut codes = codes
+            .into_iter()
+            .map(|code| code.trim().to_string())
+            .filter(|code| !code.is_empty())
+            .collect::<Vec<_>>();
+        codes.sort();
+        codes.dedup();
+        if codes.is_empty() {
+            return Err(anyhow::anyhow!("No gift code is given"));
+        }
+
+        Ok(CodesAdded {
+            reward_id: self.id,
+            codes,
+        })
+    }
+
+    /// 抽選に使うseedを決め、そのハッシュ値を公開できるようにする。
+    /// スコアを見てからseedを選べないよう、Contestが終了する前にしか行えない。
+    pub fn commit_seed<C>(&self, contest: &C, seed: Vec<u8>) -> anyhow::Result<SeedCommitted>
+    where
+        C: WithAttrs,
+    {
+        if self.selection != Selection::Lottery {
+            return Err(anyhow::anyhow!("Reward is not drawn by lottery"));
+        }
+        if self.seed_hash.is_some() {
+            return Err(anyhow::anyhow!("Seed is already committed"));
+        }
+        if contest.id() != &self.contest_id {
+            return Err(anyhow::anyhow!("Reward is not for this contest"));
+        }
+        if !matches!(
+            contest.status(),
+            ContestStatus::Upcoming | ContestStatus::Open
+        ) {
+            return Err(anyhow::anyhow!("Contest is already finished"));
+        }
+        if seed.len() < draw::SEED_LEN {
+            return Err(anyhow::anyhow!("Seed is too short"));
+        }
+
+        Ok(SeedCommitted {
+            reward_id: self.id,
+            contest_id: self.contest_id,
+            seed_hash: draw::hash_seed(seed.as_slice()),
+            seed,
+            committed_at: Utc::now(),
+        })
+    }
+
+    /// 終了したContestのスコアから当選者を選び、ギフトコードを割り当てる。
+    /// 当選者の数は、賞品の数と正解したアカウントの数の少ない方になる。
+    /// 抽選の場合は、事前にseedを決めておく必要がある。
+    pub fn draw<C>(&self, contest: &C) -> anyhow::Result<Drawn>
+    where
+        C: WithAttrs + WithPolls,
+        <C as WithPolls>::Poll: poll::WithAttrs + poll::WithUserChoices,
+    {
+        if self.drawn_at.is_some() {
+            return Err(anyhow::anyhow!("Reward is already drawn"));
+        }
+        if contest.id() != &self.contest_id {
+            return Err(anyhow::anyhow!("Reward is not for this contest"));
+        }
+        if !matches!(
+            contest.status(),
+            ContestStatus::Closed | ContestStatus::Archived
+        ) {
+            return Err(anyhow::anyhow!("Contest is not finished"));
+        }
+
+        let scores = contest.compute_account_scores();
+        let mut snapshot = scores
+            .iter()
+            .map(|(account_id, score)| AccountScore {
+                account_id: *account_id,
+                score: *score,
+            })
+            .collect::<Vec<_>>();
+        snapshot.sort_by_key(|s| s.account_id.0);
+        let winners = match self.selection {
+            Selection::Top => draw::top(&scores, self.quantity),
+            Selection::Lottery => {
+                let seed = self.seed.as_ref().ok_or(NotCommitted)?;
+                draw::lottery(&scores, self.quantity, seed.as_slice())
+            }
+        };
+        if winners.is_empty() {
+            return Err(anyhow::anyhow!("No account is eligible"));
+        }
+        if self.codes.len() < winners.len() {
+            return Err(anyhow::anyhow!(
+                "Not enough gift codes : {} codes for {} winners",
+                self.codes.len(),
+                winners.len()
+            ));
+        }
+
+        let winners = winners
+            .into_iter()
+            .zip(self.codes.iter())
+            .map(|(account_id, code)| (account_id, code.id))
+            .collect();
+        Ok(Drawn {
+            reward_id: self.id,
+            contest_id: self.contest_id,
+            selection: self.selection,
+            seed: self.seed.clone(),
+            drawn_at: Utc::now(),
+            winners,
+            scores: snapshot,
+        })
+    }
+
+    /// 当選者にギフトコードを送付する。
+    /// 送付に失敗した場合も、その結果を記録するために `Delivered` を返す。
+    pub fn deliver<N>(
+        &self,
+        winner: &Winner,
+        recipient: &Recipient,
+        notifier: &N,
+    ) -> anyhow::Result<Delivered>
+    where
+        N: Notifier + ?Sized,
+    {
+        if winner.status == DeliveryStatus::Sent {
+            return Err(anyhow::anyhow!("Gift code is already delivered"));
+        }
+        if recipient.account_id != winner.account_id {
+            return Err(anyhow::anyhow!("Recipient is not the winner"));
+        }
+        let code = self
+            .codes
+            .iter()
+            .find(|code| code.id == winner.code_id)
+            .ok_or_else(|| anyhow::anyhow!("Gift code is missing"))?;
+
+        let notice = Notice {
+            reward_name: self.name.as_str(),
+            recipient,
+            code: code.code.as_str(),
+        };
+        let result = notifier.notify(&notice).map_err(|e| format!("{:#}", e));
+        Ok(Delivered {
+            delivery_id: winner.delivery_id,
+            result,
+            delivered_at: Utc::now(),
+        })
+    }
+}
+
+impl Reward {
+    pub(crate) fn from_queried(
+        reward: QueriedReward,
+        codes: Vec<QueriedRewardCode>,
+        deliveries: Vec<QueriedRewardDelivery>,
+        drawn_scores: Vec<QueriedRewardDrawScore>,
+    ) -> Reward {
+        let winners = deliveries
+            .into_iter()
+            .map(|d| Winner {
+                delivery_id: d.id,
+                code_id: d.code_id,
+                rank: d.rank as usize,
+                account_id: AccountId(d.account_id),
+                status: d.status,
+                error: d.error,
+                delivered_at: d.delivered_at,
+            })
+            .collect();
+        Reward {
+            id: RewardId(reward.id),
+            contest_id: ContestId(reward.contest_id),
+            name: reward.name,
+            quantity: reward.quantity as usize,
+            selection: reward.selection,
+            seed: reward.seed,
+            seed_hash: reward.seed_hash,
+            seed_committed_at: reward.seed_committed_at,
+            drawn_at: reward.drawn_at,
+            codes: codes
+                .into_iter()
+                .map(|c| GiftCode {
+                    id: c.id,
+                    code: c.code,
+                })
+                .collect(),
+            winners,
+            drawn_scores: drawn_scores
+                .into_iter()
+                .map(|s| AccountScore {
+                    account_id: AccountId(s.account_id),
+                    score: s.score,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Winner {
+    /// 当選順。1から始まる。
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    pub fn status(&self) -> DeliveryStatus {
+        self.status
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+/// 抽選に使うseedがまだ決められていない
+#[derive(Debug)]
+pub struct NotCommitted;
+
+impl std::fmt::Display for NotCommitted {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Seed is not committed")
+    }
+}
+
+impl std::error::Error for NotCommitted {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct RewardId(pub Uuid);
+
+impl RewardId {
+    pub fn new() -> RewardId {
+        RewardId(Uuid::new_v4())
+    }
+}
+
+impl FromStr for RewardId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(RewardId(Uuid::parse_str(s)?))
+    }
+}
@@ -0,0 +1,51 @@
+use super::{RewardId, Selection};
+use crate::contest::ContestId;
+use crate::reward::Updatable;
+use crop_infra::pg::{
+    reward::{NewReward, RewardTable as _},
+    Connection,
+};
+
+#[must_use]
+pub struct New {
+    pub(super) id: RewardId,
+    pub(super) contest_id: ContestId,
+    pub(super) name: String,
+    pub(super) quantity: usize,
+    pub(super) selection: Selection,
+}
+
+impl New {
+    pub fn id(&self) -> &RewardId {
+        &self.id
+    }
+
+    pub fn contest_id(&self) -> &ContestId {
+        &self.contest_id
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn quantity(&self) -> usize {
+        self.quantity
+    }
+
+    pub fn selection(&self) -> Selection {
+        self.selection
+    }
+}
+
+impl Updatable for New {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        let new_reward = NewReward {
+            id: &self.id.0,
+            contest_id: &self.contest_id.0,
+            name: self.name.as_str(),
+            quantity: self.quantity as i32,
+            selection: self.selection,
+        };
+        conn.save(new_reward)
+    }
+}
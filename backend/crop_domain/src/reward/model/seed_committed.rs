@@ -0,0 +1,39 @@
+use super::RewardId;
+use crate::contest::ContestId;
+use crate::reward::Updatable;
+use chrono::{DateTime, Utc};
+use crop_infra::pg::{reward::RewardTable as _, Connection};
+
+#[must_use]
+pub struct SeedCommitted {
+    pub(super) reward_id: RewardId,
+    pub(super) contest_id: ContestId,
+    pub(super) seed: Vec<u8>,
+    pub(super) seed_hash: Vec<u8>,
+    pub(super) committed_at: DateTime<Utc>,
+}
+
+impl SeedCommitted {
+    pub fn reward_id(&self) -> &RewardId {
+        &self.reward_id
+    }
+
+    pub fn contest_id(&self) -> &ContestId {
+        &self.contest_id
+    }
+
+    pub fn seed_hash(&self) -> &[u8] {
+        self.seed_hash.as_slice()
+    }
+}
+
+impl Updatable for SeedCommitted {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()> {
+        conn.update_seed(
+            &self.reward_id.0,
+            self.seed.as_slice(),
+            self.seed_hash.as_slice(),
+            &self.committed_at,
+        )
+    }
+}
@@ -0,0 +1,54 @@
+//! 当選者へのギフトコードの送付
+//!
+//! 送付の手段は `Notifier` として差し替えられるようにしておく。
+//! 本番のメール送信などはまだ用意しておらず、`LocalNotifier` をその代わりに使う。
+use crate::account::AccountId;
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::PathBuf;
+
+/// ギフトコードの送り先
+#[derive(Debug, Clone, Serialize)]
+pub struct Recipient {
+    pub account_id: AccountId,
+    pub name: String,
+    /// 認証情報を登録していないアカウントでは無い
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Notice<'a> {
+    pub reward_name: &'a str,
+    pub recipient: &'a Recipient,
+    pub code: &'a str,
+}
+
+pub trait Notifier {
+    fn notify(&self, notice: &Notice) -> anyhow::Result<()>;
+}
+
+/// 送付する内容をJSON Linesとしてファイルに追記する。
+/// 実際の送付は、運営がこのファイルを元に手作業で行う。
+#[derive(Debug, Clone)]
+pub struct LocalNotifier {
+    path: PathBuf,
+}
+
+impl LocalNotifier {
+    pub fn new<P: Into<PathBuf>>(path: P) -> LocalNotifier {
+        LocalNotifier { path: path.into() }
+    }
+}
+
+impl Notifier for LocalNotifier {
+    fn notify(&self, notice: &Notice) -> anyhow::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let line = serde_json::to_string(notice)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+}
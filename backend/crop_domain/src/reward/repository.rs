@@ -0,0 +1,89 @@
+use crate::account::AccountId;
+use crate::contest::ContestId;
+use crate::reward::{notifier::Recipient, verification::Verification, Reward, RewardId};
+use crop_infra::pg::{
+    account::AccountTable as _, account_credential::AccountCredentialTable as _,
+    reward::RewardTable, reward_code::RewardCodeTable, reward_delivery::RewardDeliveryTable,
+    reward_draw_score::RewardDrawScoreTable, Connection,
+};
+use std::collections::HashMap;
+
+pub trait RewardRepository {
+    fn conn(&self) -> &Connection;
+
+    fn save<R>(&self, reward: &R) -> anyhow::Result<()>
+    where
+        R: Updatable,
+    {
+        reward.save(self.conn())
+    }
+
+    fn query_by_id(&self, id: &RewardId) -> anyhow::Result<Option<Reward>> {
+        let conn = self.conn();
+        let reward = match RewardTable::query_by_id(conn, &id.0)? {
+            Some(reward) => reward,
+            None => return Ok(None),
+        };
+        let codes = RewardCodeTable::query_by_reward_id(conn, &id.0)?;
+        let deliveries = RewardDeliveryTable::query_by_reward_id(conn, &id.0)?;
+        let scores = RewardDrawScoreTable::query_by_reward_id(conn, &id.0)?;
+        Ok(Some(Reward::from_queried(
+            reward, codes, deliveries, scores,
+        )))
+    }
+
+    fn query_by_contest_id(&self, contest_id: &ContestId) -> anyhow::Result<Vec<Reward>> {
+        let conn = self.conn();
+        RewardTable::query_by_contest_id(conn, &contest_id.0)?
+            .into_iter()
+            .map(|reward| {
+                let codes = RewardCodeTable::query_by_reward_id(conn, &reward.id)?;
+                let deliveries = RewardDeliveryTable::query_by_reward_id(conn, &reward.id)?;
+                let scores = RewardDrawScoreTable::query_by_reward_id(conn, &reward.id)?;
+                Ok(Reward::from_queried(reward, codes, deliveries, scores))
+            })
+            .collect()
+    }
+
+    /// 抽選の検証に必要な情報を取得する。
+    /// seedが決められていない場合は `NotCommitted` エラーになる。
+    fn query_verification(&self, id: &RewardId) -> anyhow::Result<Option<Verification>> {
+        crate::reward::verification::query(self.conn(), id)
+    }
+
+    /// 当選者の名前とメールアドレスを取得する
+    fn query_recipients(
+        &self,
+        account_ids: &[AccountId],
+    ) -> anyhow::Result<HashMap<AccountId, Recipient>> {
+        let conn = self.conn();
+        let ids = account_ids.iter().map(|id| id.0).collect::<Vec<_>>();
+        let mut emails = conn
+            .query_emails_by_account_ids(ids.as_slice())?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        Ok(conn
+            .query_by_ids(ids.as_slice())?
+            .into_iter()
+            .map(|account| {
+                let account_id = AccountId(account.id);
+                let recipient = Recipient {
+                    account_id,
+                    name: account.name,
+                    email: emails.remove(&account.id),
+                };
+                (account_id, recipient)
+            })
+            .collect())
+    }
+}
+
+impl RewardRepository for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+pub trait Updatable {
+    fn save(&self, conn: &Connection) -> anyhow::Result<()>;
+}
@@ -0,0 +1,109 @@
+//! 抽選の検証
+//!
+//! 抽選で選んだ賞品について、事前に公開したseedのハッシュ値と、
+//! 当選者を選んだ後に公開するseed・スコア・当選者をまとめる。
+//! 誰でもこれを元に `draw::verify` で抽選をやり直せる。
+//!
+//! スコアは当選者を選んだ時点で記録したものを使う。
+//! その後に正解が訂正されても、検証の結果は変わらない。
+use crate::account::AccountId;
+use crate::contest::ContestId;
+use crate::reward::{
+    draw::{self, AccountScore},
+    NotCommitted, Reward, RewardId, RewardRepository as _,
+};
+use chrono::{DateTime, Utc};
+use crop_infra::pg::Connection;
+use data_encoding::HEXLOWER;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Verification {
+    reward_id: RewardId,
+    contest_id: ContestId,
+    quantity: usize,
+    seed_hash: String,
+    seed_committed_at: DateTime<Utc>,
+    /// 以下は当選者を選んだ後にだけ公開する
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drawn_at: Option<DateTime<Utc>>,
+    /// 当選順
+    #[serde(skip_serializing_if = "Option::is_none")]
+    winners: Option<Vec<AccountId>>,
+    /// 当選者を選んだ時点の各アカウントのスコア。AccountIdの順
+    #[serde(skip_serializing_if = "Option::is_none")]
+    accounts: Option<Vec<AccountScore>>,
+    /// 公開したseedとスコアから、サーバーがやり直した抽選の結果。
+    /// seedがハッシュ値と一致しない場合はnull
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recomputed_winners: Option<Option<Vec<AccountId>>>,
+    /// やり直した抽選の結果が、記録された当選者と一致するか
+    #[serde(skip_serializing_if = "Option::is_none")]
+    valid: Option<bool>,
+}
+
+impl Verification {
+    pub fn contest_id(&self) -> &ContestId {
+        &self.contest_id
+    }
+
+    pub fn build(reward: &Reward) -> anyhow::Result<Verification> {
+        let (seed, seed_hash, seed_committed_at) = match (
+            reward.seed(),
+            reward.seed_hash(),
+            reward.seed_committed_at(),
+        ) {
+            (Some(seed), Some(seed_hash), Some(committed_at)) => (seed, seed_hash, committed_at),
+            _ => return Err(NotCommitted.into()),
+        };
+
+        let mut verification = Verification {
+            reward_id: *reward.id(),
+            contest_id: *reward.contest_id(),
+            quantity: reward.quantity(),
+            seed_hash: HEXLOWER.encode(seed_hash),
+            seed_committed_at: *seed_committed_at,
+            seed: None,
+            drawn_at: None,
+            winners: None,
+            accounts: None,
+            recomputed_winners: None,
+            valid: None,
+        };
+
+        // 当選者を選ぶまでは、seedは秘密にしておく
+        let drawn_at = match reward.drawn_at() {
+            Some(drawn_at) => drawn_at,
+            None => return Ok(verification),
+        };
+
+        let winners = reward
+            .winners()
+            .iter()
+            .map(|w| *w.account_id())
+            .collect::<Vec<_>>();
+        let accounts = reward.drawn_scores().to_vec();
+        let recomputed = draw::verify(seed, seed_hash, accounts.as_slice(), reward.quantity());
+
+        verification.seed = Some(HEXLOWER.encode(seed));
+        verification.drawn_at = Some(*drawn_at);
+        verification.valid = Some(recomputed.as_ref() == Some(&winners));
+        verification.winners = Some(winners);
+        verification.accounts = Some(accounts);
+        verification.recomputed_winners = Some(recomputed);
+        Ok(verification)
+    }
+}
+
+pub(crate) fn query(
+    conn: &Connection,
+    reward_id: &RewardId,
+) -> anyhow::Result<Option<Verification>> {
+    match conn.query_by_id(reward_id)? {
+        Some(reward) => Verification::build(&reward).map(Some),
+        None => Ok(None),
+    }
+}
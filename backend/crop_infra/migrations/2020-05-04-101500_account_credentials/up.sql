@@ -0,0 +1,9 @@
+CREATE TABLE account_credentials (
+  account_id  UUID PRIMARY KEY,
+  email       TEXT UNIQUE NOT NULL,
+  cred        BYTEA NOT NULL, -- 64byte
+  salt        BYTEA NOT NULL, -- 64byte
+
+  CONSTRAINT account_credentials_account_fkey FOREIGN KEY (account_id)
+    REFERENCES accounts (id) ON UPDATE RESTRICT ON DELETE RESTRICT
+);
@@ -0,0 +1,3 @@
+ALTER TABLE admins DROP COLUMN disabled;
+ALTER TABLE admins DROP COLUMN role;
+DROP TYPE admin_role;
@@ -0,0 +1,11 @@
+CREATE TYPE admin_role AS ENUM (
+  'superadmin',
+  'operator',
+  'moderator'
+);
+
+/* 既存のAdminは全ての操作が可能だったので、superadminとする */
+ALTER TABLE admins ADD COLUMN role admin_role NOT NULL DEFAULT 'superadmin';
+ALTER TABLE admins ALTER COLUMN role SET DEFAULT 'operator';
+
+ALTER TABLE admins ADD COLUMN disabled BOOLEAN NOT NULL DEFAULT false;
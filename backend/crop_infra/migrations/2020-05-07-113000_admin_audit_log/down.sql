@@ -0,0 +1,2 @@
+DROP TABLE admin_audit_log;
+DROP TYPE admin_action;
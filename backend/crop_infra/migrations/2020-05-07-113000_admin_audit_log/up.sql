@@ -0,0 +1,29 @@
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest'
+);
+
+CREATE TABLE admin_audit_log (
+  id            SERIAL PRIMARY KEY,
+  admin_id      UUID NOT NULL,
+  action        admin_action NOT NULL,
+  contest_id    UUID, /* 対象のContest */
+  poll_id       UUID, /* 対象のPoll */
+  before_state  JSONB, /* 操作前の状態 */
+  after_state   JSONB, /* 操作後の状態 */
+  created_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+
+  CONSTRAINT admin_audit_log_admin_fkey FOREIGN KEY (admin_id)
+    REFERENCES admins (id) ON UPDATE RESTRICT ON DELETE RESTRICT,
+  CONSTRAINT admin_audit_log_contest_fkey FOREIGN KEY (contest_id)
+    REFERENCES contests (id) ON UPDATE RESTRICT ON DELETE RESTRICT,
+  CONSTRAINT admin_audit_log_poll_fkey FOREIGN KEY (poll_id)
+    REFERENCES polls (id) ON UPDATE RESTRICT ON DELETE RESTRICT
+);
+
+CREATE INDEX admin_audit_log_created_at_idx ON admin_audit_log (created_at);
+CREATE INDEX admin_audit_log_contest_id_idx ON admin_audit_log (contest_id);
@@ -0,0 +1,22 @@
+DROP TABLE admin_login_throttles;
+DROP TYPE login_throttle_kind;
+
+DELETE FROM admin_audit_log WHERE admin_id IS NULL OR action IN ('login_failed', 'login_locked');
+
+ALTER TABLE admin_audit_log ALTER COLUMN admin_id SET NOT NULL;
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
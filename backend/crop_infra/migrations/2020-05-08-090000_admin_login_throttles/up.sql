@@ -0,0 +1,39 @@
+/*
+ * ログイン失敗を監査ログに残すため、admin_actionに値を追加する。
+ * ALTER TYPE ... ADD VALUE はトランザクション内で実行できないため、型を作り直す。
+ */
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
+
+/* 存在しないemailでのログイン失敗はAdminに紐付かない */
+ALTER TABLE admin_audit_log ALTER COLUMN admin_id DROP NOT NULL;
+
+CREATE TYPE login_throttle_kind AS ENUM (
+  'email',
+  'ip'
+);
+
+CREATE TABLE admin_login_throttles (
+  kind            login_throttle_kind NOT NULL,
+  subject         TEXT NOT NULL, /* emailもしくはIPアドレス */
+  failure_count   INTEGER NOT NULL,
+  last_failed_at  TIMESTAMPTZ NOT NULL,
+  locked_until    TIMESTAMPTZ,
+
+  PRIMARY KEY (kind, subject)
+);
@@ -0,0 +1,6 @@
+DROP TABLE admin_backup_codes;
+
+ALTER TABLE admins
+  DROP COLUMN totp_secret,
+  DROP COLUMN totp_enabled,
+  DROP COLUMN totp_required;
@@ -0,0 +1,16 @@
+ALTER TABLE admins
+  ADD COLUMN totp_secret BYTEA, /* 登録中もしくは登録済みのTOTP secret */
+  ADD COLUMN totp_enabled BOOLEAN NOT NULL DEFAULT false,
+  ADD COLUMN totp_required BOOLEAN NOT NULL DEFAULT false;
+
+CREATE TABLE admin_backup_codes (
+  id          SERIAL PRIMARY KEY,
+  admin_id    UUID NOT NULL,
+  code_hash   BYTEA NOT NULL, /* バックアップコードのSHA-256 */
+  used_at     TIMESTAMPTZ,
+
+  CONSTRAINT admin_backup_codes_admin_fkey FOREIGN KEY (admin_id)
+    REFERENCES admins (id) ON UPDATE RESTRICT ON DELETE CASCADE
+);
+
+CREATE INDEX admin_backup_codes_admin_id_idx ON admin_backup_codes (admin_id);
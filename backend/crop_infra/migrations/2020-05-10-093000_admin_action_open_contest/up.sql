@@ -0,0 +1,19 @@
+/* ALTER TYPE ... ADD VALUE はトランザクション内で実行できないため、型を作り直す */
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
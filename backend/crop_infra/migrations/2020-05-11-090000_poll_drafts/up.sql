@@ -0,0 +1,34 @@
+/* 事前に用意された、まだ公開されていないPoll */
+CREATE TABLE poll_drafts (
+  id UUID PRIMARY KEY,
+  contest_id UUID NOT NULL REFERENCES contests(id) ON DELETE CASCADE,
+  idx INTEGER NOT NULL,
+  title TEXT NOT NULL,
+  duration_sec INTEGER,
+  /* [{ "name": "...", "color": "..." }] */
+  choices JSONB NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX poll_drafts_contest_id_idx ON poll_drafts (contest_id, idx);
+
+/* ALTER TYPE ... ADD VALUE はトランザクション内で実行できないため、型を作り直す */
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
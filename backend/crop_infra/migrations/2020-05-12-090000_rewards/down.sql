@@ -0,0 +1,27 @@
+DELETE FROM admin_audit_log WHERE action IN ('create_reward', 'draw_reward');
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
+
+DROP TABLE reward_deliveries;
+DROP TABLE reward_codes;
+DROP TABLE rewards;
+DROP TYPE reward_delivery_status;
+DROP TYPE reward_selection;
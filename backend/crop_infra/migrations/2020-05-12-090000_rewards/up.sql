@@ -0,0 +1,72 @@
+CREATE TYPE reward_selection AS ENUM (
+  /* スコアの上位から選ぶ */
+  'top',
+  /* スコアを重みとした抽選で選ぶ */
+  'lottery'
+);
+
+CREATE TYPE reward_delivery_status AS ENUM (
+  'pending',
+  'sent',
+  'failed'
+);
+
+/* Contestごとの賞品 */
+CREATE TABLE rewards (
+  id UUID PRIMARY KEY,
+  contest_id UUID NOT NULL REFERENCES contests(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  quantity INTEGER NOT NULL CHECK (quantity > 0),
+  selection reward_selection NOT NULL,
+  /* 当選者の選択に使った乱数のseed */
+  seed BYTEA,
+  drawn_at TIMESTAMPTZ,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX rewards_contest_id_idx ON rewards (contest_id);
+
+/* 当選者に配布するギフトコード */
+CREATE TABLE reward_codes (
+  id SERIAL PRIMARY KEY,
+  reward_id UUID NOT NULL REFERENCES rewards(id) ON DELETE CASCADE,
+  code TEXT NOT NULL,
+  UNIQUE (reward_id, code)
+);
+
+/* 当選者と、その当選者へのギフトコードの送付状況 */
+CREATE TABLE reward_deliveries (
+  id SERIAL PRIMARY KEY,
+  reward_id UUID NOT NULL REFERENCES rewards(id) ON DELETE CASCADE,
+  account_id UUID NOT NULL REFERENCES accounts(id),
+  code_id INTEGER NOT NULL UNIQUE REFERENCES reward_codes(id),
+  /* 当選順。1から始まる */
+  rank INTEGER NOT NULL,
+  status reward_delivery_status NOT NULL DEFAULT 'pending',
+  error TEXT,
+  delivered_at TIMESTAMPTZ,
+  UNIQUE (reward_id, account_id)
+);
+
+/* ALTER TYPE ... ADD VALUE はトランザクション内で実行できないため、型を作り直す */
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest',
+  'create_reward',
+  'draw_reward'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
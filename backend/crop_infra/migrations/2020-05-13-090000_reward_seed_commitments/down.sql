@@ -0,0 +1,28 @@
+DELETE FROM admin_audit_log WHERE action = 'commit_reward_seed';
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest',
+  'create_reward',
+  'draw_reward'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
+
+ALTER TABLE rewards
+  DROP CONSTRAINT rewards_seed_committed_check,
+  DROP COLUMN seed_committed_at,
+  DROP COLUMN seed_hash;
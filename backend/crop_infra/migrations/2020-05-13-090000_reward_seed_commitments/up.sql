@@ -0,0 +1,32 @@
+/*
+ * 抽選に使うseedは、Contestが終了する前にハッシュ値だけを公開しておく。
+ * 当選者を選んだ後にseedを公開し、誰でも抽選をやり直せるようにする。
+ */
+ALTER TABLE rewards
+  ADD COLUMN seed_hash BYTEA,
+  ADD COLUMN seed_committed_at TIMESTAMPTZ,
+  ADD CONSTRAINT rewards_seed_committed_check
+    CHECK ((seed IS NULL) = (seed_hash IS NULL) AND (seed_hash IS NULL) = (seed_committed_at IS NULL));
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest',
+  'create_reward',
+  'draw_reward',
+  'commit_reward_seed'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
@@ -0,0 +1,4 @@
+ALTER TABLE polls
+  DROP CONSTRAINT polls_answer_nonce_check,
+  DROP COLUMN answer_nonce,
+  DROP COLUMN answer_commitment;
@@ -0,0 +1,10 @@
+/*
+ * 正解が事前に決まっているPollでは、出題時に正解のハッシュ値を公開しておく。
+ * Resolveする時にnonceを公開し、出題後に正解を選んでいないことを示す。
+ */
+ALTER TABLE polls
+  /* SHA-256(正解のChoice名 || ':' || nonce) */
+  ADD COLUMN answer_commitment BYTEA,
+  ADD COLUMN answer_nonce TEXT,
+  ADD CONSTRAINT polls_answer_nonce_check
+    CHECK (answer_nonce IS NULL OR answer_commitment IS NOT NULL);
@@ -0,0 +1,41 @@
+DELETE FROM admin_audit_log WHERE action = 'void_poll';
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest',
+  'create_reward',
+  'draw_reward',
+  'commit_reward_seed'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
+
+/* 中止したPollは、正解の無いClosedなPollとして残す */
+UPDATE polls SET status = 'closed' WHERE status = 'voided';
+
+ALTER TYPE poll_status RENAME TO poll_status_old;
+
+CREATE TYPE poll_status AS ENUM (
+  'open',
+  'closed'
+);
+
+ALTER TABLE polls
+  ALTER COLUMN status DROP DEFAULT,
+  ALTER COLUMN status TYPE poll_status USING status::text::poll_status,
+  ALTER COLUMN status SET DEFAULT 'open';
+
+DROP TYPE poll_status_old;
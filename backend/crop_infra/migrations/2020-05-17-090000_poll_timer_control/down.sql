@@ -0,0 +1,30 @@
+DELETE FROM admin_audit_log WHERE action IN ('extend_poll', 'pause_poll', 'resume_poll');
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest',
+  'create_reward',
+  'draw_reward',
+  'commit_reward_seed',
+  'void_poll',
+  're_resolve_poll'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
+
+ALTER TABLE polls
+  DROP CONSTRAINT polls_paused_at_check,
+  DROP COLUMN paused_at;
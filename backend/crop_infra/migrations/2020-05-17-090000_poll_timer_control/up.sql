@@ -0,0 +1,36 @@
+/*
+ * Pollのタイマーを一時停止している間は、停止した日時を持つ。
+ * 再開する時に停止していた時間を duration_sec に足し、paused_at を NULL に戻す。
+ */
+ALTER TABLE polls
+  ADD COLUMN paused_at TIMESTAMPTZ,
+  ADD CONSTRAINT polls_paused_at_check
+    CHECK (paused_at IS NULL OR duration_sec IS NOT NULL);
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest',
+  'create_reward',
+  'draw_reward',
+  'commit_reward_seed',
+  'void_poll',
+  're_resolve_poll',
+  'extend_poll',
+  'pause_poll',
+  'resume_poll'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
@@ -0,0 +1,35 @@
+/*
+ * 誤ってCloseしたPollを、Closeした時点の残り時間で再開できるよう、
+ * Closeした日時を残す。再開するとNULLに戻る。
+ */
+ALTER TABLE polls
+  ADD COLUMN closed_at TIMESTAMPTZ;
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest',
+  'create_reward',
+  'draw_reward',
+  'commit_reward_seed',
+  'void_poll',
+  're_resolve_poll',
+  'extend_poll',
+  'pause_poll',
+  'resume_poll',
+  'reopen_poll'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
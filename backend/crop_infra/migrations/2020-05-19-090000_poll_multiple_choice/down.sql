@@ -0,0 +1,17 @@
+/* 複数選択のPollの回答は、1アカウントにつき1つだけ残す */
+DELETE FROM account_choices a
+  USING account_choices b
+  WHERE a.account_id = b.account_id AND a.poll_id = b.poll_id AND a.id > b.id;
+
+ALTER TABLE account_choices
+  DROP CONSTRAINT account_choices_account_id_poll_id_choice_name_key,
+  ADD CONSTRAINT account_choices_account_id_poll_id_key UNIQUE (account_id, poll_id);
+
+ALTER TABLE polls
+  DROP CONSTRAINT polls_resolved_choice_names_check,
+  DROP COLUMN resolved_choice_names,
+  DROP COLUMN scoring,
+  DROP COLUMN kind;
+
+DROP TYPE poll_scoring;
+DROP TYPE poll_kind;
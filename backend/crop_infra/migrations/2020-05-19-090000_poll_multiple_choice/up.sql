@@ -0,0 +1,29 @@
+/*
+ * 複数の選択肢を選べるPoll。
+ * 正解も選択肢の集合になり、完全一致か部分点のどちらかで採点する。
+ */
+CREATE TYPE poll_kind AS ENUM (
+  'single',
+  'multiple'
+);
+
+CREATE TYPE poll_scoring AS ENUM (
+  /* 選んだ選択肢の集合が、正解の集合と完全に一致した場合のみ1点 */
+  'exact',
+  /* (選んだ正解の数 - 選んだ不正解の数) / 正解の数 を、0点から1点の範囲で与える */
+  'partial'
+);
+
+ALTER TABLE polls
+  ADD COLUMN kind poll_kind NOT NULL DEFAULT 'single',
+  ADD COLUMN scoring poll_scoring NOT NULL DEFAULT 'exact',
+  /* 複数選択のPollの正解。単一選択のPollは resolved_choice_name を使う */
+  ADD COLUMN resolved_choice_names TEXT[],
+  ADD CONSTRAINT polls_resolved_choice_names_check
+    CHECK (resolved_choice_names IS NULL OR kind = 'multiple');
+
+/* 複数選択のPollでは、1アカウントが選んだ選択肢の数だけ行を持つ */
+ALTER TABLE account_choices
+  DROP CONSTRAINT account_choices_account_id_poll_id_key,
+  ADD CONSTRAINT account_choices_account_id_poll_id_choice_name_key
+    UNIQUE (account_id, poll_id, choice_name);
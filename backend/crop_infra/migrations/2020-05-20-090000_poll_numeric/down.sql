@@ -0,0 +1,49 @@
+DROP TABLE account_numeric_answers;
+
+/* 数値で答えるPollは、単一選択のPollとしては残せないので削除する。監査ログは残す */
+UPDATE admin_audit_log SET poll_id = NULL
+  WHERE poll_id IN (SELECT id FROM polls WHERE kind = 'numeric');
+DELETE FROM comments WHERE poll_id IN (SELECT id FROM polls WHERE kind = 'numeric');
+DELETE FROM polls WHERE kind = 'numeric';
+UPDATE polls SET scoring = 'exact' WHERE scoring IN ('tolerance', 'closest');
+
+ALTER TABLE polls
+  DROP CONSTRAINT polls_numeric_check,
+  DROP CONSTRAINT polls_resolved_choice_names_check,
+  DROP COLUMN resolved_value,
+  DROP COLUMN numeric_tolerance,
+  DROP COLUMN numeric_step,
+  DROP COLUMN numeric_max,
+  DROP COLUMN numeric_min;
+
+ALTER TYPE poll_scoring RENAME TO poll_scoring_old;
+
+CREATE TYPE poll_scoring AS ENUM (
+  'exact',
+  'partial'
+);
+
+ALTER TABLE polls
+  ALTER COLUMN scoring DROP DEFAULT,
+  ALTER COLUMN scoring TYPE poll_scoring USING scoring::text::poll_scoring,
+  ALTER COLUMN scoring SET DEFAULT 'exact';
+
+DROP TYPE poll_scoring_old;
+
+ALTER TYPE poll_kind RENAME TO poll_kind_old;
+
+CREATE TYPE poll_kind AS ENUM (
+  'single',
+  'multiple'
+);
+
+ALTER TABLE polls
+  ALTER COLUMN kind DROP DEFAULT,
+  ALTER COLUMN kind TYPE poll_kind USING kind::text::poll_kind,
+  ALTER COLUMN kind SET DEFAULT 'single';
+
+DROP TYPE poll_kind_old;
+
+ALTER TABLE polls
+  ADD CONSTRAINT polls_resolved_choice_names_check
+    CHECK (resolved_choice_names IS NULL OR kind = 'multiple');
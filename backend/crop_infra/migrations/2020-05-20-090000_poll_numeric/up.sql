@@ -0,0 +1,71 @@
+/*
+ * 数値で答えるPoll。
+ * 回答は min から max までの step 刻みの値で、実際の値との距離で採点する。
+ */
+
+/* poll_kind を参照する制約は、型を作り直す間だけ外しておく */
+ALTER TABLE polls
+  DROP CONSTRAINT polls_resolved_choice_names_check;
+
+ALTER TYPE poll_kind RENAME TO poll_kind_old;
+
+CREATE TYPE poll_kind AS ENUM (
+  'single',
+  'multiple',
+  'numeric'
+);
+
+ALTER TABLE polls
+  ALTER COLUMN kind DROP DEFAULT,
+  ALTER COLUMN kind TYPE poll_kind USING kind::text::poll_kind,
+  ALTER COLUMN kind SET DEFAULT 'single';
+
+DROP TYPE poll_kind_old;
+
+ALTER TYPE poll_scoring RENAME TO poll_scoring_old;
+
+CREATE TYPE poll_scoring AS ENUM (
+  'exact',
+  'partial',
+  /* 実際の値との差が numeric_tolerance 以内なら1点 */
+  'tolerance',
+  /* 実際の値に近い順に順位を付け、最も近い回答に1点、遠いほど少ない点を与える */
+  'closest'
+);
+
+ALTER TABLE polls
+  ALTER COLUMN scoring DROP DEFAULT,
+  ALTER COLUMN scoring TYPE poll_scoring USING scoring::text::poll_scoring,
+  ALTER COLUMN scoring SET DEFAULT 'exact';
+
+DROP TYPE poll_scoring_old;
+
+ALTER TABLE polls
+  ADD COLUMN numeric_min DOUBLE PRECISION,
+  ADD COLUMN numeric_max DOUBLE PRECISION,
+  ADD COLUMN numeric_step DOUBLE PRECISION,
+  ADD COLUMN numeric_tolerance DOUBLE PRECISION,
+  /* 数値で答えるPollの正解 */
+  ADD COLUMN resolved_value DOUBLE PRECISION,
+  ADD CONSTRAINT polls_resolved_choice_names_check
+    CHECK (resolved_choice_names IS NULL OR kind = 'multiple'),
+  ADD CONSTRAINT polls_numeric_check
+    CHECK (
+      (kind = 'numeric') = (numeric_min IS NOT NULL AND numeric_max IS NOT NULL AND numeric_step IS NOT NULL)
+      AND (numeric_tolerance IS NULL OR kind = 'numeric')
+      AND (resolved_value IS NULL OR kind = 'numeric')
+    );
+
+CREATE TABLE account_numeric_answers (
+  /* unused */
+  id          SERIAL PRIMARY KEY,
+  account_id  UUID NOT NULL,
+  poll_id     UUID NOT NULL,
+  value       DOUBLE PRECISION NOT NULL,
+
+  UNIQUE (account_id, poll_id),
+  CONSTRAINT account_numeric_answers_account_fkey FOREIGN KEY (account_id)
+    REFERENCES accounts (id) ON UPDATE RESTRICT ON DELETE RESTRICT,
+  CONSTRAINT account_numeric_answers_poll_fkey FOREIGN KEY (poll_id)
+    REFERENCES polls (id) ON UPDATE RESTRICT ON DELETE RESTRICT
+);
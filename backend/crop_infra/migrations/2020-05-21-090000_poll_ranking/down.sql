@@ -0,0 +1,58 @@
+ALTER TABLE account_choices
+  DROP COLUMN rank;
+
+/* 順位を予想するPollは、他の種類のPollとしては残せないので削除する。監査ログは残す */
+UPDATE admin_audit_log SET poll_id = NULL
+  WHERE poll_id IN (SELECT id FROM polls WHERE kind = 'ranking');
+DELETE FROM comments WHERE poll_id IN (SELECT id FROM polls WHERE kind = 'ranking');
+DELETE FROM account_choices WHERE poll_id IN (SELECT id FROM polls WHERE kind = 'ranking');
+DELETE FROM choices WHERE poll_id IN (SELECT id FROM polls WHERE kind = 'ranking');
+DELETE FROM polls WHERE kind = 'ranking';
+UPDATE polls SET scoring = 'exact' WHERE scoring IN ('position', 'kendall');
+
+ALTER TABLE polls
+  DROP CONSTRAINT polls_ranking_check,
+  DROP CONSTRAINT polls_numeric_check,
+  DROP CONSTRAINT polls_resolved_choice_names_check,
+  DROP COLUMN ranking_size;
+
+ALTER TYPE poll_scoring RENAME TO poll_scoring_old;
+
+CREATE TYPE poll_scoring AS ENUM (
+  'exact',
+  'partial',
+  'tolerance',
+  'closest'
+);
+
+ALTER TABLE polls
+  ALTER COLUMN scoring DROP DEFAULT,
+  ALTER COLUMN scoring TYPE poll_scoring USING scoring::text::poll_scoring,
+  ALTER COLUMN scoring SET DEFAULT 'exact';
+
+DROP TYPE poll_scoring_old;
+
+ALTER TYPE poll_kind RENAME TO poll_kind_old;
+
+CREATE TYPE poll_kind AS ENUM (
+  'single',
+  'multiple',
+  'numeric'
+);
+
+ALTER TABLE polls
+  ALTER COLUMN kind DROP DEFAULT,
+  ALTER COLUMN kind TYPE poll_kind USING kind::text::poll_kind,
+  ALTER COLUMN kind SET DEFAULT 'single';
+
+DROP TYPE poll_kind_old;
+
+ALTER TABLE polls
+  ADD CONSTRAINT polls_resolved_choice_names_check
+    CHECK (resolved_choice_names IS NULL OR kind = 'multiple'),
+  ADD CONSTRAINT polls_numeric_check
+    CHECK (
+      (kind = 'numeric') = (numeric_min IS NOT NULL AND numeric_max IS NOT NULL AND numeric_step IS NOT NULL)
+      AND (numeric_tolerance IS NULL OR kind = 'numeric')
+      AND (resolved_value IS NULL OR kind = 'numeric')
+    );
@@ -0,0 +1,63 @@
+/*
+ * 順位を予想するPoll。
+ * 回答は選択肢のうち上位 ranking_size 個を順に並べたもので、実際の順位との一致度で採点する。
+ */
+
+/* poll_kind を参照する制約は、型を作り直す間だけ外しておく */
+ALTER TABLE polls
+  DROP CONSTRAINT polls_resolved_choice_names_check,
+  DROP CONSTRAINT polls_numeric_check;
+
+ALTER TYPE poll_kind RENAME TO poll_kind_old;
+
+CREATE TYPE poll_kind AS ENUM (
+  'single',
+  'multiple',
+  'numeric',
+  'ranking'
+);
+
+ALTER TABLE polls
+  ALTER COLUMN kind DROP DEFAULT,
+  ALTER COLUMN kind TYPE poll_kind USING kind::text::poll_kind,
+  ALTER COLUMN kind SET DEFAULT 'single';
+
+DROP TYPE poll_kind_old;
+
+ALTER TYPE poll_scoring RENAME TO poll_scoring_old;
+
+CREATE TYPE poll_scoring AS ENUM (
+  'exact',
+  'partial',
+  'tolerance',
+  'closest',
+  /* 順位が一致した選択肢の割合 */
+  'position',
+  /* 実際の順位で上下関係にある選択肢の組のうち、回答でも同じ上下関係になっている割合 */
+  'kendall'
+);
+
+ALTER TABLE polls
+  ALTER COLUMN scoring DROP DEFAULT,
+  ALTER COLUMN scoring TYPE poll_scoring USING scoring::text::poll_scoring,
+  ALTER COLUMN scoring SET DEFAULT 'exact';
+
+DROP TYPE poll_scoring_old;
+
+ALTER TABLE polls
+  /* 何位までを予想するか */
+  ADD COLUMN ranking_size INTEGER,
+  ADD CONSTRAINT polls_resolved_choice_names_check
+    CHECK (resolved_choice_names IS NULL OR kind IN ('multiple', 'ranking')),
+  ADD CONSTRAINT polls_numeric_check
+    CHECK (
+      (kind = 'numeric') = (numeric_min IS NOT NULL AND numeric_max IS NOT NULL AND numeric_step IS NOT NULL)
+      AND (numeric_tolerance IS NULL OR kind = 'numeric')
+      AND (resolved_value IS NULL OR kind = 'numeric')
+    ),
+  ADD CONSTRAINT polls_ranking_check
+    CHECK ((kind = 'ranking') = (ranking_size IS NOT NULL AND ranking_size > 0));
+
+/* 順位を予想するPollでの、選択肢の順位。1位が0 */
+ALTER TABLE account_choices
+  ADD COLUMN rank INTEGER;
@@ -0,0 +1,3 @@
+ALTER TABLE polls
+  DROP CONSTRAINT polls_is_scored_check,
+  DROP COLUMN is_scored;
@@ -0,0 +1,8 @@
+/* 正解の無いアンケートのPoll。Resolveせず、スコアにも数えない */
+ALTER TABLE polls
+  ADD COLUMN is_scored BOOLEAN NOT NULL DEFAULT TRUE,
+  ADD CONSTRAINT polls_is_scored_check
+    CHECK (
+      is_scored
+      OR (resolved_choice_name IS NULL AND resolved_choice_names IS NULL AND resolved_value IS NULL AND answer_commitment IS NULL)
+    );
@@ -0,0 +1,59 @@
+/*
+ * 単一選択のPollに、LMSRのマーケットメーカーを付ける。
+ * 参加者は仮想ポイントで選択肢の株を買い、価格がその選択肢の確率の推定値になる。
+ * 各選択肢の株数は、取引の記録から集計する。
+ */
+CREATE TABLE poll_markets (
+  poll_id UUID PRIMARY KEY REFERENCES polls(id),
+  /* LMSRの流動性パラメータ b。大きいほど価格が動きにくい */
+  liquidity DOUBLE PRECISION NOT NULL CHECK (liquidity > 0),
+  /* 1アカウントが使えるポイントの上限 */
+  budget DOUBLE PRECISION NOT NULL CHECK (budget > 0),
+  /* 同時に行われた取引が、同じ価格で成立しないようにするためのバージョン */
+  num_trades INTEGER NOT NULL DEFAULT 0,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE market_trades (
+  id SERIAL PRIMARY KEY,
+  poll_id UUID NOT NULL REFERENCES poll_markets(poll_id),
+  account_id UUID NOT NULL REFERENCES accounts(id),
+  choice_name TEXT NOT NULL,
+  /* 支払ったポイント */
+  cost DOUBLE PRECISION NOT NULL CHECK (cost > 0),
+  /* 得た株数 */
+  shares DOUBLE PRECISION NOT NULL CHECK (shares > 0),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX market_trades_poll_id_idx ON market_trades (poll_id);
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest',
+  'create_reward',
+  'draw_reward',
+  'commit_reward_seed',
+  'void_poll',
+  're_resolve_poll',
+  'extend_poll',
+  'pause_poll',
+  'resume_poll',
+  'reopen_poll',
+  'open_market'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
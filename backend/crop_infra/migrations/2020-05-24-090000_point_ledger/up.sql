@@ -0,0 +1,76 @@
+/*
+ * アカウントのポイントを複式で記帳する。
+ * 1つの取引は複数の仕訳からなり、仕訳の金額の合計は0になる。
+ * アカウントの残高は、そのアカウントの仕訳の合計になる。
+ */
+CREATE TYPE point_book AS ENUM (
+  /* アカウントの残高 */
+  'wallet',
+  /* ポイントの発行元。Adminが付与したポイントは、ここから払い出す */
+  'issuance'
+);
+
+CREATE TYPE point_transaction_kind AS ENUM (
+  'grant',
+  'adjustment'
+);
+
+CREATE TABLE point_transactions (
+  id UUID PRIMARY KEY,
+  /* 同じキーの取引は一度しか記帳しない。再試行による二重の記帳を防ぐ */
+  idempotency_key TEXT NOT NULL UNIQUE,
+  kind point_transaction_kind NOT NULL,
+  /* 取引のきっかけになったContestやPoll */
+  contest_id UUID REFERENCES contests(id),
+  poll_id UUID REFERENCES polls(id),
+  /* Adminによる付与や調整の場合 */
+  admin_id UUID REFERENCES admins(id),
+  memo TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE point_entries (
+  id SERIAL PRIMARY KEY,
+  transaction_id UUID NOT NULL REFERENCES point_transactions(id),
+  book point_book NOT NULL,
+  /* 'wallet' の仕訳の場合の、残高の持ち主 */
+  account_id UUID REFERENCES accounts(id),
+  /* 正なら残高が増え、負なら減る */
+  amount BIGINT NOT NULL CHECK (amount <> 0),
+  CONSTRAINT point_entries_wallet_check CHECK ((book = 'wallet') = (account_id IS NOT NULL))
+);
+
+CREATE INDEX point_entries_transaction_id_idx ON point_entries (transaction_id);
+CREATE INDEX point_entries_account_id_idx ON point_entries (account_id);
+
+ALTER TYPE admin_action RENAME TO admin_action_old;
+
+CREATE TYPE admin_action AS ENUM (
+  'create_contest',
+  'add_poll',
+  'close_poll',
+  'resolve_poll',
+  'close_contest',
+  'archive_contest',
+  'login_failed',
+  'login_locked',
+  'open_contest',
+  'import_contest',
+  'create_reward',
+  'draw_reward',
+  'commit_reward_seed',
+  'void_poll',
+  're_resolve_poll',
+  'extend_poll',
+  'pause_poll',
+  'resume_poll',
+  'reopen_poll',
+  'open_market',
+  'grant_points',
+  'adjust_points'
+);
+
+ALTER TABLE admin_audit_log
+  ALTER COLUMN action TYPE admin_action USING action::text::admin_action;
+
+DROP TYPE admin_action_old;
@@ -0,0 +1,37 @@
+DELETE FROM point_entries WHERE transaction_id IN (
+  SELECT id FROM point_transactions WHERE kind IN ('stake', 'payout', 'refund')
+);
+DELETE FROM point_transactions WHERE kind IN ('stake', 'payout', 'refund');
+
+ALTER TYPE point_transaction_kind RENAME TO point_transaction_kind_old;
+
+CREATE TYPE point_transaction_kind AS ENUM (
+  'grant',
+  'adjustment'
+);
+
+ALTER TABLE point_transactions
+  ALTER COLUMN kind TYPE point_transaction_kind USING kind::text::point_transaction_kind;
+
+DROP TYPE point_transaction_kind_old;
+
+ALTER TABLE point_entries DROP CONSTRAINT point_entries_account_check;
+
+ALTER TYPE point_book RENAME TO point_book_old;
+
+CREATE TYPE point_book AS ENUM (
+  'wallet',
+  'issuance'
+);
+
+ALTER TABLE point_entries
+  ALTER COLUMN book TYPE point_book USING book::text::point_book;
+
+DROP TYPE point_book_old;
+
+ALTER TABLE point_entries
+  ADD CONSTRAINT point_entries_wallet_check CHECK ((book = 'wallet') = (account_id IS NOT NULL));
+
+ALTER TABLE account_choices DROP COLUMN stake;
+
+ALTER TABLE polls DROP COLUMN stake_multiplier;
@@ -0,0 +1,52 @@
+/*
+ * 回答にポイントを賭けられるPoll。
+ * 正解すると、賭けたポイントに倍率を掛けて払い戻す。NULLの場合は賭けられない。
+ */
+ALTER TABLE polls
+  ADD COLUMN stake_multiplier DOUBLE PRECISION,
+  ADD CONSTRAINT polls_stake_multiplier_check
+    CHECK (stake_multiplier IS NULL OR (is_scored AND stake_multiplier >= 1));
+
+/* 回答に賭けたポイント。複数選択や順位の回答では、全ての行に同じ値を入れる */
+ALTER TABLE account_choices
+  ADD COLUMN stake BIGINT CHECK (stake > 0);
+
+/*
+ * 賭けたポイントは、精算するまで 'escrow' の帳簿に預かる。
+ * 預かっているポイントはアカウントごとに記帳し、どのPollの分かは取引から分かる。
+ */
+ALTER TABLE point_entries DROP CONSTRAINT point_entries_wallet_check;
+
+ALTER TYPE point_book RENAME TO point_book_old;
+
+CREATE TYPE point_book AS ENUM (
+  'wallet',
+  'issuance',
+  'escrow'
+);
+
+ALTER TABLE point_entries
+  ALTER COLUMN book TYPE point_book USING book::text::point_book;
+
+DROP TYPE point_book_old;
+
+ALTER TABLE point_entries
+  ADD CONSTRAINT point_entries_account_check CHECK ((book = 'issuance') = (account_id IS NULL));
+
+ALTER TYPE point_transaction_kind RENAME TO point_transaction_kind_old;
+
+CREATE TYPE point_transaction_kind AS ENUM (
+  'grant',
+  'adjustment',
+  /* 賭けたポイントを預ける、または変更する */
+  'stake',
+  /* Resolveしたときの精算 */
+  'payout',
+  /* 中止したときの払い戻し */
+  'refund'
+);
+
+ALTER TABLE point_transactions
+  ALTER COLUMN kind TYPE point_transaction_kind USING kind::text::point_transaction_kind;
+
+DROP TYPE point_transaction_kind_old;
@@ -0,0 +1 @@
+ALTER TABLE polls DROP COLUMN lock_before_close_sec;
@@ -0,0 +1,6 @@
+/*
+ * 締め切りの何秒前から回答を変更できなくするか。
+ * 制限時間の無いPollでは使わない。
+ */
+ALTER TABLE polls
+  ADD COLUMN lock_before_close_sec INTEGER CHECK (lock_before_close_sec > 0);
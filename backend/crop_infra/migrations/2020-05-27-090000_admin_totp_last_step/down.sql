@@ -0,0 +1 @@
+ALTER TABLE admins DROP COLUMN totp_last_step;
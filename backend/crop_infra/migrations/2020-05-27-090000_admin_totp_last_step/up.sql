@@ -0,0 +1,2 @@
+ALTER TABLE admins
+  ADD COLUMN totp_last_step BIGINT; /* 最後に受け付けたTOTPのステップ。同じコードの再利用を防ぐ */
@@ -0,0 +1,10 @@
+/*
+ * 当選者を選んだ時点の、各アカウントのスコア。
+ * 後から正解が訂正されてスコアが変わっても、この値で抽選を検証できるようにする。
+ */
+CREATE TABLE reward_draw_scores (
+  reward_id UUID NOT NULL REFERENCES rewards(id) ON DELETE CASCADE,
+  account_id UUID NOT NULL REFERENCES accounts(id),
+  score DOUBLE PRECISION NOT NULL,
+  PRIMARY KEY (reward_id, account_id)
+);
@@ -0,0 +1,21 @@
+/*
+ * マーケットの取引で支払ったポイントを、残高から引き落とす。
+ * 支払ったポイントは発行元に移し、Resolveしたときの払い戻しや、中止したときの返金は発行元から払い出す。
+ * 払い戻しと返金は、賭けたポイントの精算と同じ 'payout' と 'refund' で記帳する。
+ */
+ALTER TYPE point_transaction_kind RENAME TO point_transaction_kind_old;
+
+CREATE TYPE point_transaction_kind AS ENUM (
+  'grant',
+  'adjustment',
+  'stake',
+  'payout',
+  'refund',
+  /* マーケットで株を買う */
+  'trade'
+);
+
+ALTER TABLE point_transactions
+  ALTER COLUMN kind TYPE point_transaction_kind USING kind::text::point_transaction_kind;
+
+DROP TYPE point_transaction_kind_old;
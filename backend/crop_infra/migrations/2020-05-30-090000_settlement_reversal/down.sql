@@ -0,0 +1,20 @@
+DELETE FROM point_entries WHERE transaction_id IN (
+  SELECT id FROM point_transactions WHERE kind = 'reversal'
+);
+DELETE FROM point_transactions WHERE kind = 'reversal';
+
+ALTER TYPE point_transaction_kind RENAME TO point_transaction_kind_old;
+
+CREATE TYPE point_transaction_kind AS ENUM (
+  'grant',
+  'adjustment',
+  'stake',
+  'payout',
+  'refund',
+  'trade'
+);
+
+ALTER TABLE point_transactions
+  ALTER COLUMN kind TYPE point_transaction_kind USING kind::text::point_transaction_kind;
+
+DROP TYPE point_transaction_kind_old;
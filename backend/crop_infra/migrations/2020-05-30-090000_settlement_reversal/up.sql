@@ -0,0 +1,21 @@
+/*
+ * 正解を訂正したときに、訂正前の正解で記帳した払い戻しを取り消す。
+ * 取り消しの取引は、元の取引の仕訳の符号を反転したもの。
+ */
+ALTER TYPE point_transaction_kind RENAME TO point_transaction_kind_old;
+
+CREATE TYPE point_transaction_kind AS ENUM (
+  'grant',
+  'adjustment',
+  'stake',
+  'payout',
+  'refund',
+  'trade',
+  /* 精算の取り消し */
+  'reversal'
+);
+
+ALTER TABLE point_transactions
+  ALTER COLUMN kind TYPE point_transaction_kind USING kind::text::point_transaction_kind;
+
+DROP TYPE point_transaction_kind_old;
@@ -0,0 +1,13 @@
+DELETE FROM admin_login_throttles WHERE kind IN ('account_email', 'account_ip');
+
+ALTER TYPE login_throttle_kind RENAME TO login_throttle_kind_old;
+
+CREATE TYPE login_throttle_kind AS ENUM (
+  'email',
+  'ip'
+);
+
+ALTER TABLE admin_login_throttles
+  ALTER COLUMN kind TYPE login_throttle_kind USING kind::text::login_throttle_kind;
+
+DROP TYPE login_throttle_kind_old;
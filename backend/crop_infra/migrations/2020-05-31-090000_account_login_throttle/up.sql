@@ -0,0 +1,17 @@
+/*
+ * アカウントのログインも、Adminのログインと同じテーブルで試行回数を制限する。
+ * ALTER TYPE ... ADD VALUE はトランザクション内で実行できないため、型を作り直す。
+ */
+ALTER TYPE login_throttle_kind RENAME TO login_throttle_kind_old;
+
+CREATE TYPE login_throttle_kind AS ENUM (
+  'email',
+  'ip',
+  'account_email',
+  'account_ip'
+);
+
+ALTER TABLE admin_login_throttles
+  ALTER COLUMN kind TYPE login_throttle_kind USING kind::text::login_throttle_kind;
+
+DROP TYPE login_throttle_kind_old;
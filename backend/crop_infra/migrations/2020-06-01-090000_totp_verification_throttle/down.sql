@@ -0,0 +1,15 @@
+DELETE FROM admin_login_throttles WHERE kind = 'totp';
+
+ALTER TYPE login_throttle_kind RENAME TO login_throttle_kind_old;
+
+CREATE TYPE login_throttle_kind AS ENUM (
+  'email',
+  'ip',
+  'account_email',
+  'account_ip'
+);
+
+ALTER TABLE admin_login_throttles
+  ALTER COLUMN kind TYPE login_throttle_kind USING kind::text::login_throttle_kind;
+
+DROP TYPE login_throttle_kind_old;
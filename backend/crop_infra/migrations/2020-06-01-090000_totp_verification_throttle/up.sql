@@ -0,0 +1,18 @@
+/*
+ * TOTP登録時のコード検証も、ログインと同じテーブルで試行回数を制限する。
+ * subjectはAdminのID。
+ */
+ALTER TYPE login_throttle_kind RENAME TO login_throttle_kind_old;
+
+CREATE TYPE login_throttle_kind AS ENUM (
+  'email',
+  'ip',
+  'account_email',
+  'account_ip',
+  'totp'
+);
+
+ALTER TABLE admin_login_throttles
+  ALTER COLUMN kind TYPE login_throttle_kind USING kind::text::login_throttle_kind;
+
+DROP TYPE login_throttle_kind_old;
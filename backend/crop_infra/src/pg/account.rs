@@ -19,6 +19,13 @@ pub trait AccountTable {
             .first::<QueriedAccount>(self.conn())
             .optional()?)
     }
+
+    fn query_by_ids(&self, ids: &[Uuid]) -> anyhow::Result<Vec<QueriedAccount>> {
+        Ok(accounts::table
+            .filter(accounts::id.eq_any(ids))
+            .select((accounts::id, accounts::name))
+            .load::<QueriedAccount>(self.conn())?)
+    }
 }
 
 impl AccountTable for Connection {
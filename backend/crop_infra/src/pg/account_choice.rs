@@ -1,18 +1,45 @@
 use super::{schema::account_choices, Connection};
 use diesel::prelude::*;
+use diesel::Connection as _;
 use uuid::Uuid;
 
 pub trait AccountChoiceTable {
     fn conn(&self) -> &Connection;
 
-    fn upsert<'a>(&self, account_choice: NewAccountChoice<'a>) -> anyhow::Result<()> {
-        diesel::insert_into(account_choices::table)
-            .values(account_choice)
-            .on_conflict((account_choices::account_id, account_choices::poll_id))
-            .do_update()
-            .set(account_choices::choice_name.eq(account_choice.choice_name))
+    /// アカウントの回答を、指定した選択肢で置き換える。
+    /// `ranked` の場合は、選択肢の並び順を順位として保存する。
+    /// 賭けたポイントは、全ての行に同じ値を保存する。
+    fn replace(
+        &self,
+        poll_id: &Uuid,
+        account_id: &Uuid,
+        choice_names: &[&str],
+        ranked: bool,
+        stake: Option<i64>,
+    ) -> anyhow::Result<()> {
+        let records = choice_names
+            .iter()
+            .enumerate()
+            .map(|(rank, choice_name)| NewAccountChoice {
+                poll_id,
+                account_id,
+                choice_name,
+                rank: if ranked { Some(rank as i32) } else { None },
+                stake,
+            })
+            .collect::<Vec<_>>();
+        self.conn().transaction::<_, anyhow::Error, _>(|| {
+            diesel::delete(
+                account_choices::table
+                    .filter(account_choices::poll_id.eq(poll_id))
+                    .filter(account_choices::account_id.eq(account_id)),
+            )
             .execute(self.conn())?;
-        Ok(())
+            diesel::insert_into(account_choices::table)
+                .values(records)
+                .execute(self.conn())?;
+            Ok(())
+        })
     }
 
     fn query_by_poll_id(&self, poll_id: &Uuid) -> anyhow::Result<Vec<QueriedAccountChoice>> {
@@ -22,6 +49,8 @@ pub trait AccountChoiceTable {
                 account_choices::poll_id,
                 account_choices::account_id,
                 account_choices::choice_name,
+                account_choices::rank,
+                account_choices::stake,
             ))
             .load::<QueriedAccountChoice>(self.conn())?)
     }
@@ -39,6 +68,8 @@ pub struct NewAccountChoice<'a> {
     pub poll_id: &'a Uuid,
     pub account_id: &'a Uuid,
     pub choice_name: &'a str,
+    pub rank: Option<i32>,
+    pub stake: Option<i64>,
 }
 
 #[derive(Queryable, Clone)]
@@ -46,4 +77,6 @@ pub struct QueriedAccountChoice {
     pub poll_id: Uuid,
     pub account_id: Uuid,
     pub choice_name: String,
+    pub rank: Option<i32>,
+    pub stake: Option<i64>,
 }
@@ -0,0 +1,72 @@
+use super::{schema::account_credentials, Connection};
+use diesel::{prelude::*, sql_types::Text};
+use uuid::Uuid;
+
+sql_function!(fn lower(x: Text) -> Text);
+
+pub trait AccountCredentialTable {
+    fn conn(&self) -> &Connection;
+
+    fn save<'a>(&self, new_credential: NewAccountCredential<'a>) -> anyhow::Result<()> {
+        diesel::insert_into(account_credentials::table)
+            .values(new_credential)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// 大文字と小文字を区別せずに検索する。
+    /// `email` は小文字にしてから渡す。
+    fn query_by_email(&self, email: &str) -> anyhow::Result<Option<QueriedAccountCredential>> {
+        Ok(account_credentials::table
+            .filter(lower(account_credentials::email).eq(email))
+            .select((
+                account_credentials::account_id,
+                account_credentials::email,
+                account_credentials::cred,
+                account_credentials::salt,
+            ))
+            .first::<QueriedAccountCredential>(self.conn())
+            .optional()?)
+    }
+
+    fn exists_by_account_id(&self, account_id: &Uuid) -> anyhow::Result<bool> {
+        Ok(diesel::select(diesel::dsl::exists(
+            account_credentials::table.filter(account_credentials::account_id.eq(account_id)),
+        ))
+        .get_result(self.conn())?)
+    }
+
+    /// (account_id, email) の一覧を返す
+    fn query_emails_by_account_ids(
+        &self,
+        account_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<(Uuid, String)>> {
+        Ok(account_credentials::table
+            .filter(account_credentials::account_id.eq_any(account_ids))
+            .select((account_credentials::account_id, account_credentials::email))
+            .load::<(Uuid, String)>(self.conn())?)
+    }
+}
+
+impl AccountCredentialTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "account_credentials"]
+pub struct NewAccountCredential<'a> {
+    pub account_id: &'a Uuid,
+    pub email: &'a str,
+    pub cred: &'a [u8],
+    pub salt: &'a [u8],
+}
+
+#[derive(Queryable)]
+pub struct QueriedAccountCredential {
+    pub account_id: Uuid,
+    pub email: String,
+    pub cred: Vec<u8>,
+    pub salt: Vec<u8>,
+}
@@ -0,0 +1,53 @@
+use super::{schema::account_numeric_answers, Connection};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub trait AccountNumericAnswerTable {
+    fn conn(&self) -> &Connection;
+
+    /// アカウントの回答を、指定した値で置き換える
+    fn upsert<'a>(&self, answer: NewAccountNumericAnswer<'a>) -> anyhow::Result<()> {
+        diesel::insert_into(account_numeric_answers::table)
+            .values(answer)
+            .on_conflict((
+                account_numeric_answers::account_id,
+                account_numeric_answers::poll_id,
+            ))
+            .do_update()
+            .set(account_numeric_answers::value.eq(answer.value))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn query_by_poll_id(&self, poll_id: &Uuid) -> anyhow::Result<Vec<QueriedAccountNumericAnswer>> {
+        Ok(account_numeric_answers::table
+            .filter(account_numeric_answers::poll_id.eq(poll_id))
+            .select((
+                account_numeric_answers::poll_id,
+                account_numeric_answers::account_id,
+                account_numeric_answers::value,
+            ))
+            .load::<QueriedAccountNumericAnswer>(self.conn())?)
+    }
+}
+
+impl AccountNumericAnswerTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable, Clone, Copy)]
+#[table_name = "account_numeric_answers"]
+pub struct NewAccountNumericAnswer<'a> {
+    pub poll_id: &'a Uuid,
+    pub account_id: &'a Uuid,
+    pub value: f64,
+}
+
+#[derive(Queryable, Clone)]
+pub struct QueriedAccountNumericAnswer {
+    pub poll_id: Uuid,
+    pub account_id: Uuid,
+    pub value: f64,
+}
@@ -1,4 +1,4 @@
-use super::{schema::admins, Connection};
+use super::{schema::admins, types::AdminRole, Connection};
 use diesel::prelude::*;
 use uuid::Uuid;
 
@@ -18,18 +18,144 @@ pub trait AdminTable {
     ) -> anyhow::Result<Option<QueriedAdminCredentials>> {
         Ok(admins::table
             .filter(admins::email.eq(email))
-            .select((admins::id, admins::cred, admins::salt))
+            .select((
+                admins::id,
+                admins::cred,
+                admins::salt,
+                admins::role,
+                admins::disabled,
+                admins::totp_secret,
+                admins::totp_enabled,
+                admins::totp_required,
+            ))
             .first::<QueriedAdminCredentials>(self.conn())
             .optional()?)
     }
+
+    fn query_by_id(&self, id: &Uuid) -> anyhow::Result<Option<QueriedAdmin>> {
+        Ok(admins::table
+            .filter(admins::id.eq(id))
+            .select(QUERIED_ADMIN_COLUMNS)
+            .first::<QueriedAdmin>(self.conn())
+            .optional()?)
+    }
+
+    fn query_by_email(&self, email: &str) -> anyhow::Result<Option<QueriedAdmin>> {
+        Ok(admins::table
+            .filter(admins::email.eq(email))
+            .select(QUERIED_ADMIN_COLUMNS)
+            .first::<QueriedAdmin>(self.conn())
+            .optional()?)
+    }
+
+    fn query_all(&self) -> anyhow::Result<Vec<QueriedAdmin>> {
+        Ok(admins::table
+            .select(QUERIED_ADMIN_COLUMNS)
+            .order(admins::email.asc())
+            .load::<QueriedAdmin>(self.conn())?)
+    }
+
+    fn update_credentials(&self, id: &Uuid, cred: &[u8], salt: &[u8]) -> anyhow::Result<()> {
+        diesel::update(admins::table.filter(admins::id.eq(id)))
+            .set((admins::cred.eq(cred), admins::salt.eq(salt)))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn update_disabled(&self, id: &Uuid, disabled: bool) -> anyhow::Result<()> {
+        diesel::update(admins::table.filter(admins::id.eq(id)))
+            .set(admins::disabled.eq(disabled))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn query_totp_by_id(&self, id: &Uuid) -> anyhow::Result<Option<QueriedAdminTotp>> {
+        Ok(admins::table
+            .filter(admins::id.eq(id))
+            .select(QUERIED_ADMIN_TOTP_COLUMNS)
+            .first::<QueriedAdminTotp>(self.conn())
+            .optional()?)
+    }
+
+    fn query_totp_by_email(&self, email: &str) -> anyhow::Result<Option<QueriedAdminTotp>> {
+        Ok(admins::table
+            .filter(admins::email.eq(email))
+            .select(QUERIED_ADMIN_TOTP_COLUMNS)
+            .first::<QueriedAdminTotp>(self.conn())
+            .optional()?)
+    }
+
+    fn update_totp(&self, id: &Uuid, secret: Option<&[u8]>, enabled: bool) -> anyhow::Result<()> {
+        diesel::update(admins::table.filter(admins::id.eq(id)))
+            .set((
+                admins::totp_secret.eq(secret),
+                admins::totp_enabled.eq(enabled),
+            ))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// TOTPのステップを受け付ける。
+    /// 既に同じかそれより後のステップを受け付けていれば、再利用とみなしてfalseを返す。
+    fn accept_totp_step(&self, id: &Uuid, step: i64) -> anyhow::Result<bool> {
+        let n = diesel::update(
+            admins::table.filter(admins::id.eq(id)).filter(
+                admins::totp_last_step
+                    .is_null()
+                    .or(admins::totp_last_step.lt(step)),
+            ),
+        )
+        .set(admins::totp_last_step.eq(step))
+        .execute(self.conn())?;
+        Ok(n > 0)
+    }
+
+    fn update_totp_required(&self, id: &Uuid, required: bool) -> anyhow::Result<()> {
+        diesel::update(admins::table.filter(admins::id.eq(id)))
+            .set(admins::totp_required.eq(required))
+            .execute(self.conn())?;
+        Ok(())
+    }
 }
 
+const QUERIED_ADMIN_COLUMNS: (
+    admins::id,
+    admins::email,
+    admins::role,
+    admins::disabled,
+    admins::totp_enabled,
+    admins::totp_required,
+) = (
+    admins::id,
+    admins::email,
+    admins::role,
+    admins::disabled,
+    admins::totp_enabled,
+    admins::totp_required,
+);
+
 impl AdminTable for Connection {
     fn conn(&self) -> &Connection {
         self
     }
 }
 
+const QUERIED_ADMIN_TOTP_COLUMNS: (
+    admins::id,
+    admins::email,
+    admins::role,
+    admins::disabled,
+    admins::totp_secret,
+    admins::totp_enabled,
+) = (
+    admins::id,
+    admins::email,
+    admins::role,
+    admins::disabled,
+    admins::totp_secret,
+    admins::totp_enabled,
+);
+
 #[derive(Insertable)]
 #[table_name = "admins"]
 pub struct NewAdmin<'a> {
@@ -37,6 +163,7 @@ pub struct NewAdmin<'a> {
     pub email: &'a str,
     pub cred: &'a [u8],
     pub salt: &'a [u8],
+    pub role: AdminRole,
 }
 
 #[derive(Queryable)]
@@ -44,4 +171,29 @@ pub struct QueriedAdminCredentials {
     pub id: Uuid,
     pub cred: Vec<u8>,
     pub salt: Vec<u8>,
+    pub role: AdminRole,
+    pub disabled: bool,
+    pub totp_secret: Option<Vec<u8>>,
+    pub totp_enabled: bool,
+    pub totp_required: bool,
+}
+
+#[derive(Queryable)]
+pub struct QueriedAdmin {
+    pub id: Uuid,
+    pub email: String,
+    pub role: AdminRole,
+    pub disabled: bool,
+    pub totp_enabled: bool,
+    pub totp_required: bool,
+}
+
+#[derive(Queryable)]
+pub struct QueriedAdminTotp {
+    pub id: Uuid,
+    pub email: String,
+    pub role: AdminRole,
+    pub disabled: bool,
+    pub totp_secret: Option<Vec<u8>>,
+    pub totp_enabled: bool,
 }
@@ -0,0 +1,99 @@
+use super::{schema::admin_audit_log, types::AdminAction, Connection};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub trait AdminAuditLogTable {
+    fn conn(&self) -> &Connection;
+
+    fn save<'a>(&self, log: NewAdminAuditLog<'a>) -> anyhow::Result<()> {
+        diesel::insert_into(admin_audit_log::table)
+            .values(log)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// 条件に一致するログを新しい順に取得する
+    fn query<'a>(
+        &self,
+        filter: AdminAuditLogFilter<'a>,
+    ) -> anyhow::Result<Vec<QueriedAdminAuditLog>> {
+        let mut query = admin_audit_log::table
+            .select((
+                admin_audit_log::id,
+                admin_audit_log::admin_id,
+                admin_audit_log::action,
+                admin_audit_log::contest_id,
+                admin_audit_log::poll_id,
+                admin_audit_log::before_state,
+                admin_audit_log::after_state,
+                admin_audit_log::created_at,
+            ))
+            .into_boxed();
+
+        if let Some(admin_id) = filter.admin_id {
+            query = query.filter(admin_audit_log::admin_id.eq(admin_id));
+        }
+        if let Some(action) = filter.action {
+            query = query.filter(admin_audit_log::action.eq(action));
+        }
+        if let Some(contest_id) = filter.contest_id {
+            query = query.filter(admin_audit_log::contest_id.eq(contest_id));
+        }
+        if let Some(poll_id) = filter.poll_id {
+            query = query.filter(admin_audit_log::poll_id.eq(poll_id));
+        }
+        if let Some(since) = filter.since {
+            query = query.filter(admin_audit_log::created_at.ge(since));
+        }
+        if let Some(until) = filter.until {
+            query = query.filter(admin_audit_log::created_at.lt(until));
+        }
+
+        Ok(query
+            .order(admin_audit_log::id.desc())
+            .limit(filter.limit)
+            .load::<QueriedAdminAuditLog>(self.conn())?)
+    }
+}
+
+impl AdminAuditLogTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "admin_audit_log"]
+pub struct NewAdminAuditLog<'a> {
+    pub admin_id: Option<&'a Uuid>,
+    pub action: AdminAction,
+    pub contest_id: Option<&'a Uuid>,
+    pub poll_id: Option<&'a Uuid>,
+    pub before_state: Option<&'a serde_json::Value>,
+    pub after_state: Option<&'a serde_json::Value>,
+    pub created_at: &'a DateTime<Utc>,
+}
+
+#[derive(Clone, Copy)]
+pub struct AdminAuditLogFilter<'a> {
+    pub admin_id: Option<&'a Uuid>,
+    pub action: Option<AdminAction>,
+    pub contest_id: Option<&'a Uuid>,
+    pub poll_id: Option<&'a Uuid>,
+    pub since: Option<&'a DateTime<Utc>>,
+    pub until: Option<&'a DateTime<Utc>>,
+    pub limit: i64,
+}
+
+#[derive(Queryable)]
+pub struct QueriedAdminAuditLog {
+    pub id: i32,
+    pub admin_id: Option<Uuid>,
+    pub action: AdminAction,
+    pub contest_id: Option<Uuid>,
+    pub poll_id: Option<Uuid>,
+    pub before_state: Option<serde_json::Value>,
+    pub after_state: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,59 @@
+use super::{schema::admin_backup_codes, Connection};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub trait AdminBackupCodeTable {
+    fn conn(&self) -> &Connection;
+
+    /// 既存のバックアップコードを全て削除し、新しいものに置き換える
+    fn replace_all<'a>(
+        &self,
+        admin_id: &Uuid,
+        codes: &[NewAdminBackupCode<'a>],
+    ) -> anyhow::Result<()> {
+        self.delete_all(admin_id)?;
+        diesel::insert_into(admin_backup_codes::table)
+            .values(codes)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn delete_all(&self, admin_id: &Uuid) -> anyhow::Result<()> {
+        diesel::delete(admin_backup_codes::table.filter(admin_backup_codes::admin_id.eq(admin_id)))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// 未使用のバックアップコードを使用済みにする。
+    /// 該当するコードが無ければfalseを返す。
+    fn mark_used(
+        &self,
+        admin_id: &Uuid,
+        code_hash: &[u8],
+        used_at: &DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let n = diesel::update(
+            admin_backup_codes::table
+                .filter(admin_backup_codes::admin_id.eq(admin_id))
+                .filter(admin_backup_codes::code_hash.eq(code_hash))
+                .filter(admin_backup_codes::used_at.is_null()),
+        )
+        .set(admin_backup_codes::used_at.eq(used_at))
+        .execute(self.conn())?;
+        Ok(n > 0)
+    }
+}
+
+impl AdminBackupCodeTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "admin_backup_codes"]
+pub struct NewAdminBackupCode<'a> {
+    pub admin_id: &'a Uuid,
+    pub code_hash: &'a [u8],
+}
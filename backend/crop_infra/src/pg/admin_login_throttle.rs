@@ -0,0 +1,99 @@
+use super::{schema::admin_login_throttles, types::LoginThrottleKind, Connection};
+use chrono::{DateTime, Utc};
+use diesel::{
+    dsl::sql,
+    prelude::*,
+    sql_types::{Int4, Nullable, Timestamptz},
+};
+
+pub trait AdminLoginThrottleTable {
+    fn conn(&self) -> &Connection;
+
+    fn query_by_subject(
+        &self,
+        kind: LoginThrottleKind,
+        subject: &str,
+    ) -> anyhow::Result<Option<QueriedAdminLoginThrottle>> {
+        Ok(admin_login_throttles::table
+            .filter(admin_login_throttles::kind.eq(kind))
+            .filter(admin_login_throttles::subject.eq(subject))
+            .first::<QueriedAdminLoginThrottle>(self.conn())
+            .optional()?)
+    }
+
+    /// 失敗回数を1つ加算し、加算後の失敗回数を返す。
+    /// 同時に失敗した場合にも数え漏れないよう、読み出した値ではなくDB上の値に加算する。
+    /// 最後の失敗が `reset_before` 以前であれば、1から数え直す。
+    fn increment_failure(
+        &self,
+        kind: LoginThrottleKind,
+        subject: &str,
+        failed_at: &DateTime<Utc>,
+        reset_before: &DateTime<Utc>,
+    ) -> anyhow::Result<i32> {
+        let incremented = sql::<Int4>("CASE WHEN admin_login_throttles.last_failed_at > ")
+            .bind::<Timestamptz, _>(reset_before)
+            .sql(" THEN admin_login_throttles.failure_count + 1 ELSE 1 END");
+        Ok(diesel::insert_into(admin_login_throttles::table)
+            .values((
+                admin_login_throttles::kind.eq(kind),
+                admin_login_throttles::subject.eq(subject),
+                admin_login_throttles::failure_count.eq(1),
+                admin_login_throttles::last_failed_at.eq(failed_at),
+            ))
+            .on_conflict((admin_login_throttles::kind, admin_login_throttles::subject))
+            .do_update()
+            .set((
+                admin_login_throttles::failure_count.eq(incremented),
+                admin_login_throttles::last_failed_at.eq(failed_at),
+            ))
+            .returning(admin_login_throttles::failure_count)
+            .get_result(self.conn())?)
+    }
+
+    /// 同時に失敗した場合にも短いロックで上書きしないよう、長い方のロックを残す
+    fn extend_lock(
+        &self,
+        kind: LoginThrottleKind,
+        subject: &str,
+        locked_until: &DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let extended =
+            sql::<Nullable<Timestamptz>>("GREATEST(admin_login_throttles.locked_until, ")
+                .bind::<Timestamptz, _>(locked_until)
+                .sql(")");
+        diesel::update(
+            admin_login_throttles::table
+                .filter(admin_login_throttles::kind.eq(kind))
+                .filter(admin_login_throttles::subject.eq(subject)),
+        )
+        .set(admin_login_throttles::locked_until.eq(extended))
+        .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn delete(&self, kind: LoginThrottleKind, subject: &str) -> anyhow::Result<()> {
+        diesel::delete(
+            admin_login_throttles::table
+                .filter(admin_login_throttles::kind.eq(kind))
+                .filter(admin_login_throttles::subject.eq(subject)),
+        )
+        .execute(self.conn())?;
+        Ok(())
+    }
+}
+
+impl AdminLoginThrottleTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Queryable)]
+pub struct QueriedAdminLoginThrottle {
+    pub kind: LoginThrottleKind,
+    pub subject: String,
+    pub failure_count: i32,
+    pub last_failed_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,69 @@
+use super::{poll_market::PollMarketTable as _, schema::market_trades, Connection};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::Connection as _;
+use uuid::Uuid;
+
+pub trait MarketTradeTable {
+    fn conn(&self) -> &Connection;
+
+    /// マーケットの取引数が `num_trades` のままであれば、取引を記録する。
+    /// 他の取引が先に成立していた場合は何もせず `false` を返す。
+    fn save<'a>(&self, num_trades: i32, trade: NewMarketTrade<'a>) -> anyhow::Result<bool> {
+        self.conn().transaction::<_, anyhow::Error, _>(|| {
+            if !self
+                .conn()
+                .increment_num_trades(trade.poll_id, num_trades)?
+            {
+                return Ok(false);
+            }
+            diesel::insert_into(market_trades::table)
+                .values(trade)
+                .execute(self.conn())?;
+            Ok(true)
+        })
+    }
+
+    /// 取引が成立した順に取得する
+    fn query_by_poll_id(&self, poll_id: &Uuid) -> anyhow::Result<Vec<QueriedMarketTrade>> {
+        Ok(market_trades::table
+            .filter(market_trades::poll_id.eq(poll_id))
+            .select((
+                market_trades::poll_id,
+                market_trades::account_id,
+                market_trades::choice_name,
+                market_trades::cost,
+                market_trades::shares,
+                market_trades::created_at,
+            ))
+            .order(market_trades::id.asc())
+            .load::<QueriedMarketTrade>(self.conn())?)
+    }
+}
+
+impl MarketTradeTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "market_trades"]
+pub struct NewMarketTrade<'a> {
+    pub poll_id: &'a Uuid,
+    pub account_id: &'a Uuid,
+    pub choice_name: &'a str,
+    pub cost: f64,
+    pub shares: f64,
+    pub created_at: &'a DateTime<Utc>,
+}
+
+#[derive(Queryable)]
+pub struct QueriedMarketTrade {
+    pub poll_id: Uuid,
+    pub account_id: Uuid,
+    pub choice_name: String,
+    pub cost: f64,
+    pub shares: f64,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,10 +1,24 @@
 pub mod account;
 pub mod account_choice;
+pub mod account_credential;
+pub mod account_numeric_answer;
 pub mod admin;
+pub mod admin_audit_log;
+pub mod admin_backup_code;
+pub mod admin_login_throttle;
 pub mod choice;
 pub mod comment;
 pub mod contest;
+pub mod market_trade;
+pub mod point_entry;
+pub mod point_transaction;
 pub mod poll;
+pub mod poll_draft;
+pub mod poll_market;
+pub mod reward;
+pub mod reward_code;
+pub mod reward_delivery;
+pub mod reward_draw_score;
 #[allow(unused_imports)]
 pub(crate) mod schema;
 pub mod types;
@@ -16,6 +30,30 @@ use diesel::{
 
 pub type Connection = PooledConnection<ConnectionManager<PgConnection>>;
 
+/// `func` の中で行う読み書きを、1つのトランザクションにまとめる。
+/// `func` がエラーを返した場合は、それまでの書き込みをすべてロールバックする。
+pub fn transaction<T, E, F>(conn: &Connection, func: F) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E>,
+    E: From<anyhow::Error>,
+{
+    enum TxError<E> {
+        Func(E),
+        Pg(diesel::result::Error),
+    }
+
+    impl<E> From<diesel::result::Error> for TxError<E> {
+        fn from(e: diesel::result::Error) -> Self {
+            TxError::Pg(e)
+        }
+    }
+
+    diesel::Connection::transaction(conn, || func().map_err(TxError::Func)).map_err(|e| match e {
+        TxError::Func(e) => e,
+        TxError::Pg(e) => E::from(e.into()),
+    })
+}
+
 #[derive(Clone)]
 pub struct Pool {
     pool: PgPool<ConnectionManager<PgConnection>>,
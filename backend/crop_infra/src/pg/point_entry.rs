@@ -0,0 +1,82 @@
+use super::{
+    schema::{point_entries, point_transactions},
+    types::{PointBook, PointTransactionKind},
+    Connection,
+};
+use chrono::{DateTime, Utc};
+use diesel::{dsl::sql, prelude::*, sql_types::BigInt};
+use uuid::Uuid;
+
+pub trait PointEntryTable {
+    fn conn(&self) -> &Connection;
+
+    /// アカウントの残高
+    fn balance_of(&self, account_id: &Uuid) -> anyhow::Result<i64> {
+        Ok(point_entries::table
+            .filter(point_entries::book.eq(PointBook::Wallet))
+            .filter(point_entries::account_id.eq(account_id))
+            .select(sql::<BigInt>("COALESCE(SUM(amount), 0)::BIGINT"))
+            .first::<i64>(self.conn())?)
+    }
+
+    /// アカウントの残高に関わる仕訳を、新しいものから順に取得する
+    fn query_by_account_id(
+        &self,
+        account_id: &Uuid,
+        limit: i64,
+    ) -> anyhow::Result<Vec<QueriedWalletEntry>> {
+        Ok(point_entries::table
+            .inner_join(point_transactions::table)
+            .filter(point_entries::book.eq(PointBook::Wallet))
+            .filter(point_entries::account_id.eq(account_id))
+            .select((
+                point_transactions::id,
+                point_transactions::kind,
+                point_transactions::contest_id,
+                point_transactions::poll_id,
+                point_transactions::memo,
+                point_entries::amount,
+                point_transactions::created_at,
+            ))
+            .order((
+                point_transactions::created_at.desc(),
+                point_entries::id.desc(),
+            ))
+            .limit(limit)
+            .load::<QueriedWalletEntry>(self.conn())?)
+    }
+
+    /// Pollに賭けて預かっているポイントの仕訳を、記帳した順に取得する
+    fn query_escrow_by_poll_id(&self, poll_id: &Uuid) -> anyhow::Result<Vec<QueriedEscrowEntry>> {
+        Ok(point_entries::table
+            .inner_join(point_transactions::table)
+            .filter(point_entries::book.eq(PointBook::Escrow))
+            .filter(point_transactions::poll_id.eq(poll_id))
+            .select((point_entries::account_id, point_entries::amount))
+            .order(point_entries::id)
+            .load::<QueriedEscrowEntry>(self.conn())?)
+    }
+}
+
+impl PointEntryTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Queryable)]
+pub struct QueriedWalletEntry {
+    pub transaction_id: Uuid,
+    pub kind: PointTransactionKind,
+    pub contest_id: Option<Uuid>,
+    pub poll_id: Option<Uuid>,
+    pub memo: Option<String>,
+    pub amount: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Queryable)]
+pub struct QueriedEscrowEntry {
+    pub account_id: Option<Uuid>,
+    pub amount: i64,
+}
@@ -0,0 +1,209 @@
+use super::{
+    point_entry::PointEntryTable as _,
+    schema::{accounts, point_entries, point_transactions},
+    types::{PointBook, PointTransactionKind},
+    Connection,
+};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::Connection as _;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// 取引を記帳した結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Posting {
+    Posted,
+    /// 同じキーの取引が既に記帳されていた
+    AlreadyPosted,
+    /// 記帳するとアカウントの残高が負になる
+    Overdrawn(Uuid),
+}
+
+/// トランザクションをロールバックするためのエラー
+#[derive(Debug)]
+struct Overdrawn(Uuid);
+
+impl std::fmt::Display for Overdrawn {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Account {} is overdrawn", self.0)
+    }
+}
+
+impl std::error::Error for Overdrawn {}
+
+pub trait PointTransactionTable {
+    fn conn(&self) -> &Connection;
+
+    /// 取引と仕訳を記帳する。
+    /// 残高が減るアカウントは記帳が終わるまでロックし、残高が負になる場合は記帳しない。
+    /// ただし精算の取り消しは、払い戻したポイントを使っていても取り消せるよう、残高が負になっても記帳する。
+    fn post<'a>(
+        &self,
+        transaction: NewPointTransaction<'a>,
+        entries: &[NewPointEntry<'a>],
+    ) -> anyhow::Result<Posting> {
+        // デッドロックを避けるため、ロックする順番を揃える
+        let mut debited = entries
+            .iter()
+            .filter(|e| e.book == PointBook::Wallet && e.amount < 0)
+            .filter_map(|e| e.account_id)
+            .collect::<Vec<_>>();
+        debited.sort();
+        debited.dedup();
+
+        let result = self.conn().transaction::<_, anyhow::Error, _>(|| {
+            for account_id in debited.iter() {
+                accounts::table
+                    .filter(accounts::id.eq(account_id))
+                    .select(accounts::id)
+                    .for_update()
+                    .first::<Uuid>(self.conn())?;
+            }
+
+            let inserted = diesel::insert_into(point_transactions::table)
+                .values(&transaction)
+                .on_conflict(point_transactions::idempotency_key)
+                .do_nothing()
+                .execute(self.conn())?;
+            if inserted == 0 {
+                return Ok(Posting::AlreadyPosted);
+            }
+            diesel::insert_into(point_entries::table)
+                .values(entries)
+                .execute(self.conn())?;
+
+            if transaction.kind == PointTransactionKind::Reversal {
+                return Ok(Posting::Posted);
+            }
+            for account_id in debited.iter() {
+                if self.conn().balance_of(account_id)? < 0 {
+                    return Err(Overdrawn(**account_id).into());
+                }
+            }
+            Ok(Posting::Posted)
+        });
+
+        match result {
+            Err(e) => match e.downcast::<Overdrawn>() {
+                Ok(Overdrawn(account_id)) => Ok(Posting::Overdrawn(account_id)),
+                Err(e) => Err(e),
+            },
+            posting => posting,
+        }
+    }
+
+    /// 冪等キーで記帳済みの取引と、その仕訳を取得する
+    fn query_by_idempotency_key(
+        &self,
+        idempotency_key: &str,
+    ) -> anyhow::Result<Option<(QueriedPointTransaction, Vec<QueriedPointEntry>)>> {
+        let transaction = point_transactions::table
+            .filter(point_transactions::idempotency_key.eq(idempotency_key))
+            .select((point_transactions::id, point_transactions::kind))
+            .first::<QueriedPointTransaction>(self.conn())
+            .optional()?;
+        let transaction = match transaction {
+            Some(transaction) => transaction,
+            None => return Ok(None),
+        };
+        let entries = point_entries::table
+            .filter(point_entries::transaction_id.eq(&transaction.id))
+            .select((
+                point_entries::book,
+                point_entries::account_id,
+                point_entries::amount,
+            ))
+            .order(point_entries::id)
+            .load::<QueriedPointEntry>(self.conn())?;
+        Ok(Some((transaction, entries)))
+    }
+
+    /// Pollに関わる取引と、その仕訳を取得する
+    fn query_by_poll_id(
+        &self,
+        poll_id: &Uuid,
+    ) -> anyhow::Result<Vec<(QueriedPollTransaction, Vec<QueriedPointEntry>)>> {
+        let transactions = point_transactions::table
+            .filter(point_transactions::poll_id.eq(poll_id))
+            .select((
+                point_transactions::id,
+                point_transactions::idempotency_key,
+                point_transactions::kind,
+            ))
+            .order(point_transactions::created_at)
+            .load::<QueriedPollTransaction>(self.conn())?;
+        let ids = transactions.iter().map(|t| t.id).collect::<Vec<_>>();
+        let mut entries = HashMap::<Uuid, Vec<QueriedPointEntry>>::new();
+        for (transaction_id, entry) in point_entries::table
+            .filter(point_entries::transaction_id.eq_any(ids))
+            .select((
+                point_entries::transaction_id,
+                (
+                    point_entries::book,
+                    point_entries::account_id,
+                    point_entries::amount,
+                ),
+            ))
+            .order(point_entries::id)
+            .load::<(Uuid, QueriedPointEntry)>(self.conn())?
+        {
+            entries.entry(transaction_id).or_default().push(entry);
+        }
+        Ok(transactions
+            .into_iter()
+            .map(|t| {
+                let entries = entries.remove(&t.id).unwrap_or_default();
+                (t, entries)
+            })
+            .collect())
+    }
+}
+
+impl PointTransactionTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "point_transactions"]
+pub struct NewPointTransaction<'a> {
+    pub id: &'a Uuid,
+    pub idempotency_key: &'a str,
+    pub kind: PointTransactionKind,
+    pub contest_id: Option<&'a Uuid>,
+    pub poll_id: Option<&'a Uuid>,
+    pub admin_id: Option<&'a Uuid>,
+    pub memo: Option<&'a str>,
+    pub created_at: &'a DateTime<Utc>,
+}
+
+#[derive(Insertable)]
+#[table_name = "point_entries"]
+pub struct NewPointEntry<'a> {
+    pub transaction_id: &'a Uuid,
+    pub book: PointBook,
+    pub account_id: Option<&'a Uuid>,
+    pub amount: i64,
+}
+
+#[derive(Queryable)]
+pub struct QueriedPointTransaction {
+    pub id: Uuid,
+    pub kind: PointTransactionKind,
+}
+
+#[derive(Queryable)]
+pub struct QueriedPollTransaction {
+    pub id: Uuid,
+    pub idempotency_key: String,
+    pub kind: PointTransactionKind,
+}
+
+#[derive(Queryable)]
+pub struct QueriedPointEntry {
+    pub book: PointBook,
+    pub account_id: Option<Uuid>,
+    pub amount: i64,
+}
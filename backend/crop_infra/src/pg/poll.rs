@@ -1,4 +1,8 @@
-use super::{schema::polls, types::PollStatus, Connection};
+use super::{
+    schema::polls,
+    types::{PollKind, PollScoring, PollStatus},
+    Connection,
+};
 use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 use uuid::Uuid;
@@ -16,20 +20,20 @@ pub trait PollTable {
     fn query_by_contest_id(&self, id: &Uuid) -> anyhow::Result<Vec<QueriedPoll>> {
         Ok(polls::table
             .filter(polls::contest_id.eq(id))
-            .select((
-                polls::id,
-                polls::contest_id,
-                polls::status,
-                polls::title,
-                polls::created_at,
-                polls::duration_sec,
-                polls::idx,
-                polls::resolved_at,
-                polls::resolved_choice_name,
-            ))
+            .select(QUERIED_POLL_COLUMNS)
             .load::<QueriedPoll>(self.conn())?)
     }
 
+    /// Pollを取得し、トランザクションが終わるまで他の変更をロックする
+    fn query_by_id_for_update(&self, id: &Uuid) -> anyhow::Result<Option<QueriedPoll>> {
+        Ok(polls::table
+            .filter(polls::id.eq(id))
+            .select(QUERIED_POLL_COLUMNS)
+            .for_update()
+            .first::<QueriedPoll>(self.conn())
+            .optional()?)
+    }
+
     fn update_status(&self, id: &Uuid, new_status: PollStatus) -> anyhow::Result<()> {
         diesel::update(polls::table.filter(polls::id.eq(id)))
             .set(polls::status.eq(new_status))
@@ -37,15 +41,81 @@ pub trait PollTable {
         Ok(())
     }
 
+    /// 複数選択のPollや、順位を予想するPollの正解
+    fn update_resolved_choice_names(
+        &self,
+        id: &Uuid,
+        resolved_choice_names: &[&str],
+    ) -> anyhow::Result<()> {
+        diesel::update(polls::table.filter(polls::id.eq(id)))
+            .set((
+                polls::resolved_choice_names.eq(resolved_choice_names),
+                polls::resolved_at.eq(Utc::now()),
+            ))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// 数値で答えるPollの正解
+    fn update_resolved_value(&self, id: &Uuid, resolved_value: f64) -> anyhow::Result<()> {
+        diesel::update(polls::table.filter(polls::id.eq(id)))
+            .set((
+                polls::resolved_value.eq(resolved_value),
+                polls::resolved_at.eq(Utc::now()),
+            ))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn update_closed(&self, id: &Uuid, closed_at: &DateTime<Utc>) -> anyhow::Result<()> {
+        diesel::update(polls::table.filter(polls::id.eq(id)))
+            .set((
+                polls::status.eq(PollStatus::Closed),
+                polls::closed_at.eq(closed_at),
+            ))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// CloseしたPollを、新しい制限時間でOpenに戻す
+    fn update_reopened(&self, id: &Uuid, duration_sec: Option<i32>) -> anyhow::Result<()> {
+        diesel::update(polls::table.filter(polls::id.eq(id)))
+            .set((
+                polls::status.eq(PollStatus::Open),
+                polls::duration_sec.eq(duration_sec),
+                polls::closed_at.eq(None::<DateTime<Utc>>),
+            ))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// 延長や一時停止によるタイマーの変更
+    fn update_timer(
+        &self,
+        id: &Uuid,
+        duration_sec: Option<i32>,
+        paused_at: Option<&DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        diesel::update(polls::table.filter(polls::id.eq(id)))
+            .set((
+                polls::duration_sec.eq(duration_sec),
+                polls::paused_at.eq(paused_at),
+            ))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
     fn update_resolved_choice_name(
         &self,
         id: &Uuid,
         resolved_choice_name: &str,
+        answer_nonce: Option<&str>,
     ) -> anyhow::Result<()> {
         diesel::update(polls::table.filter(polls::id.eq(id)))
             .set((
                 polls::resolved_choice_name.eq(resolved_choice_name),
                 polls::resolved_at.eq(Utc::now()),
+                polls::answer_nonce.eq(answer_nonce),
             ))
             .execute(self.conn())?;
         Ok(())
@@ -67,8 +137,74 @@ pub struct NewPoll<'a> {
     pub created_at: &'a DateTime<Utc>,
     pub duration_sec: Option<i32>,
     pub idx: i32,
+    pub answer_commitment: Option<&'a [u8]>,
+    pub kind: PollKind,
+    pub scoring: PollScoring,
+    pub numeric_min: Option<f64>,
+    pub numeric_max: Option<f64>,
+    pub numeric_step: Option<f64>,
+    pub numeric_tolerance: Option<f64>,
+    pub ranking_size: Option<i32>,
+    pub is_scored: bool,
+    pub stake_multiplier: Option<f64>,
+    pub lock_before_close_sec: Option<i32>,
 }
 
+#[allow(clippy::type_complexity)]
+const QUERIED_POLL_COLUMNS: (
+    polls::id,
+    polls::contest_id,
+    polls::status,
+    polls::title,
+    polls::created_at,
+    polls::duration_sec,
+    polls::idx,
+    polls::resolved_at,
+    polls::resolved_choice_name,
+    polls::answer_commitment,
+    polls::answer_nonce,
+    polls::paused_at,
+    polls::closed_at,
+    polls::kind,
+    polls::scoring,
+    polls::resolved_choice_names,
+    polls::numeric_min,
+    polls::numeric_max,
+    polls::numeric_step,
+    polls::numeric_tolerance,
+    polls::resolved_value,
+    polls::ranking_size,
+    polls::is_scored,
+    polls::stake_multiplier,
+    polls::lock_before_close_sec,
+) = (
+    polls::id,
+    polls::contest_id,
+    polls::status,
+    polls::title,
+    polls::created_at,
+    polls::duration_sec,
+    polls::idx,
+    polls::resolved_at,
+    polls::resolved_choice_name,
+    polls::answer_commitment,
+    polls::answer_nonce,
+    polls::paused_at,
+    polls::closed_at,
+    polls::kind,
+    polls::scoring,
+    polls::resolved_choice_names,
+    polls::numeric_min,
+    polls::numeric_max,
+    polls::numeric_step,
+    polls::numeric_tolerance,
+    polls::resolved_value,
+    polls::ranking_size,
+    polls::is_scored,
+    polls::stake_multiplier,
+    polls::lock_before_close_sec,
+);
+
 #[derive(Queryable)]
 pub struct QueriedPoll {
     pub id: Uuid,
@@ -80,4 +216,20 @@ pub struct QueriedPoll {
     pub idx: i32,
     pub resolved_at: Option<DateTime<Utc>>,
     pub resolved_choice_name: Option<String>,
+    pub answer_commitment: Option<Vec<u8>>,
+    pub answer_nonce: Option<String>,
+    pub paused_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub kind: PollKind,
+    pub scoring: PollScoring,
+    pub resolved_choice_names: Option<Vec<String>>,
+    pub numeric_min: Option<f64>,
+    pub numeric_max: Option<f64>,
+    pub numeric_step: Option<f64>,
+    pub numeric_tolerance: Option<f64>,
+    pub resolved_value: Option<f64>,
+    pub ranking_size: Option<i32>,
+    pub is_scored: bool,
+    pub stake_multiplier: Option<f64>,
+    pub lock_before_close_sec: Option<i32>,
 }
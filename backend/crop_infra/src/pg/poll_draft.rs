@@ -0,0 +1,93 @@
+use super::{schema::poll_drafts, Connection};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub trait PollDraftTable {
+    fn conn(&self) -> &Connection;
+
+    #[allow(clippy::ptr_arg)]
+    fn save_all<'a>(&self, drafts: &Vec<NewPollDraft<'a>>) -> anyhow::Result<()> {
+        diesel::insert_into(poll_drafts::table)
+            .values(drafts)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// 公開する順に取得する
+    fn query_by_contest_id(&self, contest_id: &Uuid) -> anyhow::Result<Vec<QueriedPollDraft>> {
+        Ok(poll_drafts::table
+            .filter(poll_drafts::contest_id.eq(contest_id))
+            .select((
+                poll_drafts::id,
+                poll_drafts::contest_id,
+                poll_drafts::idx,
+                poll_drafts::title,
+                poll_drafts::duration_sec,
+                poll_drafts::choices,
+            ))
+            .order(poll_drafts::idx.asc())
+            .load::<QueriedPollDraft>(self.conn())?)
+    }
+
+    fn save<'a>(&self, draft: NewPollDraft<'a>) -> anyhow::Result<()> {
+        diesel::insert_into(poll_drafts::table)
+            .values(draft)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn update<'a>(&self, id: &Uuid, draft: UpdatedPollDraft<'a>) -> anyhow::Result<()> {
+        diesel::update(poll_drafts::table.filter(poll_drafts::id.eq(id)))
+            .set(draft)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn update_idx(&self, id: &Uuid, idx: i32) -> anyhow::Result<()> {
+        diesel::update(poll_drafts::table.filter(poll_drafts::id.eq(id)))
+            .set(poll_drafts::idx.eq(idx))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn delete(&self, id: &Uuid) -> anyhow::Result<()> {
+        diesel::delete(poll_drafts::table.filter(poll_drafts::id.eq(id))).execute(self.conn())?;
+        Ok(())
+    }
+}
+
+impl PollDraftTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "poll_drafts"]
+pub struct NewPollDraft<'a> {
+    pub id: &'a Uuid,
+    pub contest_id: &'a Uuid,
+    pub idx: i32,
+    pub title: &'a str,
+    pub duration_sec: Option<i32>,
+    pub choices: &'a serde_json::Value,
+}
+
+#[derive(AsChangeset)]
+#[table_name = "poll_drafts"]
+#[changeset_options(treat_none_as_null = "true")]
+pub struct UpdatedPollDraft<'a> {
+    pub title: &'a str,
+    pub duration_sec: Option<i32>,
+    pub choices: &'a serde_json::Value,
+}
+
+#[derive(Queryable)]
+pub struct QueriedPollDraft {
+    pub id: Uuid,
+    pub contest_id: Uuid,
+    pub idx: i32,
+    pub title: String,
+    pub duration_sec: Option<i32>,
+    pub choices: serde_json::Value,
+}
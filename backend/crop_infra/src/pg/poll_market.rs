@@ -0,0 +1,63 @@
+use super::{schema::poll_markets, Connection};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub trait PollMarketTable {
+    fn conn(&self) -> &Connection;
+
+    fn save(&self, market: NewPollMarket) -> anyhow::Result<()> {
+        diesel::insert_into(poll_markets::table)
+            .values(market)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn query_by_poll_id(&self, poll_id: &Uuid) -> anyhow::Result<Option<QueriedPollMarket>> {
+        Ok(poll_markets::table
+            .filter(poll_markets::poll_id.eq(poll_id))
+            .select((
+                poll_markets::poll_id,
+                poll_markets::liquidity,
+                poll_markets::budget,
+                poll_markets::num_trades,
+            ))
+            .first::<QueriedPollMarket>(self.conn())
+            .optional()?)
+    }
+
+    /// 取引数が `num_trades` のままであれば1つ進める。
+    /// 他の取引が先に成立していた場合は `false` を返す。
+    /// 取引の記録と同じトランザクションで呼ぶこと。
+    fn increment_num_trades(&self, poll_id: &Uuid, num_trades: i32) -> anyhow::Result<bool> {
+        let updated = diesel::update(
+            poll_markets::table
+                .filter(poll_markets::poll_id.eq(poll_id))
+                .filter(poll_markets::num_trades.eq(num_trades)),
+        )
+        .set(poll_markets::num_trades.eq(num_trades + 1))
+        .execute(self.conn())?;
+        Ok(updated == 1)
+    }
+}
+
+impl PollMarketTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "poll_markets"]
+pub struct NewPollMarket<'a> {
+    pub poll_id: &'a Uuid,
+    pub liquidity: f64,
+    pub budget: f64,
+}
+
+#[derive(Queryable)]
+pub struct QueriedPollMarket {
+    pub poll_id: Uuid,
+    pub liquidity: f64,
+    pub budget: f64,
+    pub num_trades: i32,
+}
@@ -0,0 +1,106 @@
+use super::{schema::rewards, types::RewardSelection, Connection};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub trait RewardTable {
+    fn conn(&self) -> &Connection;
+
+    fn save<'a>(&self, reward: NewReward<'a>) -> anyhow::Result<()> {
+        diesel::insert_into(rewards::table)
+            .values(reward)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn query_by_id(&self, id: &Uuid) -> anyhow::Result<Option<QueriedReward>> {
+        Ok(rewards::table
+            .filter(rewards::id.eq(id))
+            .select(QUERIED_REWARD_COLUMNS)
+            .first::<QueriedReward>(self.conn())
+            .optional()?)
+    }
+
+    fn query_by_contest_id(&self, contest_id: &Uuid) -> anyhow::Result<Vec<QueriedReward>> {
+        Ok(rewards::table
+            .filter(rewards::contest_id.eq(contest_id))
+            .select(QUERIED_REWARD_COLUMNS)
+            .order(rewards::created_at.asc())
+            .load::<QueriedReward>(self.conn())?)
+    }
+
+    fn update_seed(
+        &self,
+        id: &Uuid,
+        seed: &[u8],
+        seed_hash: &[u8],
+        committed_at: &DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        diesel::update(rewards::table.filter(rewards::id.eq(id)))
+            .set((
+                rewards::seed.eq(seed),
+                rewards::seed_hash.eq(seed_hash),
+                rewards::seed_committed_at.eq(committed_at),
+            ))
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    fn update_drawn(&self, id: &Uuid, drawn_at: &DateTime<Utc>) -> anyhow::Result<()> {
+        diesel::update(rewards::table.filter(rewards::id.eq(id)))
+            .set(rewards::drawn_at.eq(drawn_at))
+            .execute(self.conn())?;
+        Ok(())
+    }
+}
+
+impl RewardTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "rewards"]
+pub struct NewReward<'a> {
+    pub id: &'a Uuid,
+    pub contest_id: &'a Uuid,
+    pub name: &'a str,
+    pub quantity: i32,
+    pub selection: RewardSelection,
+}
+
+const QUERIED_REWARD_COLUMNS: (
+    rewards::id,
+    rewards::contest_id,
+    rewards::name,
+    rewards::quantity,
+    rewards::selection,
+    rewards::seed,
+    rewards::seed_hash,
+    rewards::seed_committed_at,
+    rewards::drawn_at,
+) = (
+    rewards::id,
+    rewards::contest_id,
+    rewards::name,
+    rewards::quantity,
+    rewards::selection,
+    rewards::seed,
+    rewards::seed_hash,
+    rewards::seed_committed_at,
+    rewards::drawn_at,
+);
+
+#[derive(Queryable)]
+pub struct QueriedReward {
+    pub id: Uuid,
+    pub contest_id: Uuid,
+    pub name: String,
+    pub quantity: i32,
+    pub selection: RewardSelection,
+    pub seed: Option<Vec<u8>>,
+    pub seed_hash: Option<Vec<u8>>,
+    pub seed_committed_at: Option<DateTime<Utc>>,
+    pub drawn_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,45 @@
+use super::{schema::reward_codes, Connection};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub trait RewardCodeTable {
+    fn conn(&self) -> &Connection;
+
+    /// 既に登録されているコードは無視する。
+    /// 新たに登録されたコードの数を返す。
+    fn save_all<'a>(&self, codes: &[NewRewardCode<'a>]) -> anyhow::Result<usize> {
+        Ok(diesel::insert_into(reward_codes::table)
+            .values(codes)
+            .on_conflict((reward_codes::reward_id, reward_codes::code))
+            .do_nothing()
+            .execute(self.conn())?)
+    }
+
+    /// 登録された順に取得する
+    fn query_by_reward_id(&self, reward_id: &Uuid) -> anyhow::Result<Vec<QueriedRewardCode>> {
+        Ok(reward_codes::table
+            .filter(reward_codes::reward_id.eq(reward_id))
+            .select((reward_codes::id, reward_codes::code))
+            .order(reward_codes::id.asc())
+            .load::<QueriedRewardCode>(self.conn())?)
+    }
+}
+
+impl RewardCodeTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "reward_codes"]
+pub struct NewRewardCode<'a> {
+    pub reward_id: &'a Uuid,
+    pub code: &'a str,
+}
+
+#[derive(Queryable)]
+pub struct QueriedRewardCode {
+    pub id: i32,
+    pub code: String,
+}
@@ -0,0 +1,75 @@
+use super::{schema::reward_deliveries, types::RewardDeliveryStatus, Connection};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub trait RewardDeliveryTable {
+    fn conn(&self) -> &Connection;
+
+    fn save_all<'a>(&self, deliveries: &[NewRewardDelivery<'a>]) -> anyhow::Result<()> {
+        diesel::insert_into(reward_deliveries::table)
+            .values(deliveries)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// 当選順に取得する
+    fn query_by_reward_id(&self, reward_id: &Uuid) -> anyhow::Result<Vec<QueriedRewardDelivery>> {
+        Ok(reward_deliveries::table
+            .filter(reward_deliveries::reward_id.eq(reward_id))
+            .select((
+                reward_deliveries::id,
+                reward_deliveries::account_id,
+                reward_deliveries::code_id,
+                reward_deliveries::rank,
+                reward_deliveries::status,
+                reward_deliveries::error,
+                reward_deliveries::delivered_at,
+            ))
+            .order(reward_deliveries::rank.asc())
+            .load::<QueriedRewardDelivery>(self.conn())?)
+    }
+
+    fn update_status(
+        &self,
+        id: i32,
+        status: RewardDeliveryStatus,
+        error: Option<&str>,
+        delivered_at: Option<&DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        diesel::update(reward_deliveries::table.filter(reward_deliveries::id.eq(id)))
+            .set((
+                reward_deliveries::status.eq(status),
+                reward_deliveries::error.eq(error),
+                reward_deliveries::delivered_at.eq(delivered_at),
+            ))
+            .execute(self.conn())?;
+        Ok(())
+    }
+}
+
+impl RewardDeliveryTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "reward_deliveries"]
+pub struct NewRewardDelivery<'a> {
+    pub reward_id: &'a Uuid,
+    pub account_id: &'a Uuid,
+    pub code_id: i32,
+    pub rank: i32,
+}
+
+#[derive(Queryable)]
+pub struct QueriedRewardDelivery {
+    pub id: i32,
+    pub account_id: Uuid,
+    pub code_id: i32,
+    pub rank: i32,
+    pub status: RewardDeliveryStatus,
+    pub error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,43 @@
+use super::{schema::reward_draw_scores, Connection};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub trait RewardDrawScoreTable {
+    fn conn(&self) -> &Connection;
+
+    fn save_all<'a>(&self, scores: &[NewRewardDrawScore<'a>]) -> anyhow::Result<()> {
+        diesel::insert_into(reward_draw_scores::table)
+            .values(scores)
+            .execute(self.conn())?;
+        Ok(())
+    }
+
+    /// AccountIdの順に取得する
+    fn query_by_reward_id(&self, reward_id: &Uuid) -> anyhow::Result<Vec<QueriedRewardDrawScore>> {
+        Ok(reward_draw_scores::table
+            .filter(reward_draw_scores::reward_id.eq(reward_id))
+            .select((reward_draw_scores::account_id, reward_draw_scores::score))
+            .order(reward_draw_scores::account_id.asc())
+            .load::<QueriedRewardDrawScore>(self.conn())?)
+    }
+}
+
+impl RewardDrawScoreTable for Connection {
+    fn conn(&self) -> &Connection {
+        self
+    }
+}
+
+#[derive(Insertable)]
+#[table_name = "reward_draw_scores"]
+pub struct NewRewardDrawScore<'a> {
+    pub reward_id: &'a Uuid,
+    pub account_id: &'a Uuid,
+    pub score: f64,
+}
+
+#[derive(Queryable)]
+pub struct QueriedRewardDrawScore {
+    pub account_id: Uuid,
+    pub score: f64,
+}
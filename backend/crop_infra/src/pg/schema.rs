@@ -30,6 +30,88 @@ table! {
         ///
         /// (Automatically generated by Diesel.)
         choice_name -> Text,
+        /// The `rank` column of the `account_choices` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        rank -> Nullable<Int4>,
+        /// The `stake` column of the `account_choices` table.
+        ///
+        /// Its SQL type is `Nullable<Int8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        stake -> Nullable<Int8>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `account_credentials` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    account_credentials (account_id) {
+        /// The `account_id` column of the `account_credentials` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        account_id -> Uuid,
+        /// The `email` column of the `account_credentials` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        email -> Text,
+        /// The `cred` column of the `account_credentials` table.
+        ///
+        /// Its SQL type is `Bytea`.
+        ///
+        /// (Automatically generated by Diesel.)
+        cred -> Bytea,
+        /// The `salt` column of the `account_credentials` table.
+        ///
+        /// Its SQL type is `Bytea`.
+        ///
+        /// (Automatically generated by Diesel.)
+        salt -> Bytea,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `account_numeric_answers` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    account_numeric_answers (id) {
+        /// The `id` column of the `account_numeric_answers` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `account_id` column of the `account_numeric_answers` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        account_id -> Uuid,
+        /// The `poll_id` column of the `account_numeric_answers` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        poll_id -> Uuid,
+        /// The `value` column of the `account_numeric_answers` table.
+        ///
+        /// Its SQL type is `Float8`.
+        ///
+        /// (Automatically generated by Diesel.)
+        value -> Float8,
     }
 }
 
@@ -56,6 +138,141 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `admin_audit_log` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    admin_audit_log (id) {
+        /// The `id` column of the `admin_audit_log` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `admin_id` column of the `admin_audit_log` table.
+        ///
+        /// Its SQL type is `Nullable<Uuid>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        admin_id -> Nullable<Uuid>,
+        /// The `action` column of the `admin_audit_log` table.
+        ///
+        /// Its SQL type is `Admin_action`.
+        ///
+        /// (Automatically generated by Diesel.)
+        action -> Admin_action,
+        /// The `contest_id` column of the `admin_audit_log` table.
+        ///
+        /// Its SQL type is `Nullable<Uuid>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        contest_id -> Nullable<Uuid>,
+        /// The `poll_id` column of the `admin_audit_log` table.
+        ///
+        /// Its SQL type is `Nullable<Uuid>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        poll_id -> Nullable<Uuid>,
+        /// The `before_state` column of the `admin_audit_log` table.
+        ///
+        /// Its SQL type is `Nullable<Jsonb>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        before_state -> Nullable<Jsonb>,
+        /// The `after_state` column of the `admin_audit_log` table.
+        ///
+        /// Its SQL type is `Nullable<Jsonb>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        after_state -> Nullable<Jsonb>,
+        /// The `created_at` column of the `admin_audit_log` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `admin_backup_codes` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    admin_backup_codes (id) {
+        /// The `id` column of the `admin_backup_codes` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `admin_id` column of the `admin_backup_codes` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        admin_id -> Uuid,
+        /// The `code_hash` column of the `admin_backup_codes` table.
+        ///
+        /// Its SQL type is `Bytea`.
+        ///
+        /// (Automatically generated by Diesel.)
+        code_hash -> Bytea,
+        /// The `used_at` column of the `admin_backup_codes` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        used_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `admin_login_throttles` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    admin_login_throttles (kind, subject) {
+        /// The `kind` column of the `admin_login_throttles` table.
+        ///
+        /// Its SQL type is `Login_throttle_kind`.
+        ///
+        /// (Automatically generated by Diesel.)
+        kind -> Login_throttle_kind,
+        /// The `subject` column of the `admin_login_throttles` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        subject -> Text,
+        /// The `failure_count` column of the `admin_login_throttles` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        failure_count -> Int4,
+        /// The `last_failed_at` column of the `admin_login_throttles` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        last_failed_at -> Timestamptz,
+        /// The `locked_until` column of the `admin_login_throttles` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        locked_until -> Nullable<Timestamptz>,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::pg::types::*;
@@ -88,6 +305,42 @@ table! {
         ///
         /// (Automatically generated by Diesel.)
         salt -> Bytea,
+        /// The `role` column of the `admins` table.
+        ///
+        /// Its SQL type is `Admin_role`.
+        ///
+        /// (Automatically generated by Diesel.)
+        role -> Admin_role,
+        /// The `disabled` column of the `admins` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        disabled -> Bool,
+        /// The `totp_secret` column of the `admins` table.
+        ///
+        /// Its SQL type is `Nullable<Bytea>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        totp_secret -> Nullable<Bytea>,
+        /// The `totp_enabled` column of the `admins` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        totp_enabled -> Bool,
+        /// The `totp_required` column of the `admins` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        totp_required -> Bool,
+        /// The `totp_last_step` column of the `admins` table.
+        ///
+        /// Its SQL type is `Nullable<Int8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        totp_last_step -> Nullable<Int8>,
     }
 }
 
@@ -230,81 +483,649 @@ table! {
     use diesel::sql_types::*;
     use crate::pg::types::*;
 
-    /// Representation of the `polls` table.
+    /// Representation of the `market_trades` table.
     ///
     /// (Automatically generated by Diesel.)
-    polls (id) {
-        /// The `id` column of the `polls` table.
+    market_trades (id) {
+        /// The `id` column of the `market_trades` table.
         ///
-        /// Its SQL type is `Uuid`.
+        /// Its SQL type is `Int4`.
         ///
         /// (Automatically generated by Diesel.)
-        id -> Uuid,
-        /// The `status` column of the `polls` table.
+        id -> Int4,
+        /// The `poll_id` column of the `market_trades` table.
         ///
-        /// Its SQL type is `Poll_status`.
+        /// Its SQL type is `Uuid`.
         ///
         /// (Automatically generated by Diesel.)
-        status -> Poll_status,
-        /// The `contest_id` column of the `polls` table.
+        poll_id -> Uuid,
+        /// The `account_id` column of the `market_trades` table.
         ///
         /// Its SQL type is `Uuid`.
         ///
         /// (Automatically generated by Diesel.)
-        contest_id -> Uuid,
-        /// The `title` column of the `polls` table.
+        account_id -> Uuid,
+        /// The `choice_name` column of the `market_trades` table.
         ///
         /// Its SQL type is `Text`.
         ///
         /// (Automatically generated by Diesel.)
-        title -> Text,
-        /// The `duration_sec` column of the `polls` table.
+        choice_name -> Text,
+        /// The `cost` column of the `market_trades` table.
         ///
-        /// Its SQL type is `Nullable<Int4>`.
+        /// Its SQL type is `Float8`.
         ///
         /// (Automatically generated by Diesel.)
-        duration_sec -> Nullable<Int4>,
-        /// The `idx` column of the `polls` table.
+        cost -> Float8,
+        /// The `shares` column of the `market_trades` table.
         ///
-        /// Its SQL type is `Int4`.
+        /// Its SQL type is `Float8`.
         ///
         /// (Automatically generated by Diesel.)
-        idx -> Int4,
-        /// The `created_at` column of the `polls` table.
+        shares -> Float8,
+        /// The `created_at` column of the `market_trades` table.
         ///
         /// Its SQL type is `Timestamptz`.
         ///
         /// (Automatically generated by Diesel.)
         created_at -> Timestamptz,
-        /// The `resolved_at` column of the `polls` table.
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `point_entries` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    point_entries (id) {
+        /// The `id` column of the `point_entries` table.
         ///
-        /// Its SQL type is `Nullable<Timestamptz>`.
+        /// Its SQL type is `Int4`.
         ///
         /// (Automatically generated by Diesel.)
-        resolved_at -> Nullable<Timestamptz>,
-        /// The `resolved_choice_name` column of the `polls` table.
+        id -> Int4,
+        /// The `transaction_id` column of the `point_entries` table.
         ///
-        /// Its SQL type is `Nullable<Text>`.
+        /// Its SQL type is `Uuid`.
         ///
         /// (Automatically generated by Diesel.)
-        resolved_choice_name -> Nullable<Text>,
+        transaction_id -> Uuid,
+        /// The `book` column of the `point_entries` table.
+        ///
+        /// Its SQL type is `Point_book`.
+        ///
+        /// (Automatically generated by Diesel.)
+        book -> Point_book,
+        /// The `account_id` column of the `point_entries` table.
+        ///
+        /// Its SQL type is `Nullable<Uuid>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        account_id -> Nullable<Uuid>,
+        /// The `amount` column of the `point_entries` table.
+        ///
+        /// Its SQL type is `Int8`.
+        ///
+        /// (Automatically generated by Diesel.)
+        amount -> Int8,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `point_transactions` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    point_transactions (id) {
+        /// The `id` column of the `point_transactions` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Uuid,
+        /// The `idempotency_key` column of the `point_transactions` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        idempotency_key -> Text,
+        /// The `kind` column of the `point_transactions` table.
+        ///
+        /// Its SQL type is `Point_transaction_kind`.
+        ///
+        /// (Automatically generated by Diesel.)
+        kind -> Point_transaction_kind,
+        /// The `contest_id` column of the `point_transactions` table.
+        ///
+        /// Its SQL type is `Nullable<Uuid>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        contest_id -> Nullable<Uuid>,
+        /// The `poll_id` column of the `point_transactions` table.
+        ///
+        /// Its SQL type is `Nullable<Uuid>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        poll_id -> Nullable<Uuid>,
+        /// The `admin_id` column of the `point_transactions` table.
+        ///
+        /// Its SQL type is `Nullable<Uuid>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        admin_id -> Nullable<Uuid>,
+        /// The `memo` column of the `point_transactions` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        memo -> Nullable<Text>,
+        /// The `created_at` column of the `point_transactions` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `poll_drafts` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    poll_drafts (id) {
+        /// The `id` column of the `poll_drafts` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Uuid,
+        /// The `contest_id` column of the `poll_drafts` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        contest_id -> Uuid,
+        /// The `idx` column of the `poll_drafts` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        idx -> Int4,
+        /// The `title` column of the `poll_drafts` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        title -> Text,
+        /// The `duration_sec` column of the `poll_drafts` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        duration_sec -> Nullable<Int4>,
+        /// The `choices` column of the `poll_drafts` table.
+        ///
+        /// Its SQL type is `Jsonb`.
+        ///
+        /// (Automatically generated by Diesel.)
+        choices -> Jsonb,
+        /// The `created_at` column of the `poll_drafts` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `poll_markets` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    poll_markets (poll_id) {
+        /// The `poll_id` column of the `poll_markets` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        poll_id -> Uuid,
+        /// The `liquidity` column of the `poll_markets` table.
+        ///
+        /// Its SQL type is `Float8`.
+        ///
+        /// (Automatically generated by Diesel.)
+        liquidity -> Float8,
+        /// The `budget` column of the `poll_markets` table.
+        ///
+        /// Its SQL type is `Float8`.
+        ///
+        /// (Automatically generated by Diesel.)
+        budget -> Float8,
+        /// The `num_trades` column of the `poll_markets` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        num_trades -> Int4,
+        /// The `created_at` column of the `poll_markets` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamptz,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `polls` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    polls (id) {
+        /// The `id` column of the `polls` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Uuid,
+        /// The `status` column of the `polls` table.
+        ///
+        /// Its SQL type is `Poll_status`.
+        ///
+        /// (Automatically generated by Diesel.)
+        status -> Poll_status,
+        /// The `contest_id` column of the `polls` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        contest_id -> Uuid,
+        /// The `title` column of the `polls` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        title -> Text,
+        /// The `duration_sec` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        duration_sec -> Nullable<Int4>,
+        /// The `idx` column of the `polls` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        idx -> Int4,
+        /// The `created_at` column of the `polls` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamptz,
+        /// The `resolved_at` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        resolved_at -> Nullable<Timestamptz>,
+        /// The `resolved_choice_name` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        resolved_choice_name -> Nullable<Text>,
+        /// The `answer_commitment` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Bytea>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        answer_commitment -> Nullable<Bytea>,
+        /// The `answer_nonce` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        answer_nonce -> Nullable<Text>,
+        /// The `paused_at` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        paused_at -> Nullable<Timestamptz>,
+        /// The `closed_at` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        closed_at -> Nullable<Timestamptz>,
+        /// The `kind` column of the `polls` table.
+        ///
+        /// Its SQL type is `Poll_kind`.
+        ///
+        /// (Automatically generated by Diesel.)
+        kind -> Poll_kind,
+        /// The `scoring` column of the `polls` table.
+        ///
+        /// Its SQL type is `Poll_scoring`.
+        ///
+        /// (Automatically generated by Diesel.)
+        scoring -> Poll_scoring,
+        /// The `resolved_choice_names` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Array<Text>>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        resolved_choice_names -> Nullable<Array<Text>>,
+        /// The `numeric_min` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Float8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        numeric_min -> Nullable<Float8>,
+        /// The `numeric_max` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Float8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        numeric_max -> Nullable<Float8>,
+        /// The `numeric_step` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Float8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        numeric_step -> Nullable<Float8>,
+        /// The `numeric_tolerance` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Float8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        numeric_tolerance -> Nullable<Float8>,
+        /// The `resolved_value` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Float8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        resolved_value -> Nullable<Float8>,
+        /// The `ranking_size` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        ranking_size -> Nullable<Int4>,
+        /// The `is_scored` column of the `polls` table.
+        ///
+        /// Its SQL type is `Bool`.
+        ///
+        /// (Automatically generated by Diesel.)
+        is_scored -> Bool,
+        /// The `stake_multiplier` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Float8>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        stake_multiplier -> Nullable<Float8>,
+        /// The `lock_before_close_sec` column of the `polls` table.
+        ///
+        /// Its SQL type is `Nullable<Int4>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        lock_before_close_sec -> Nullable<Int4>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `reward_codes` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    reward_codes (id) {
+        /// The `id` column of the `reward_codes` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `reward_id` column of the `reward_codes` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        reward_id -> Uuid,
+        /// The `code` column of the `reward_codes` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        code -> Text,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `reward_deliveries` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    reward_deliveries (id) {
+        /// The `id` column of the `reward_deliveries` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Int4,
+        /// The `reward_id` column of the `reward_deliveries` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        reward_id -> Uuid,
+        /// The `account_id` column of the `reward_deliveries` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        account_id -> Uuid,
+        /// The `code_id` column of the `reward_deliveries` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        code_id -> Int4,
+        /// The `rank` column of the `reward_deliveries` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        rank -> Int4,
+        /// The `status` column of the `reward_deliveries` table.
+        ///
+        /// Its SQL type is `Reward_delivery_status`.
+        ///
+        /// (Automatically generated by Diesel.)
+        status -> Reward_delivery_status,
+        /// The `error` column of the `reward_deliveries` table.
+        ///
+        /// Its SQL type is `Nullable<Text>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        error -> Nullable<Text>,
+        /// The `delivered_at` column of the `reward_deliveries` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        delivered_at -> Nullable<Timestamptz>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `reward_draw_scores` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    reward_draw_scores (reward_id, account_id) {
+        /// The `reward_id` column of the `reward_draw_scores` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        reward_id -> Uuid,
+        /// The `account_id` column of the `reward_draw_scores` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        account_id -> Uuid,
+        /// The `score` column of the `reward_draw_scores` table.
+        ///
+        /// Its SQL type is `Float8`.
+        ///
+        /// (Automatically generated by Diesel.)
+        score -> Float8,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::pg::types::*;
+
+    /// Representation of the `rewards` table.
+    ///
+    /// (Automatically generated by Diesel.)
+    rewards (id) {
+        /// The `id` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        id -> Uuid,
+        /// The `contest_id` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Uuid`.
+        ///
+        /// (Automatically generated by Diesel.)
+        contest_id -> Uuid,
+        /// The `name` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Text`.
+        ///
+        /// (Automatically generated by Diesel.)
+        name -> Text,
+        /// The `quantity` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Int4`.
+        ///
+        /// (Automatically generated by Diesel.)
+        quantity -> Int4,
+        /// The `selection` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Reward_selection`.
+        ///
+        /// (Automatically generated by Diesel.)
+        selection -> Reward_selection,
+        /// The `seed` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Nullable<Bytea>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        seed -> Nullable<Bytea>,
+        /// The `drawn_at` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        drawn_at -> Nullable<Timestamptz>,
+        /// The `created_at` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Timestamptz`.
+        ///
+        /// (Automatically generated by Diesel.)
+        created_at -> Timestamptz,
+        /// The `seed_hash` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Nullable<Bytea>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        seed_hash -> Nullable<Bytea>,
+        /// The `seed_committed_at` column of the `rewards` table.
+        ///
+        /// Its SQL type is `Nullable<Timestamptz>`.
+        ///
+        /// (Automatically generated by Diesel.)
+        seed_committed_at -> Nullable<Timestamptz>,
     }
 }
 
 joinable!(account_choices -> accounts (account_id));
 joinable!(account_choices -> polls (poll_id));
+joinable!(account_credentials -> accounts (account_id));
+joinable!(account_numeric_answers -> accounts (account_id));
+joinable!(account_numeric_answers -> polls (poll_id));
+joinable!(admin_audit_log -> admins (admin_id));
+joinable!(admin_audit_log -> contests (contest_id));
+joinable!(admin_audit_log -> polls (poll_id));
+joinable!(admin_backup_codes -> admins (admin_id));
 joinable!(choices -> polls (poll_id));
 joinable!(comments -> accounts (account_id));
 joinable!(comments -> contests (contest_id));
 joinable!(comments -> polls (poll_id));
+joinable!(market_trades -> accounts (account_id));
+joinable!(market_trades -> poll_markets (poll_id));
+joinable!(point_entries -> accounts (account_id));
+joinable!(point_entries -> point_transactions (transaction_id));
+joinable!(point_transactions -> admins (admin_id));
+joinable!(point_transactions -> contests (contest_id));
+joinable!(point_transactions -> polls (poll_id));
+joinable!(poll_drafts -> contests (contest_id));
+joinable!(poll_markets -> polls (poll_id));
 joinable!(polls -> contests (contest_id));
+joinable!(reward_codes -> rewards (reward_id));
+joinable!(reward_deliveries -> accounts (account_id));
+joinable!(reward_deliveries -> reward_codes (code_id));
+joinable!(reward_deliveries -> rewards (reward_id));
+joinable!(reward_draw_scores -> accounts (account_id));
+joinable!(reward_draw_scores -> rewards (reward_id));
+joinable!(rewards -> contests (contest_id));
 
 allow_tables_to_appear_in_same_query!(
     account_choices,
+    account_credentials,
+    account_numeric_answers,
     accounts,
+    admin_audit_log,
+    admin_backup_codes,
+    admin_login_throttles,
     admins,
     choices,
     comments,
     contests,
+    market_trades,
+    point_entries,
+    point_transactions,
+    poll_drafts,
+    poll_markets,
     polls,
+    reward_codes,
+    reward_deliveries,
+    reward_draw_scores,
+    rewards,
 );
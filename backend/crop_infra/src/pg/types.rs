@@ -15,4 +15,130 @@ pub enum ContestStatus {
 pub enum PollStatus {
     Open,
     Closed,
+    Voided,
+}
+
+/// 1アカウントが選べる選択肢の数。
+/// `Numeric` では選択肢を使わず、数値で答える。
+/// `Ranking` では、選択肢を上位から順に並べて答える。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, Serialize, Deserialize, JsonSchema)]
+#[DieselType = "Poll_kind"]
+pub enum PollKind {
+    Single,
+    Multiple,
+    Numeric,
+    Ranking,
+}
+
+impl Default for PollKind {
+    fn default() -> Self {
+        PollKind::Single
+    }
+}
+
+/// 複数選択のPollや、数値・順位で答えるPollの採点方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, Serialize, Deserialize, JsonSchema)]
+#[DieselType = "Poll_scoring"]
+pub enum PollScoring {
+    Exact,
+    Partial,
+    Tolerance,
+    Closest,
+    Position,
+    Kendall,
+}
+
+impl Default for PollScoring {
+    fn default() -> Self {
+        PollScoring::Exact
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, Serialize, Deserialize, JsonSchema)]
+#[DieselType = "Admin_role"]
+pub enum AdminRole {
+    Superadmin,
+    Operator,
+    Moderator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, Serialize, Deserialize, JsonSchema)]
+#[DieselType = "Admin_action"]
+pub enum AdminAction {
+    CreateContest,
+    AddPoll,
+    ClosePoll,
+    ResolvePoll,
+    CloseContest,
+    ArchiveContest,
+    LoginFailed,
+    LoginLocked,
+    OpenContest,
+    ImportContest,
+    CreateReward,
+    DrawReward,
+    CommitRewardSeed,
+    VoidPoll,
+    ReResolvePoll,
+    ExtendPoll,
+    PausePoll,
+    ResumePoll,
+    ReopenPoll,
+    OpenMarket,
+    GrantPoints,
+    AdjustPoints,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, Serialize, Deserialize, JsonSchema)]
+#[DieselType = "Login_throttle_kind"]
+pub enum LoginThrottleKind {
+    Email,
+    Ip,
+    AccountEmail,
+    AccountIp,
+    Totp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, Serialize, Deserialize, JsonSchema)]
+#[DieselType = "Reward_selection"]
+pub enum RewardSelection {
+    Top,
+    Lottery,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, Serialize, Deserialize, JsonSchema)]
+#[DieselType = "Reward_delivery_status"]
+pub enum RewardDeliveryStatus {
+    Pending,
+    Sent,
+    Failed,
+}
+
+/// 仕訳の対象となる帳簿
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, Serialize, Deserialize, JsonSchema)]
+#[DieselType = "Point_book"]
+pub enum PointBook {
+    /// アカウントの残高
+    Wallet,
+    /// ポイントの発行元
+    Issuance,
+    /// Pollに賭けて、精算を待っているポイント
+    Escrow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, DbEnum, Serialize, Deserialize, JsonSchema)]
+#[DieselType = "Point_transaction_kind"]
+pub enum PointTransactionKind {
+    Grant,
+    Adjustment,
+    /// 回答に賭けるポイントの変更
+    Stake,
+    /// Resolveしたときの、賭けたポイントやマーケットの株の精算
+    Payout,
+    /// Pollを中止したときの、賭けたポイントやマーケットで支払ったポイントの払い戻し
+    Refund,
+    /// マーケットで株を買うときの支払い
+    Trade,
+    /// 正解を訂正したときの、精算の取り消し
+    Reversal,
 }
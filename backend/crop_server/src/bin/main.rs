@@ -1,5 +1,6 @@
 use crop_infra::{jwt, pg::Pool};
 use crop_server::{context::Context, server};
+use std::net::IpAddr;
 
 #[tokio::main]
 async fn main() {
@@ -11,7 +12,7 @@ async fn main() {
 
     // Contextの初期化
     let pg_pool = Pool::new(get_env_var_or_panic("DATABASE_URL"));
-    let context = Context::new(pg_pool);
+    let context = Context::new(pg_pool).trust_proxies(get_trusted_proxies());
 
     // Serverの起動
     let port = get_env_var_u16_or_panic("PORT");
@@ -23,6 +24,20 @@ fn get_env_var_or_panic(key: &'static str) -> String {
     std::env::var(key).unwrap_or_else(|_| panic!(format!("{} is not specified", key)))
 }
 
+/// カンマ区切りで指定する。指定しない場合はX-Forwarded-Forを使わない。
+fn get_trusted_proxies() -> Vec<IpAddr> {
+    std::env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse()
+                .unwrap_or_else(|_| panic!("Invalid TRUSTED_PROXIES entry : {}", s))
+        })
+        .collect()
+}
+
 fn get_env_var_u16_or_panic(key: &'static str) -> u16 {
     let s = get_env_var_or_panic(key);
     u16::from_str_radix(s.as_str(), 10).unwrap()
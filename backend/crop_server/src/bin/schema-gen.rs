@@ -30,6 +30,18 @@ fn main() {
         routes::contests::post::ResBody
     );
 
+    /*
+     * POST /contests/import
+     */
+    write_json_schema!(
+        "api/contests_import__post__req.json",
+        routes::contests::import::post::ReqBody
+    );
+    write_json_schema!(
+        "api/contests_import__post__res.json",
+        routes::contests::import::post::ResBody
+    );
+
     /*
      * GET /contests/:id
      */
@@ -38,6 +50,58 @@ fn main() {
         routes::contests::_id::get::ResBody
     );
 
+    /*
+     * GET /contests/:id/export
+     */
+    write_json_schema!(
+        "api/contests_id_export__get__query.json",
+        routes::contests::_id::export::get::ReqQuery
+    );
+    write_json_schema!(
+        "api/contests_id_export__get__res.json",
+        routes::contests::_id::export::get::ResBody
+    );
+
+    /*
+     * GET /contests/:id/drafts
+     */
+    write_json_schema!(
+        "api/contests_id_drafts__get__res.json",
+        routes::contests::_id::drafts::get::ResBody
+    );
+
+    /*
+     * POST /contests/:id/drafts
+     */
+    write_json_schema!(
+        "api/contests_id_drafts__post__req.json",
+        routes::contests::_id::drafts::post::ReqBody
+    );
+    write_json_schema!(
+        "api/contests_id_drafts__post__res.json",
+        routes::contests::_id::drafts::post::ResBody
+    );
+
+    /*
+     * PATCH /contests/:id/drafts/:id
+     */
+    write_json_schema!(
+        "api/contests_id_drafts_id__patch__req.json",
+        routes::contests::_id::drafts::_id::patch::ReqBody
+    );
+    write_json_schema!(
+        "api/contests_id_drafts_id__patch__res.json",
+        routes::contests::_id::drafts::_id::patch::ResBody
+    );
+
+    /*
+     * DELETE /contests/:id/drafts/:id
+     */
+    write_json_schema!(
+        "api/contests_id_drafts_id__delete__res.json",
+        routes::contests::_id::drafts::_id::delete::ResBody
+    );
+
     /*
      * POST /contests/:id/polls
      */
@@ -50,6 +114,14 @@ fn main() {
         routes::contests::_id::polls::post::ResBody
     );
 
+    /*
+     * POST /contests/:id/polls/next
+     */
+    write_json_schema!(
+        "api/contests_id_polls_next__post__res.json",
+        routes::contests::_id::polls::next::post::ResBody
+    );
+
     /*
      * PATCH /contests/:id/polls/:id
      */
@@ -58,6 +130,50 @@ fn main() {
         routes::contests::_id::polls::_id::patch::ReqBody
     );
 
+    /*
+     * PATCH /contests/:id/polls/:id/timer
+     */
+    write_json_schema!(
+        "api/contests_id_polls_id_timer__patch__req.json",
+        routes::contests::_id::polls::_id::timer::patch::ReqBody
+    );
+
+    /*
+     * GET /contests/:id/polls/:id/market
+     */
+    write_json_schema!(
+        "api/contests_id_polls_id_market__get__res.json",
+        routes::contests::_id::polls::_id::market::get::ResBody
+    );
+
+    /*
+     * POST /contests/:id/polls/:id/market
+     */
+    write_json_schema!(
+        "api/contests_id_polls_id_market__post__req.json",
+        routes::contests::_id::polls::_id::market::post::ReqBody
+    );
+
+    /*
+     * POST /contests/:id/polls/:id/market/trades
+     */
+    write_json_schema!(
+        "api/contests_id_polls_id_market_trades__post__req.json",
+        routes::contests::_id::polls::_id::market::trades::post::ReqBody
+    );
+    write_json_schema!(
+        "api/contests_id_polls_id_market_trades__post__res.json",
+        routes::contests::_id::polls::_id::market::trades::post::ResBody
+    );
+
+    /*
+     * GET /contests/:id/rewards/:id/verification
+     */
+    write_json_schema!(
+        "api/contests_id_rewards_id_verification__get__res.json",
+        routes::contests::_id::rewards::_id::verification::get::ResBody
+    );
+
     /*
      * POST /accounts/
      */
@@ -70,6 +186,34 @@ fn main() {
         routes::accounts::post::ResBody
     );
 
+    /*
+     * POST /accounts/access_tokens
+     */
+    write_json_schema!(
+        "api/accounts_access_tokens__post__req.json",
+        routes::accounts::access_tokens::post::ReqBody
+    );
+    write_json_schema!(
+        "api/accounts_access_tokens__post__res.json",
+        routes::accounts::access_tokens::post::ResBody
+    );
+
+    /*
+     * POST /accounts/me/credentials
+     */
+    write_json_schema!(
+        "api/accounts_me_credentials__post__req.json",
+        routes::accounts::me::credentials::post::ReqBody
+    );
+
+    /*
+     * GET /accounts/me/wallet
+     */
+    write_json_schema!(
+        "api/accounts_me_wallet__get__res.json",
+        routes::accounts::me::wallet::get::ResBody
+    );
+
     /*
      * POST /admins/me/access_tokens
      */
@@ -81,4 +225,67 @@ fn main() {
         "api/admins_me_access_tokens__post__res.json",
         routes::admins::me::access_tokens::post::ResBody
     );
+
+    /*
+     * POST /admins/me/totp
+     */
+    write_json_schema!(
+        "api/admins_me_totp__post__res.json",
+        routes::admins::me::totp::post::ResBody
+    );
+
+    /*
+     * POST /admins/me/totp/verification
+     */
+    write_json_schema!(
+        "api/admins_me_totp_verification__post__req.json",
+        routes::admins::me::totp::verification::post::ReqBody
+    );
+    write_json_schema!(
+        "api/admins_me_totp_verification__post__res.json",
+        routes::admins::me::totp::verification::post::ResBody
+    );
+
+    /*
+     * GET /admins
+     */
+    write_json_schema!("api/admins__get__res.json", routes::admins::get::ResBody);
+
+    /*
+     * POST /admins
+     */
+    write_json_schema!("api/admins__post__req.json", routes::admins::post::ReqBody);
+    write_json_schema!("api/admins__post__res.json", routes::admins::post::ResBody);
+
+    /*
+     * PATCH /admins/:id
+     */
+    write_json_schema!(
+        "api/admins_id__patch__req.json",
+        routes::admins::_id::patch::ReqBody
+    );
+
+    /*
+     * GET /admin/audit_log
+     */
+    write_json_schema!(
+        "api/admin_audit_log__get__query.json",
+        routes::admin::audit_log::get::ReqQuery
+    );
+    write_json_schema!(
+        "api/admin_audit_log__get__res.json",
+        routes::admin::audit_log::get::ResBody
+    );
+
+    /*
+     * POST /admin/wallets/:id/transactions
+     */
+    write_json_schema!(
+        "api/admin_wallets_id_transactions__post__req.json",
+        routes::admin::wallets::_id::transactions::post::ReqBody
+    );
+    write_json_schema!(
+        "api/admin_wallets_id_transactions__post__res.json",
+        routes::admin::wallets::_id::transactions::post::ResBody
+    );
 }
@@ -1,16 +1,23 @@
 use crate::{error::Error, response::Response};
 use crop_infra::pg::Pool;
 use futures::future::{TryFuture, TryFutureExt};
+use std::{net::IpAddr, sync::Arc};
 use warp::reject::Rejection;
 
 mod contest;
+mod timer;
 
 pub use contest::ContestManager;
+pub use timer::TimerManager;
 
 #[derive(Clone)]
 pub struct Context {
     pub pg: Pool,
     pub contest_manager: ContestManager,
+    /// Pollを自動でCloseするタイマー
+    pub timer_manager: TimerManager,
+    /// X-Forwarded-Forを付与する、信頼できるリバースプロキシのアドレス
+    pub trusted_proxies: Arc<Vec<IpAddr>>,
 }
 
 impl Context {
@@ -18,6 +25,15 @@ impl Context {
         Context {
             pg,
             contest_manager: ContestManager::new(),
+            timer_manager: TimerManager::new(),
+            trusted_proxies: Arc::new(Vec::new()),
+        }
+    }
+
+    pub fn trust_proxies(self, proxies: Vec<IpAddr>) -> Context {
+        Context {
+            trusted_proxies: Arc::new(proxies),
+            ..self
         }
     }
 
@@ -0,0 +1,47 @@
+use crop_domain::contest::poll::PollId;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::Mutex;
+
+/// Pollを自動でCloseするタイマーを管理する。
+/// 同じPollで新しいタイマーを開始すると、古いタイマーは目覚めた時に何もせず終了する。
+#[derive(Clone)]
+pub struct TimerManager {
+    next_generation: Arc<AtomicU64>,
+    generations: Arc<Mutex<HashMap<PollId, u64>>>,
+}
+
+impl TimerManager {
+    pub fn new() -> TimerManager {
+        TimerManager {
+            next_generation: Arc::new(AtomicU64::new(0)),
+            generations: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 新しいタイマーの世代を発行し、それより前のタイマーを無効にする
+    pub async fn start(&self, poll_id: PollId) -> u64 {
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+        self.generations.lock().await.insert(poll_id, generation);
+        generation
+    }
+
+    /// 後から別のタイマーが開始されていなければtrue
+    pub async fn is_current(&self, poll_id: &PollId, generation: u64) -> bool {
+        self.generations.lock().await.get(poll_id) == Some(&generation)
+    }
+
+    /// 終了したタイマーの世代を破棄する。
+    /// 後から開始されたタイマーの世代は残す。
+    pub async fn finish(&self, poll_id: &PollId, generation: u64) {
+        let mut generations = self.generations.lock().await;
+        if generations.get(poll_id) == Some(&generation) {
+            generations.remove(poll_id);
+        }
+    }
+}
@@ -1,7 +1,10 @@
-use crate::error::Error;
+use crate::{context::Context, error::Error};
 use crop_domain::{
     account,
-    admin::model::{AccessToken, AuthenticatedAdmin},
+    admin::{
+        model::{AccessToken, Admin as _, AuthenticatedAdmin, Permission},
+        repository,
+    },
 };
 use http::StatusCode;
 use std::str::FromStr;
@@ -11,19 +14,61 @@ use warp::{
     Filter,
 };
 
-pub fn admin() -> BoxedFilter<(AuthenticatedAdmin,)> {
+/// AccessTokenを持つAdminのみを通すFilter。
+/// AccessTokenの内容は、リクエストのたびにDBにあるAdminの状態で検証し直す。
+pub fn admin(ctx: Context) -> BoxedFilter<(AuthenticatedAdmin,)> {
     (header::<BearerToken<AccessToken>>("Authorization")
         .or(header::<BearerToken<AccessToken>>("authorization"))
         .unify())
-    .map(|BearerToken(token)| AuthenticatedAdmin::from(token))
     .or_else(|r| {
         log::debug!("Admin authentication is rejected : {:?}", r);
         let err = Error::new(StatusCode::UNAUTHORIZED, "Unauthenticated");
         futures::future::err(Into::<Rejection>::into(err))
     })
+    .and_then(move |BearerToken(token): BearerToken<AccessToken>| {
+        let pg = ctx.pg.clone();
+        async move {
+            let authenticated = pg
+                .with_conn(move |conn| repository::authenticate_token(&conn, &token))
+                .await
+                .map_err(|e| Into::<Rejection>::into(Error::from(e)))?;
+            authenticated.map_err(|e| {
+                log::info!("Admin authentication is rejected : {:?}", e);
+                Into::<Rejection>::into(Error::new(StatusCode::UNAUTHORIZED, "Unauthenticated"))
+            })
+        }
+    })
     .boxed()
 }
 
+/// 指定した操作が許可されているAdminのみを通すFilter
+pub fn admin_with(ctx: Context, permission: Permission) -> BoxedFilter<(AuthenticatedAdmin,)> {
+    admin(ctx)
+        .and_then(move |admin: AuthenticatedAdmin| {
+            futures::future::ready(
+                require(&admin, permission)
+                    .map(|_| admin)
+                    .map_err(Into::<Rejection>::into),
+            )
+        })
+        .boxed()
+}
+
+/// 1つのrouteで複数の操作を扱う場合に、
+/// 操作ごとに権限をチェックするために使う
+pub fn require(admin: &AuthenticatedAdmin, permission: Permission) -> Result<(), Error> {
+    if admin.has_permission(permission) {
+        Ok(())
+    } else {
+        log::info!(
+            "Admin {:?} is not permitted to {:?}",
+            admin.id(),
+            permission
+        );
+        Err(Error::new(StatusCode::FORBIDDEN, "Forbidden"))
+    }
+}
+
 pub fn account() -> BoxedFilter<(account::Authenticated,)> {
     // TODO : case insensitive
     (header::<BearerToken<account::AccessToken>>("Authorization")
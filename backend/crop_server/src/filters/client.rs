@@ -0,0 +1,80 @@
+use crate::context::Context;
+use std::net::{IpAddr, SocketAddr};
+use warp::{filters::BoxedFilter, Filter};
+
+/// TCP接続の相手のアドレス。
+/// `Server` がリクエストごとに付与する。
+#[derive(Clone, Copy, Debug)]
+pub struct RemoteAddr(pub SocketAddr);
+
+/// クライアントのIPアドレスを取り出すFilter
+///
+/// X-Forwarded-Forはクライアントが自由に詐称できるので、
+/// 接続元が設定済みの信頼できるプロキシである場合のみ使う。
+/// それ以外では接続元のアドレスを使う。
+pub fn ip(ctx: Context) -> BoxedFilter<(Option<IpAddr>,)> {
+    warp::filters::header::optional::<String>("x-forwarded-for")
+        .and(warp::filters::ext::optional::<RemoteAddr>())
+        .and(warp::filters::addr::remote())
+        .map(
+            move |forwarded: Option<String>,
+                  remote: Option<RemoteAddr>,
+                  addr: Option<SocketAddr>| {
+                let remote = remote.map(|RemoteAddr(addr)| addr).or(addr)?;
+                Some(client_ip(
+                    forwarded.as_deref(),
+                    remote.ip(),
+                    ctx.trusted_proxies.as_slice(),
+                ))
+            },
+        )
+        .boxed()
+}
+
+/// 信頼できるプロキシを経由している間だけ、X-Forwarded-Forを末尾から辿る。
+/// 信頼できるプロキシ以外で最初に現れたアドレスがクライアントのもの。
+fn client_ip(forwarded: Option<&str>, remote: IpAddr, trusted_proxies: &[IpAddr]) -> IpAddr {
+    let mut client = remote;
+    let hops = forwarded.into_iter().flat_map(|f| f.rsplit(','));
+    for hop in hops {
+        if !trusted_proxies.contains(&client) {
+            break;
+        }
+        match hop.trim().parse() {
+            Ok(ip) => client = ip,
+            Err(_) => break,
+        }
+    }
+    client
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn ignore_forwarded_for_from_untrusted_remote() {
+        let proxies = [ip("10.0.0.1")];
+        let client = client_ip(Some("1.1.1.1"), ip("2.2.2.2"), &proxies);
+        assert_eq!(client, ip("2.2.2.2"));
+    }
+
+    #[test]
+    fn follow_forwarded_for_through_trusted_proxies() {
+        let proxies = [ip("10.0.0.1"), ip("10.0.0.2")];
+        // 先頭の値はクライアントが詐称したもの
+        let forwarded = "9.9.9.9, 1.1.1.1, 10.0.0.2";
+        let client = client_ip(Some(forwarded), ip("10.0.0.1"), &proxies);
+        assert_eq!(client, ip("1.1.1.1"));
+    }
+
+    #[test]
+    fn use_remote_without_forwarded_for() {
+        let proxies = [ip("10.0.0.1")];
+        assert_eq!(client_ip(None, ip("10.0.0.1"), &proxies), ip("10.0.0.1"));
+    }
+}
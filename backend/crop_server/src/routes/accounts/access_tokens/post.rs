@@ -0,0 +1,72 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::client,
+    response::{self, Response},
+};
+use crop_domain::{
+    account::{normalize_email, Account as _, AccountRepository as _},
+    admin::{repository::Updatable as _, service::auth::reject_credentials, throttle},
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqBody {
+    email: String,
+    pass: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ResBody {
+    access_token: String,
+}
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("accounts" / "access_tokens")
+        .and(warp::filters::method::post())
+        .and(client::ip(ctx.clone()))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |ip, body| ctx.clone().handle_request(move |ctx| inner(ctx, ip, body)))
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, ip: Option<IpAddr>, body: ReqBody) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let email = normalize_email(body.email.as_str());
+            let attempt = throttle::query_account_attempt(&conn, email.as_str(), ip.as_ref())?;
+            attempt.check().map_err(|e| {
+                log::info!("account login is throttled : {:?}", e);
+                Error::new(StatusCode::TOO_MANY_REQUESTS, "Too many failed attempts")
+            })?;
+
+            let authenticated = match conn.query_unauthenticated(email.as_str())? {
+                Some(u) => u.authenticate(body.pass.as_str()),
+                // 存在しないemailでもPBKDF2を実行し、応答時間の差をなくす
+                None => Err(reject_credentials(body.pass.as_str())),
+            };
+
+            match authenticated {
+                Ok(account) => {
+                    attempt.succeed().save(&conn)?;
+                    let access_token = account.gen_access_token().encode();
+                    Ok(response::new(
+                        StatusCode::CREATED,
+                        &ResBody { access_token },
+                    ))
+                }
+                Err(e) => {
+                    log::info!("failed to auth account : {:?}", e);
+                    attempt.fail(&conn)?;
+                    Err(Error::new(StatusCode::UNAUTHORIZED, "Unauthorized"))
+                }
+            }
+        })
+        .await?
+}
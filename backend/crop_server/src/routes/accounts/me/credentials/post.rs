@@ -0,0 +1,64 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::account::{Account as _, AccountRepository, AlreadyLinked, Authenticated};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqBody {
+    email: String,
+    pass: String,
+}
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("accounts" / "me" / "credentials")
+        .and(warp::filters::method::post())
+        .and(auth::account())
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |account, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, account, body))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, account: Authenticated, body: ReqBody) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            if conn.is_credentials_linked(account.id())? {
+                return Err(Error::new(
+                    StatusCode::CONFLICT,
+                    "Credentials are already linked",
+                ));
+            }
+            if conn.query_unauthenticated(body.email.as_str())?.is_some() {
+                return Err(Error::new(
+                    StatusCode::CONFLICT,
+                    "Email is already registered",
+                ));
+            }
+
+            let linked = account
+                .link_credentials(body.email.as_str(), body.pass.as_str())
+                .map_err(|e| {
+                    log::info!("Failed to link credentials : {:?}", e);
+                    if e.is::<AlreadyLinked>() {
+                        Error::new(StatusCode::CONFLICT, "Credentials are already linked")
+                    } else {
+                        Error::new(StatusCode::BAD_REQUEST, "Invalid email or password")
+                    }
+                })?;
+            conn.save(&linked)?;
+
+            Ok(response::new(StatusCode::CREATED, &"linked"))
+        })
+        .await?
+}
@@ -0,0 +1,35 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::account::{ledger::Wallet, Account as _, AccountRepository as _, Authenticated};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Serialize;
+use warp::Filter as _;
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody(Wallet);
+
+/// 自分のポイントの残高と、最近の取引を返す
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("accounts" / "me" / "wallet")
+        .and(warp::filters::method::get())
+        .and(auth::account())
+        .and_then(move |account| ctx.clone().handle_request(move |ctx| inner(ctx, account)))
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, account: Authenticated) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let wallet = conn.query_wallet(account.id())?;
+            Ok(response::new(StatusCode::OK, &ResBody(wallet)))
+        })
+        .await?
+}
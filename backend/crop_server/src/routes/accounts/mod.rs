@@ -1 +1,3 @@
+pub mod access_tokens;
+pub mod me;
 pub mod post;
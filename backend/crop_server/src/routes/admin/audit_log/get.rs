@@ -0,0 +1,65 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use chrono::{DateTime, Utc};
+use crop_domain::admin::{
+    audit::{self, Action, AuditLog},
+    model::{AdminId, Permission},
+    repository::AdminRepository as _,
+};
+use crop_domain::contest::{poll::PollId, ContestId};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqQuery {
+    admin_id: Option<AdminId>,
+    action: Option<Action>,
+    contest_id: Option<ContestId>,
+    poll_id: Option<PollId>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ResBody {
+    logs: Vec<AuditLog>,
+}
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("admin" / "audit_log")
+        .and(warp::filters::method::get())
+        .and(auth::admin_with(ctx.clone(), Permission::ViewAuditLog))
+        .and(warp::filters::query::query::<ReqQuery>())
+        .and_then(move |_admin, query| ctx.clone().handle_request(move |ctx| inner(ctx, query)))
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, query: ReqQuery) -> Result<Response, Error> {
+    let filter = audit::Filter {
+        admin_id: query.admin_id,
+        action: query.action,
+        contest_id: query.contest_id,
+        poll_id: query.poll_id,
+        since: query.since,
+        until: query.until,
+        limit: query.limit,
+    };
+
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let body = ResBody {
+                logs: conn.query_audit_log(&filter)?,
+            };
+            Ok(response::new(StatusCode::OK, &body))
+        })
+        .await?
+}
@@ -0,0 +1,157 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::account::{
+    ledger::{KeyReused, Overdrawn, Transaction, TransactionId},
+    AccountId, AccountRepository, BriefAccount,
+};
+use crop_domain::admin::{
+    audit,
+    model::{AuthenticatedAdmin, Permission},
+    repository::AdminRepository,
+};
+use crop_domain::contest::ContestId;
+use crop_infra::pg::{self, Connection};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use warp::Filter as _;
+
+/// 同じ `idempotency_key` のリクエストは一度しか記帳されないので、
+/// 失敗した場合は同じキーで再試行する。
+/// 同じキーで、アカウントや金額の異なる取引を記帳しようとすると409になる。
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ReqBody {
+    /// ポイントを付与する
+    Grant {
+        amount: i64,
+        idempotency_key: String,
+        /// Contestの賞品として付与する場合
+        contest_id: Option<ContestId>,
+        memo: Option<String>,
+    },
+    /// 残高を調整する。負の値を指定すると残高が減る。
+    Adjust {
+        amount: i64,
+        idempotency_key: String,
+        memo: String,
+    },
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ResBody {
+    /// 再試行した場合は、最初に記帳した取引のID
+    transaction_id: TransactionId,
+    /// falseの場合は、同じキーの取引が既に記帳されていた
+    posted: bool,
+    balance: i64,
+}
+
+/// アカウントのポイントを付与・調整する
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("admin" / "wallets" / AccountId / "transactions")
+        .and(warp::filters::method::post())
+        .and(auth::admin_with(ctx.clone(), Permission::ManagePoints))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |account_id, admin, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, account_id, admin, body))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    ctx: Context,
+    account_id: AccountId,
+    admin: AuthenticatedAdmin,
+    body: ReqBody,
+) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            pg::transaction(&conn, || {
+                AccountRepository::query_by_id::<BriefAccount>(&conn, &account_id)?
+                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Account not found"))?;
+                let wallet = conn.query_wallet(&account_id)?;
+
+                let invalid = |e: anyhow::Error| {
+                    log::info!("Invalid point transaction : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Invalid point transaction")
+                };
+                let (transaction_id, posted) = match body {
+                    ReqBody::Grant {
+                        amount,
+                        idempotency_key,
+                        contest_id,
+                        memo,
+                    } => {
+                        let granted = wallet
+                            .grant(&admin, amount, idempotency_key, contest_id, memo)
+                            .map_err(invalid)?;
+                        let (transaction_id, posted) = post(&conn, &granted.transaction)?;
+                        if posted {
+                            AdminRepository::save(&conn, &audit::record(&admin, &granted))?;
+                        }
+                        (transaction_id, posted)
+                    }
+                    ReqBody::Adjust {
+                        amount,
+                        idempotency_key,
+                        memo,
+                    } => {
+                        let adjusted = wallet
+                            .adjust(&admin, amount, idempotency_key, memo)
+                            .map_err(invalid)?;
+                        let (transaction_id, posted) = post(&conn, &adjusted.transaction)?;
+                        if posted {
+                            AdminRepository::save(&conn, &audit::record(&admin, &adjusted))?;
+                        }
+                        (transaction_id, posted)
+                    }
+                };
+
+                let status = if posted {
+                    StatusCode::CREATED
+                } else {
+                    StatusCode::OK
+                };
+                let res = ResBody {
+                    transaction_id,
+                    posted,
+                    balance: conn.query_wallet(&account_id)?.balance(),
+                };
+                Ok(response::new(status, &res))
+            })
+        })
+        .await?
+}
+
+/// 記帳した取引のIDと、新しく記帳したかどうかを返す。
+/// 同じキーの取引が既に記帳されていた場合は、その取引のIDを返す。
+fn post(conn: &Connection, transaction: &Transaction) -> Result<(TransactionId, bool), Error> {
+    let posted = match AccountRepository::post(conn, transaction) {
+        Err(e) if e.is::<Overdrawn>() => {
+            return Err(Error::new(StatusCode::BAD_REQUEST, "Not enough points"))
+        }
+        res => res?,
+    };
+    if posted {
+        return Ok((*transaction.id(), true));
+    }
+
+    // 同じキーで別のアカウントや金額の取引を記帳しようとしていたら、再試行ではない
+    match AccountRepository::query_posted(conn, transaction) {
+        Ok(Some(transaction_id)) => Ok((transaction_id, false)),
+        Ok(None) => Err(anyhow::anyhow!("Posted transaction is not found").into()),
+        Err(e) if e.is::<KeyReused>() => Err(Error::new(
+            StatusCode::CONFLICT,
+            "Idempotency key is already used for another transaction",
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
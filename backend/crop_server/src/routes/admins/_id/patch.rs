@@ -0,0 +1,92 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::admin::{
+    model::{Admin as _, AdminId, AuthenticatedAdmin, BriefAdmin, Permission},
+    repository::AdminRepository as _,
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqBody {
+    disabled: Option<bool>,
+    totp_required: Option<bool>,
+}
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("admins" / AdminId)
+        .and(warp::filters::method::patch())
+        .and(auth::admin_with(ctx.clone(), Permission::ManageAdmins))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |admin_id, admin, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, admin, admin_id, body))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    ctx: Context,
+    admin: AuthenticatedAdmin,
+    admin_id: AdminId,
+    body: ReqBody,
+) -> Result<Response, Error> {
+    match (body.disabled, body.totp_required) {
+        (Some(true), None) => disable_admin(ctx, admin, admin_id).await,
+        (Some(false), None) => Err(Error::new(
+            StatusCode::BAD_REQUEST,
+            "Unsupported status change",
+        )),
+        (None, Some(required)) => require_totp(ctx, admin_id, required).await,
+        _ => Err(Error::new(StatusCode::BAD_REQUEST, "Invalid body format")),
+    }
+}
+
+async fn disable_admin(
+    ctx: Context,
+    admin: AuthenticatedAdmin,
+    admin_id: AdminId,
+) -> Result<Response, Error> {
+    if *admin.id() == admin_id {
+        return Err(Error::new(
+            StatusCode::BAD_REQUEST,
+            "You can't disable yourself",
+        ));
+    }
+
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let target = conn
+                .query_by_id::<BriefAdmin>(&admin_id)?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Admin not found"))?;
+            let disabled = target.disable().map_err(|e| {
+                log::info!("Failed to disable admin : {:?}", e);
+                Error::new(StatusCode::BAD_REQUEST, "Failed to disable admin")
+            })?;
+            conn.save(&disabled)?;
+
+            Ok(response::new(StatusCode::OK, &"disabled"))
+        })
+        .await?
+}
+
+async fn require_totp(ctx: Context, admin_id: AdminId, required: bool) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let target = conn
+                .query_by_id::<BriefAdmin>(&admin_id)?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Admin not found"))?;
+            conn.save(&target.require_totp(required))?;
+
+            Ok(response::new(StatusCode::OK, &"updated"))
+        })
+        .await?
+}
@@ -0,0 +1,40 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::admin::{
+    model::{BriefAdmin, Permission},
+    repository::AdminRepository as _,
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Serialize;
+use warp::Filter as _;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ResBody {
+    admins: Vec<BriefAdmin>,
+}
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("admins")
+        .and(warp::filters::method::get())
+        .and(auth::admin_with(ctx.clone(), Permission::ManageAdmins))
+        .and_then(move |_admin| ctx.clone().handle_request(inner))
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(|conn| {
+            let body = ResBody {
+                admins: conn.query_all()?,
+            };
+            Ok(response::new(StatusCode::OK, &body))
+        })
+        .await?
+}
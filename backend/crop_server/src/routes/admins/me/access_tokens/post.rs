@@ -1,18 +1,30 @@
 use crate::{
     context::Context,
     error::Error,
+    filters::client,
     response::{self, Response},
 };
-use crop_domain::admin::{self, model::Admin as _};
+use crop_domain::admin::{
+    self, audit,
+    model::Admin as _,
+    repository::{AdminRepository as _, TotpCodeRequired, Unauthenticated},
+    service::auth::reject_credentials,
+    throttle,
+};
+use crop_infra::pg;
 use http::StatusCode;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 use warp::Filter as _;
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ReqBody {
     email: String,
     pass: String,
+    /// TOTPが有効なAdminの場合に必要。
+    /// バックアップコードも受け付ける。
+    totp_code: Option<String>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -23,30 +35,54 @@ pub struct ResBody {
 pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
     warp::path!("admins" / "me" / "access_tokens")
         .and(warp::filters::method::post())
+        .and(client::ip(ctx.clone()))
         .and(warp::filters::body::json::<ReqBody>())
-        .and_then(move |body| ctx.clone().handle_request(move |ctx| inner(ctx, body)))
+        .and_then(move |ip, body| ctx.clone().handle_request(move |ctx| inner(ctx, ip, body)))
         .recover(Error::recover)
         .unify()
         .boxed()
 }
 
-async fn inner(ctx: Context, body: ReqBody) -> Result<Response, Error> {
+async fn inner(ctx: Context, ip: Option<IpAddr>, body: ReqBody) -> Result<Response, Error> {
     ctx.pg
         .with_conn::<Result<Response, Error>, _>(move |conn| {
-            admin::repository::query_unauthenticated(&conn, body.email.as_str())?
-                .ok_or_else(|| {
-                    log::info!("admin not found");
-                    Error::new(StatusCode::UNAUTHORIZED, "Unauthorized")
-                })?
-                .authenticate(body.pass.as_str())
-                .map_err(|_| {
-                    log::info!("failed to auth admin");
-                    Error::new(StatusCode::UNAUTHORIZED, "Unauthorized")
-                })
-                .map(|admin| {
+            let attempt = throttle::query_attempt(&conn, body.email.as_str(), ip.as_ref())?;
+            attempt.check().map_err(|e| {
+                log::info!("admin login is throttled : {:?}", e);
+                Error::new(StatusCode::TOO_MANY_REQUESTS, "Too many failed attempts")
+            })?;
+
+            let unauthenticated =
+                admin::repository::query_unauthenticated(&conn, body.email.as_str())?;
+            let authenticated = match unauthenticated.as_ref() {
+                Some(u) => u.authenticate(&conn, body.pass.as_str(), body.totp_code.as_deref()),
+                // 存在しないemailでもPBKDF2を実行し、応答時間の差をなくす
+                None => Err(reject_credentials(body.pass.as_str())),
+            };
+
+            match authenticated {
+                Ok(admin) => {
+                    conn.save(&attempt.succeed())?;
                     let access_token = admin.gen_access_token().encode();
-                    response::new(StatusCode::CREATED, &ResBody { access_token })
-                })
+                    Ok(response::new(
+                        StatusCode::CREATED,
+                        &ResBody { access_token },
+                    ))
+                }
+                // パスワードは正しいので、失敗として数えない
+                Err(e) if e.is::<TotpCodeRequired>() => {
+                    Err(Error::new(StatusCode::UNAUTHORIZED, "TOTP code required"))
+                }
+                Err(e) => {
+                    log::info!("failed to auth admin : {:?}", e);
+                    let admin_id = unauthenticated.as_ref().map(Unauthenticated::admin_id);
+                    pg::transaction(&conn, || {
+                        let failed = attempt.fail(&conn)?;
+                        conn.save(&audit::record_by(admin_id.as_ref(), &failed))
+                    })?;
+                    Err(Error::new(StatusCode::UNAUTHORIZED, "Unauthorized"))
+                }
+            }
         })
         .await?
 }
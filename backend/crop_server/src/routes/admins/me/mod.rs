@@ -1 +1,2 @@
 pub mod access_tokens;
+pub mod totp;
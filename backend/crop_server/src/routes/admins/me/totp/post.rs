@@ -0,0 +1,54 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::admin::{
+    model::{Admin as _, AuthenticatedAdmin, TotpAdmin},
+    repository::AdminRepository as _,
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Serialize;
+use warp::Filter as _;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ResBody {
+    /// 認証アプリに手入力するためのBASE32文字列
+    secret: String,
+    otpauth_uri: String,
+}
+
+/// TOTPの登録を開始する。
+/// TOTPが必須だが未登録のAdminも使えるように、Scopeは問わない。
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("admins" / "me" / "totp")
+        .and(warp::filters::method::post())
+        .and(auth::admin(ctx.clone()))
+        .and_then(move |admin| ctx.clone().handle_request(move |ctx| inner(ctx, admin)))
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, admin: AuthenticatedAdmin) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let admin = conn
+                .query_by_id::<TotpAdmin>(admin.id())?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Admin not found"))?;
+            let enrolling = admin.start_totp_enrollment().map_err(|e| {
+                log::info!("Failed to start TOTP enrollment : {:?}", e);
+                Error::new(StatusCode::CONFLICT, "TOTP is already enabled")
+            })?;
+            conn.save(&enrolling)?;
+
+            let body = ResBody {
+                secret: enrolling.secret(),
+                otpauth_uri: enrolling.otpauth_uri(),
+            };
+            Ok(response::new(StatusCode::CREATED, &body))
+        })
+        .await?
+}
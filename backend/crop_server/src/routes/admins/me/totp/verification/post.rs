@@ -0,0 +1,74 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::admin::{
+    model::{Admin as _, AuthenticatedAdmin, TotpAdmin},
+    repository::AdminRepository as _,
+    throttle,
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqBody {
+    code: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ResBody {
+    /// この時にしか取得できないので、Adminに保管してもらう
+    backup_codes: Vec<String>,
+    /// TOTPの登録が必須なAdminは、このAccessTokenから全ての操作が行える
+    access_token: String,
+}
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("admins" / "me" / "totp" / "verification")
+        .and(warp::filters::method::post())
+        .and(auth::admin(ctx.clone()))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |admin, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, admin, body))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, admin: AuthenticatedAdmin, body: ReqBody) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let attempt = throttle::query_totp_attempt(&conn, admin.id())?;
+            attempt.check().map_err(|e| {
+                log::info!("TOTP verification is throttled : {:?}", e);
+                Error::new(StatusCode::TOO_MANY_REQUESTS, "Too many failed attempts")
+            })?;
+
+            let admin = conn
+                .query_by_id::<TotpAdmin>(admin.id())?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Admin not found"))?;
+            let enabled = match admin.enable_totp(body.code.as_str()) {
+                Ok(enabled) => enabled,
+                Err(e) => {
+                    log::info!("Failed to enable TOTP : {:?}", e);
+                    attempt.fail(&conn)?;
+                    return Err(Error::new(StatusCode::BAD_REQUEST, "Failed to enable TOTP"));
+                }
+            };
+            conn.save(&enabled)?;
+            conn.save(&attempt.succeed())?;
+
+            let body = ResBody {
+                backup_codes: enabled.backup_codes().to_vec(),
+                access_token: enabled.gen_access_token().encode(),
+            };
+            Ok(response::new(StatusCode::OK, &body))
+        })
+        .await?
+}
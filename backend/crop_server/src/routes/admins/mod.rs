@@ -1 +1,4 @@
+pub mod _id;
+pub mod get;
 pub mod me;
+pub mod post;
@@ -0,0 +1,62 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::admin::{
+    self,
+    model::{Admin as _, AdminId, Permission, Role},
+    repository::AdminRepository as _,
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqBody {
+    email: String,
+    pass: String,
+    role: Role,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody(AdminId);
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("admins")
+        .and(warp::filters::method::post())
+        .and(auth::admin_with(ctx.clone(), Permission::ManageAdmins))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |_admin, body| ctx.clone().handle_request(move |ctx| inner(ctx, body)))
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, body: ReqBody) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            if admin::repository::query_unauthenticated(&conn, body.email.as_str())?.is_some() {
+                return Err(Error::new(
+                    StatusCode::CONFLICT,
+                    "Email is already registered",
+                ));
+            }
+
+            let new_admin =
+                admin::model::new(body.email, body.pass.as_str(), body.role).map_err(|e| {
+                    log::info!("Failed to create admin : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Invalid email or password")
+                })?;
+            conn.save(&new_admin)?;
+
+            Ok(response::new(
+                StatusCode::CREATED,
+                &ResBody(*new_admin.id()),
+            ))
+        })
+        .await?
+}
@@ -0,0 +1,51 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::admin::model::Permission;
+use crop_domain::contest::{
+    draft::{DraftId, PollDraft},
+    ContestId, ContestRepository,
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Serialize;
+use warp::Filter as _;
+
+/// 削除後のキュー全体
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody(Vec<PollDraft>);
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "drafts" / DraftId)
+        .and(warp::filters::method::delete())
+        .and(auth::admin_with(ctx.clone(), Permission::ManageDrafts))
+        .and_then(move |contest_id, draft_id, _admin| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id, draft_id))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, contest_id: ContestId, draft_id: DraftId) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let removed = conn
+                .query_drafts(&contest_id)?
+                .remove(&draft_id)
+                .map_err(|_| Error::new(StatusCode::NOT_FOUND, "Draft not found"))?;
+            ContestRepository::save(&conn, &removed)?;
+
+            let queue = conn.query_drafts(&contest_id)?;
+            Ok(response::new(
+                StatusCode::OK,
+                &ResBody(queue.drafts().to_vec()),
+            ))
+        })
+        .await?
+}
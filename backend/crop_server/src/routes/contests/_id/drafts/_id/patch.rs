@@ -0,0 +1,92 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use chrono::Duration;
+use crop_domain::admin::model::Permission;
+use crop_domain::contest::{
+    draft::{DraftId, PollDraft},
+    poll::Choice,
+    ContestId, ContestRepository,
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use warp::Filter as _;
+
+/// `content` と `position` のどちらか一方を指定する
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqBody {
+    /// 下書きの内容を置き換える
+    content: Option<DraftContent>,
+    /// 下書きをこの位置（0始まり）に移動する
+    position: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DraftContent {
+    title: String,
+    duration_sec: Option<i32>,
+    choices: Vec<Choice>,
+}
+
+/// 変更後のキュー全体
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody(Vec<PollDraft>);
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "drafts" / DraftId)
+        .and(warp::filters::method::patch())
+        .and(auth::admin_with(ctx.clone(), Permission::ManageDrafts))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |contest_id, draft_id, _admin, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id, draft_id, body))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    ctx: Context,
+    contest_id: ContestId,
+    draft_id: DraftId,
+    body: ReqBody,
+) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let queue = conn.query_drafts(&contest_id)?;
+            if !queue.drafts().iter().any(|d| *d.id() == draft_id) {
+                return Err(Error::new(StatusCode::NOT_FOUND, "Draft not found"));
+            }
+
+            match (body.content, body.position) {
+                (Some(content), None) => {
+                    let duration = content.duration_sec.map(|s| Duration::seconds(s as i64));
+                    let edited = queue
+                        .edit(&draft_id, content.title, duration, content.choices)
+                        .map_err(|e| {
+                            log::info!("invalid draft : {:?}", e);
+                            Error::new(StatusCode::BAD_REQUEST, "Invalid draft")
+                        })?;
+                    ContestRepository::save(&conn, &edited)?;
+                }
+                (None, Some(position)) => {
+                    let reordered = queue.move_to(&draft_id, position)?;
+                    ContestRepository::save(&conn, &reordered)?;
+                }
+                _ => return Err(Error::new(StatusCode::BAD_REQUEST, "Invalid body format")),
+            }
+
+            let queue = conn.query_drafts(&contest_id)?;
+            Ok(response::new(
+                StatusCode::OK,
+                &ResBody(queue.drafts().to_vec()),
+            ))
+        })
+        .await?
+}
@@ -0,0 +1,42 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::admin::model::Permission;
+use crop_domain::contest::{draft::PollDraft, ContestId, ContestRepository as _};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Serialize;
+use warp::Filter as _;
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody(Vec<PollDraft>);
+
+/// まだ公開されていないPollの下書きを、公開する順に返す
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "drafts")
+        .and(warp::filters::method::get())
+        .and(auth::admin_with(ctx.clone(), Permission::ManageDrafts))
+        .and_then(move |contest_id, _admin| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, contest_id: ContestId) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let queue = conn.query_drafts(&contest_id)?;
+            Ok(response::new(
+                StatusCode::OK,
+                &ResBody(queue.drafts().to_vec()),
+            ))
+        })
+        .await?
+}
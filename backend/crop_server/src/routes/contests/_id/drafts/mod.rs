@@ -0,0 +1,3 @@
+pub mod _id;
+pub mod get;
+pub mod post;
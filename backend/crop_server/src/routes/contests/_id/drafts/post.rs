@@ -0,0 +1,74 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use chrono::Duration;
+use crop_domain::admin::model::Permission;
+use crop_domain::contest::{
+    draft::DraftId, poll::Choice, BriefContest, Contest as _, ContestId, ContestRepository,
+    ContestStatus,
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqBody {
+    title: String,
+    duration_sec: Option<i32>,
+    choices: Vec<Choice>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody(DraftId);
+
+/// 下書きをキューの末尾に追加する
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "drafts")
+        .and(warp::filters::method::post())
+        .and(auth::admin_with(ctx.clone(), Permission::ManageDrafts))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |contest_id, _admin, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id, body))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, contest_id: ContestId, body: ReqBody) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let contest = ContestRepository::query_by_id::<BriefContest>(&conn, &contest_id)?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+            if matches!(
+                contest.status(),
+                ContestStatus::Closed | ContestStatus::Archived
+            ) {
+                return Err(Error::new(
+                    StatusCode::BAD_REQUEST,
+                    "Contest is already closed",
+                ));
+            }
+
+            let duration = body.duration_sec.map(|s| Duration::seconds(s as i64));
+            let pushed = conn
+                .query_drafts(&contest_id)?
+                .push(body.title, duration, body.choices)
+                .map_err(|e| {
+                    log::info!("invalid draft : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Invalid draft")
+                })?;
+            ContestRepository::save(&conn, &pushed)?;
+            Ok(response::new(
+                StatusCode::CREATED,
+                &ResBody(*pushed.draft.id()),
+            ))
+        })
+        .await?
+}
@@ -0,0 +1,71 @@
+use crate::{context::Context, error::Error, filters::auth, response};
+use crop_domain::admin::model::Permission;
+use crop_domain::contest::{
+    export::{Export, Format, NotFinished},
+    ContestId, ContestRepository as _,
+};
+use futures::TryFutureExt as _;
+use http::{header, StatusCode};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use warp::{reject::Rejection, reply::Reply as _, Filter as _};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqQuery {
+    /// 省略した場合はjson
+    format: Option<Format>,
+}
+
+/// `format=json` の場合のレスポンス。
+/// `format=csv` の場合は、Pollに対する回答ごとに1行のCSVを返す。
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody(Export);
+
+/// CSVはJSONではないので、他のrouteと違い `warp::reply::Response` を返す
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(warp::reply::Response,)> {
+    warp::path!("contests" / ContestId / "export")
+        .and(warp::filters::method::get())
+        .and(auth::admin_with(ctx.clone(), Permission::ExportContest))
+        .and(warp::filters::query::query::<ReqQuery>())
+        .and_then(move |contest_id, _admin, query| {
+            inner(ctx.clone(), contest_id, query).err_into::<Rejection>()
+        })
+        .recover(|reject| Error::recover(reject).map_ok(|res| res.into_response()))
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    ctx: Context,
+    contest_id: ContestId,
+    query: ReqQuery,
+) -> Result<warp::reply::Response, Error> {
+    let export = ctx
+        .pg
+        .with_conn::<Result<Export, Error>, _>(move |conn| match conn.query_export(&contest_id) {
+            Ok(export) => {
+                export.ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))
+            }
+            Err(e) if e.is::<NotFinished>() => Err(Error::new(
+                StatusCode::BAD_REQUEST,
+                "Contest is not finished",
+            )),
+            Err(e) => Err(Error::from(e)),
+        })
+        .await??;
+
+    match query.format.unwrap_or_default() {
+        Format::Json => Ok(response::new(StatusCode::OK, &ResBody(export)).into_response()),
+        Format::Csv => {
+            let csv = export.to_csv()?;
+            let disposition = format!("attachment; filename=\"contest-{}.csv\"", contest_id.0);
+            let reply =
+                warp::reply::with_header(csv, header::CONTENT_TYPE, "text/csv; charset=utf-8");
+            Ok(
+                warp::reply::with_header(reply, header::CONTENT_DISPOSITION, disposition)
+                    .into_response(),
+            )
+        }
+    }
+}
@@ -1,3 +1,7 @@
+pub mod drafts;
+pub mod export;
 pub mod get;
 pub mod patch;
 pub mod polls;
+pub mod rewards;
+pub mod sync;
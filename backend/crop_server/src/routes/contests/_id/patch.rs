@@ -4,10 +4,16 @@ use crate::{
     filters::auth,
     response::{self, Response},
 };
+use crop_domain::admin::{
+    audit,
+    model::{AuthenticatedAdmin, Permission},
+    repository::AdminRepository,
+};
 use crop_domain::contest::poll::BriefPoll;
 use crop_domain::contest::{
     BriefContest, Contest, ContestId, ContestRepository, ContestStatus, DetailedContest,
 };
+use crop_infra::pg;
 use http::StatusCode;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -21,21 +27,36 @@ pub struct ReqBody {
 pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
     warp::path!("contests" / ContestId)
         .and(warp::filters::method::patch())
-        .and(auth::admin())
+        .and(auth::admin(ctx.clone()))
         .and(warp::filters::body::json::<ReqBody>())
-        .and_then(move |contest_id, _admin, body| {
+        .and_then(move |contest_id, admin, body| {
             ctx.clone()
-                .handle_request(move |ctx| inner(ctx, body, contest_id))
+                .handle_request(move |ctx| inner(ctx, admin, body, contest_id))
         })
         .recover(Error::recover)
         .unify()
         .boxed()
 }
 
-async fn inner(ctx: Context, body: ReqBody, contest_id: ContestId) -> Result<Response, Error> {
+async fn inner(
+    ctx: Context,
+    admin: AuthenticatedAdmin,
+    body: ReqBody,
+    contest_id: ContestId,
+) -> Result<Response, Error> {
     match body.status {
-        ContestStatus::Closed => close_contest(ctx, contest_id).await,
-        ContestStatus::Archived => archive_contest(ctx, contest_id).await,
+        ContestStatus::Open => {
+            auth::require(&admin, Permission::OpenContest)?;
+            open_contest(ctx, admin, contest_id).await
+        }
+        ContestStatus::Closed => {
+            auth::require(&admin, Permission::CloseContest)?;
+            close_contest(ctx, admin, contest_id).await
+        }
+        ContestStatus::Archived => {
+            auth::require(&admin, Permission::ArchiveContest)?;
+            archive_contest(ctx, admin, contest_id).await
+        }
         _ => Err(Error::new(
             StatusCode::BAD_REQUEST,
             "Unsupported status change",
@@ -43,37 +64,79 @@ async fn inner(ctx: Context, body: ReqBody, contest_id: ContestId) -> Result<Res
     }
 }
 
-async fn close_contest(ctx: Context, contest_id: ContestId) -> Result<Response, Error> {
+async fn open_contest(
+    ctx: Context,
+    admin: AuthenticatedAdmin,
+    contest_id: ContestId,
+) -> Result<Response, Error> {
     ctx.pg
         .with_conn::<Result<Response, Error>, _>(move |conn| {
-            let contest =
-                ContestRepository::query_by_id::<DetailedContest<BriefPoll>>(&conn, &contest_id)?
-                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
-            let closed = contest.close().map_err(|e| {
-                log::info!("Failed to close contest because of {:?}", e);
-                Error::new(StatusCode::BAD_REQUEST, "Failed to close contest")
-            })?;
+            pg::transaction(&conn, || {
+                let contest =
+                    ContestRepository::query_by_id::<BriefContest>(&conn, &contest_id)?
+                        .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                let opened = contest.open().map_err(|e| {
+                    log::info!("Failed to open contest because of {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Failed to open contest")
+                })?;
 
-            ContestRepository::save(&conn, &closed)?;
+                ContestRepository::save(&conn, &opened)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &opened))?;
 
-            Ok(response::new(StatusCode::OK, &"closed"))
+                Ok(response::new(StatusCode::OK, &"opened"))
+            })
         })
         .await?
 }
 
-async fn archive_contest(ctx: Context, contest_id: ContestId) -> Result<Response, Error> {
+async fn close_contest(
+    ctx: Context,
+    admin: AuthenticatedAdmin,
+    contest_id: ContestId,
+) -> Result<Response, Error> {
     ctx.pg
         .with_conn::<Result<Response, Error>, _>(move |conn| {
-            let contest = ContestRepository::query_by_id::<BriefContest>(&conn, &contest_id)?
+            pg::transaction(&conn, || {
+                let contest = ContestRepository::query_by_id::<DetailedContest<BriefPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
                 .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
-            let archived = contest.archive().map_err(|e| {
-                log::info!("Failed to close contest because of {:?}", e);
-                Error::new(StatusCode::BAD_REQUEST, "Failed to close contest")
-            })?;
+                let closed = contest.close().map_err(|e| {
+                    log::info!("Failed to close contest because of {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Failed to close contest")
+                })?;
+
+                ContestRepository::save(&conn, &closed)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &closed))?;
+
+                Ok(response::new(StatusCode::OK, &"closed"))
+            })
+        })
+        .await?
+}
+
+async fn archive_contest(
+    ctx: Context,
+    admin: AuthenticatedAdmin,
+    contest_id: ContestId,
+) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            pg::transaction(&conn, || {
+                let contest =
+                    ContestRepository::query_by_id::<BriefContest>(&conn, &contest_id)?
+                        .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                let archived = contest.archive().map_err(|e| {
+                    log::info!("Failed to close contest because of {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Failed to close contest")
+                })?;
 
-            ContestRepository::save(&conn, &archived)?;
+                ContestRepository::save(&conn, &archived)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &archived))?;
 
-            Ok(response::new(StatusCode::OK, &"closed"))
+                Ok(response::new(StatusCode::OK, &"closed"))
+            })
         })
         .await?
 }
@@ -0,0 +1,76 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::account::{Account as _, Authenticated};
+use crop_domain::contest::poll::{market::Position, BriefPoll, ChoiceName, Poll as _, PollId};
+use crop_domain::contest::{Contest as _, ContestId, ContestRepository as _, DetailedContest};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use warp::Filter as _;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ResBody {
+    liquidity: f64,
+    budget: f64,
+    /// 選択肢ごとの価格。その選択肢が正解になる確率の推定値で、合計は1になる
+    prices: HashMap<ChoiceName, f64>,
+    /// 自分の持ち分
+    position: Position,
+    /// PollがResolveまたは中止された後の、自分への払い戻し
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payout: Option<i64>,
+}
+
+/// Pollに開かれたマーケットの、現在の価格と自分の持ち分を返す
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "polls" / PollId / "market")
+        .and(warp::filters::method::get())
+        .and(auth::account())
+        .and_then(move |contest_id, poll_id, account| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id, poll_id, account))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    ctx: Context,
+    contest_id: ContestId,
+    poll_id: PollId,
+    account: Authenticated,
+) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            let contest = conn
+                .query_by_id::<DetailedContest<BriefPoll>>(&contest_id)?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+            let poll = contest
+                .polls()
+                .iter()
+                .find(|poll| *poll.id() == poll_id)
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Poll not found"))?;
+            let market = conn
+                .query_market(&poll_id)?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Market not found"))?;
+
+            let payout = market
+                .settlement(poll)
+                .map(|payouts| payouts.get(account.id()).copied().unwrap_or(0));
+            let res = ResBody {
+                liquidity: market.liquidity(),
+                budget: market.budget(),
+                prices: market.prices(),
+                position: market.position(account.id()),
+                payout,
+            };
+            Ok(response::new(StatusCode::OK, &res))
+        })
+        .await?
+}
@@ -0,0 +1,90 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+    routes::ws::contests::_id::MarketMsgSource,
+};
+use crop_domain::admin::{
+    audit,
+    model::{AuthenticatedAdmin, Permission},
+    repository::AdminRepository,
+};
+use crop_domain::contest::poll::{market, BriefPoll, Poll as _, PollId};
+use crop_domain::contest::{Contest as _, ContestId, ContestRepository, DetailedContest};
+use crop_infra::pg;
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqBody {
+    /// LMSRの流動性。大きいほど価格が動きにくい
+    liquidity: f64,
+    /// 1アカウントが使えるポイントの上限
+    budget: f64,
+}
+
+/// 現在のPollに、選択肢を取引するマーケットを開く
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "polls" / PollId / "market")
+        .and(warp::filters::method::post())
+        .and(auth::admin_with(ctx.clone(), Permission::OpenMarket))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |contest_id, poll_id, admin, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id, poll_id, admin, body))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    ctx: Context,
+    contest_id: ContestId,
+    poll_id: PollId,
+    admin: AuthenticatedAdmin,
+    body: ReqBody,
+) -> Result<Response, Error> {
+    let msg_source = ctx
+        .pg
+        .with_conn::<Result<MarketMsgSource, Error>, _>(move |conn| {
+            pg::transaction(&conn, || {
+                let contest = ContestRepository::query_by_id::<DetailedContest<BriefPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                let poll = contest
+                    .current_poll()
+                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
+                if *poll.id() != poll_id {
+                    return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
+                }
+                if conn.query_market(&poll_id)?.is_some() {
+                    return Err(Error::new(
+                        StatusCode::BAD_REQUEST,
+                        "Market is already opened",
+                    ));
+                }
+
+                let opened = market::open(poll, body.liquidity, body.budget).map_err(|e| {
+                    log::info!("Failed to open market : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Failed to open market")
+                })?;
+                ContestRepository::save(&conn, &opened)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &opened).contest(&contest_id))?;
+
+                Ok(MarketMsgSource::from(&opened.market))
+            })
+        })
+        .await??;
+
+    ctx.contest_manager
+        .broadcast_msg(contest_id, msg_source)
+        .await;
+
+    Ok(response::new(StatusCode::CREATED, &"opened"))
+}
@@ -0,0 +1,115 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+    routes::ws::contests::_id::MarketMsgSource,
+};
+use crop_domain::account::{ledger::Overdrawn, Account as _, Authenticated};
+use crop_domain::contest::poll::{
+    market::{Conflict, Position},
+    AnswerLocked, BriefPoll, ChoiceName, NotOpen, Poll as _, PollId,
+};
+use crop_domain::contest::{Contest as _, ContestId, ContestRepository as _, DetailedContest};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReqBody {
+    choice: ChoiceName,
+    /// 支払うポイント。残高から引き落とす
+    amount: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ResBody {
+    /// この取引で得た株数
+    shares: f64,
+    /// 取引した後の自分の持ち分
+    position: Position,
+}
+
+/// 現在のPollのマーケットで、選択肢の株を買う
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "polls" / PollId / "market" / "trades")
+        .and(warp::filters::method::post())
+        .and(auth::account())
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |contest_id, poll_id, account, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id, poll_id, account, body))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    ctx: Context,
+    contest_id: ContestId,
+    poll_id: PollId,
+    account: Authenticated,
+    body: ReqBody,
+) -> Result<Response, Error> {
+    let (res, msg_source) = ctx
+        .pg
+        .with_conn::<Result<(ResBody, MarketMsgSource), Error>, _>(move |conn| {
+            let contest = conn
+                .query_by_id::<DetailedContest<BriefPoll>>(&contest_id)?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+            let poll = contest
+                .current_poll()
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
+            if *poll.id() != poll_id {
+                return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
+            }
+            let market = conn
+                .query_market(&poll_id)?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Market not found"))?;
+
+            let traded = market
+                .trade(poll, &account, body.choice, body.amount)
+                .map_err(|e| {
+                    log::info!("Failed to trade : {:?}", e);
+                    trade_error(e)
+                })?;
+            match conn.save(&traded) {
+                Err(e) if e.is::<AnswerLocked>() || e.is::<NotOpen>() => {
+                    return Err(trade_error(e))
+                }
+                Err(e) if e.is::<Overdrawn>() => {
+                    return Err(Error::new(StatusCode::BAD_REQUEST, "Not enough points"))
+                }
+                Err(e) if e.is::<Conflict>() => {
+                    return Err(Error::new(
+                        StatusCode::CONFLICT,
+                        "Market was updated by another trade",
+                    ))
+                }
+                res => res?,
+            }
+
+            let res = ResBody {
+                shares: traded.trade.shares(),
+                position: traded.market.position(account.id()),
+            };
+            Ok((res, MarketMsgSource::from(&traded.market)))
+        })
+        .await??;
+
+    ctx.contest_manager
+        .broadcast_msg(contest_id, msg_source)
+        .await;
+
+    Ok(response::new(StatusCode::CREATED, &res))
+}
+
+fn trade_error(e: anyhow::Error) -> Error {
+    if e.is::<AnswerLocked>() {
+        Error::new(StatusCode::FORBIDDEN, "Answer is locked")
+    } else {
+        Error::new(StatusCode::BAD_REQUEST, "Failed to trade")
+    }
+}
@@ -1,3 +1,5 @@
 pub mod comments;
+pub mod market;
 pub mod my_choice;
 pub mod patch;
+pub mod timer;
@@ -4,8 +4,11 @@ use crate::{
     filters::auth,
     response::{self, Response},
 };
-use crop_domain::account::Authenticated;
-use crop_domain::contest::poll::{BriefPoll, ChoiceName, Poll, PollId};
+use crop_domain::account::{
+    ledger::{Conflict, Overdrawn},
+    Authenticated,
+};
+use crop_domain::contest::poll::{AnswerLocked, BriefPoll, ChoiceName, NotOpen, Poll, PollId};
 use crop_domain::contest::{Contest, ContestId, ContestRepository as _, DetailedContest};
 use http::StatusCode;
 use schemars::JsonSchema;
@@ -14,7 +17,20 @@ use warp::Filter as _;
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ReqBody {
-    choice: ChoiceName,
+    /// 単一選択のPollへの回答
+    choice: Option<ChoiceName>,
+    /// 複数選択のPollへの回答。順位を予想するPollでは、上位から順に並べる
+    choices: Option<Vec<ChoiceName>>,
+    /// 数値で答えるPollへの回答
+    value: Option<f64>,
+    /// 選択肢の回答に賭けるポイント。賭けられるPollでのみ指定できる。
+    /// 省略した場合は、賭けていたポイントが残高に戻る
+    stake: Option<i64>,
+}
+
+enum Answer {
+    Choices(Vec<ChoiceName>, Option<i64>),
+    Value(f64),
 }
 
 pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
@@ -38,6 +54,12 @@ async fn inner(
     body: ReqBody,
     ctx: Context,
 ) -> Result<Response, Error> {
+    let answer = match (body.choice, body.choices, body.value, body.stake) {
+        (Some(choice), None, None, stake) => Answer::Choices(vec![choice], stake),
+        (None, Some(choices), None, stake) => Answer::Choices(choices, stake),
+        (None, None, Some(value), None) => Answer::Value(value),
+        _ => return Err(Error::new(StatusCode::BAD_REQUEST, "Invalid body format")),
+    };
     ctx.pg
         .with_conn::<Result<Response, Error>, _>(move |conn| {
             let contest = conn
@@ -46,18 +68,57 @@ async fn inner(
             let poll = contest
                 .current_poll()
                 .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
-            if *poll.id() == poll_id {
-                let updated = poll
-                    .update_account_choice(&account, body.choice)
-                    .map_err(|e| {
-                        log::info!("Failed to update account choice : {:?}", e);
-                        Error::new(StatusCode::BAD_REQUEST, "Failed to update")
+            if *poll.id() != poll_id {
+                return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
+            }
+
+            match answer {
+                Answer::Choices(choices, stake) => {
+                    let updated = poll
+                        .update_account_choice(&account, choices, stake)
+                        .map_err(|e| {
+                            log::info!("Failed to update account choice : {:?}", e);
+                            update_error(e)
+                        })?;
+                    match conn.save(&updated) {
+                        Err(e) if e.is::<AnswerLocked>() || e.is::<NotOpen>() => {
+                            return Err(update_error(e))
+                        }
+                        Err(e) if e.is::<Overdrawn>() => {
+                            return Err(Error::new(StatusCode::BAD_REQUEST, "Not enough points"))
+                        }
+                        Err(e) if e.is::<Conflict>() => {
+                            return Err(Error::new(
+                                StatusCode::CONFLICT,
+                                "Stake was updated by another request",
+                            ))
+                        }
+                        res => res?,
+                    }
+                }
+                Answer::Value(value) => {
+                    let updated = poll.update_account_value(&account, value).map_err(|e| {
+                        log::info!("Failed to update account value : {:?}", e);
+                        update_error(e)
                     })?;
-                conn.save(&updated)?;
-                Ok(response::new(StatusCode::OK, &"updated"))
-            } else {
-                Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"))
+                    match conn.save(&updated) {
+                        Err(e) if e.is::<AnswerLocked>() || e.is::<NotOpen>() => {
+                            return Err(update_error(e))
+                        }
+                        res => res?,
+                    }
+                }
             }
+            Ok(response::new(StatusCode::OK, &"updated"))
         })
         .await?
 }
+
+/// 締め切り直前で回答が締め切られている場合は、他の失敗と区別できるようにする
+fn update_error(e: anyhow::Error) -> Error {
+    if e.is::<AnswerLocked>() {
+        Error::new(StatusCode::FORBIDDEN, "Answer is locked")
+    } else {
+        Error::new(StatusCode::BAD_REQUEST, "Failed to update")
+    }
+}
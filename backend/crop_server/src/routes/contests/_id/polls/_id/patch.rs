@@ -3,10 +3,20 @@ use crate::{
     error::Error,
     filters::auth,
     response::{self, Response},
-    routes::ws::contests::_id::PollMsgSource,
+    routes::{
+        contests::_id::polls::post::start_close_timer,
+        ws::contests::_id::{ClosedMsgSource, CorrectedMsgSource, PollMsgSource},
+    },
 };
-use crop_domain::contest::poll::{ChoiceName, DetailedPoll, Poll, PollId, PollStatus};
-use crop_domain::contest::{Contest, ContestId, ContestRepository as _, DetailedContest};
+use chrono::{Duration, Utc};
+use crop_domain::admin::{
+    audit,
+    model::{AuthenticatedAdmin, Permission},
+    repository::AdminRepository,
+};
+use crop_domain::contest::poll::{ChoiceName, DetailedPoll, Poll, PollId, PollStatus, Resolution};
+use crop_domain::contest::{Contest, ContestId, ContestRepository, ContestStatus, DetailedContest};
+use crop_infra::pg;
 use http::StatusCode;
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -15,17 +25,33 @@ use warp::Filter as _;
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ReqBody {
     status: Option<PollStatus>,
+    /// 単一選択のPollの正解
     resolved_choice: Option<ChoiceName>,
+    /// 複数選択のPollの正解。順位を予想するPollでは、実際の上位から順に並べる
+    resolved_choices: Option<Vec<ChoiceName>>,
+    /// 数値で答えるPollの正解
+    resolved_value: Option<f64>,
+    /// 正解のコミットメントがあるPollをResolveする時に、公開するnonce
+    answer_nonce: Option<String>,
+    /// CloseしたPollをOpenに戻す時の、今からの制限時間。
+    /// 省略した場合は、Closeした時点の残り時間になる。
+    duration_sec: Option<i64>,
+    /// trueの場合、制限時間の前でもPollをCloseする
+    #[serde(default)]
+    force: bool,
+    /// trueの場合、Resolve済みのPollの正解を `resolved_choice` 、 `resolved_choices` 、 `resolved_value` のいずれかに訂正する
+    #[serde(default)]
+    correction: bool,
 }
 
 pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
     warp::path!("contests" / ContestId / "polls" / PollId)
         .and(warp::filters::method::patch())
-        .and(auth::admin())
+        .and(auth::admin(ctx.clone()))
         .and(warp::filters::body::json::<ReqBody>())
-        .and_then(move |contest_id, poll_id, _admin, body| {
+        .and_then(move |contest_id, poll_id, admin, body| {
             ctx.clone()
-                .handle_request(move |ctx| inner(contest_id, poll_id, body, ctx))
+                .handle_request(move |ctx| inner(contest_id, poll_id, admin, body, ctx))
         })
         .recover(Error::recover)
         .unify()
@@ -35,44 +61,95 @@ pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
 async fn inner(
     contest_id: ContestId,
     poll_id: PollId,
+    admin: AuthenticatedAdmin,
     body: ReqBody,
     ctx: Context,
 ) -> Result<Response, Error> {
-    match (body.status, body.resolved_choice) {
-        (Some(PollStatus::Closed), None) => close_poll(contest_id, poll_id, ctx).await,
-        (None, Some(resolved_choice)) => {
-            resolve_poll(contest_id, poll_id, ctx, resolved_choice).await
+    let resolution = match (
+        body.resolved_choice,
+        body.resolved_choices,
+        body.resolved_value,
+    ) {
+        (Some(choice), None, None) => Some(Resolution::Choices(vec![choice])),
+        (None, Some(choices), None) => Some(Resolution::Choices(choices)),
+        (None, None, Some(value)) => Some(Resolution::Value(value)),
+        (None, None, None) => None,
+        _ => return Err(Error::new(StatusCode::BAD_REQUEST, "Invalid body format")),
+    };
+    match (body.status, resolution) {
+        (Some(PollStatus::Closed), None) => {
+            auth::require(&admin, Permission::ClosePoll)?;
+            close_poll(contest_id, poll_id, admin, ctx, body.force).await
+        }
+        (Some(PollStatus::Open), None) => {
+            auth::require(&admin, Permission::ReopenPoll)?;
+            let duration = body.duration_sec.map(Duration::seconds);
+            reopen_poll(contest_id, poll_id, admin, ctx, duration).await
+        }
+        (Some(PollStatus::Voided), None) => {
+            auth::require(&admin, Permission::VoidPoll)?;
+            void_poll(contest_id, poll_id, admin, ctx).await
+        }
+        (None, Some(resolution)) if body.correction => {
+            auth::require(&admin, Permission::ReResolvePoll)?;
+            re_resolve_poll(contest_id, poll_id, admin, ctx, resolution).await
+        }
+        (None, Some(resolution)) => {
+            auth::require(&admin, Permission::ResolvePoll)?;
+            resolve_poll(
+                contest_id,
+                poll_id,
+                admin,
+                ctx,
+                resolution,
+                body.answer_nonce,
+            )
+            .await
         }
         _ => Err(Error::new(StatusCode::BAD_REQUEST, "Invalid body format")),
     }
 }
 
+/// `force` の場合は、制限時間の前や一時停止中でもCloseする。
+/// 動いているタイマーは、目覚めた時にClose済みであれば何もしない。
 async fn close_poll(
     contest_id: ContestId,
     poll_id: PollId,
+    admin: AuthenticatedAdmin,
     ctx: Context,
+    force: bool,
 ) -> Result<Response, Error> {
     let msg_source = ctx
         .pg
         .with_conn::<Result<_, Error>, _>(move |conn| {
-            // TODO: DetailedContestである必要ない。MinumumContestでいい。
-            let contest = conn
-                .query_by_id::<DetailedContest<DetailedPoll>>(&contest_id)?
+            pg::transaction(&conn, || {
+                // TODO: DetailedContestである必要ない。MinumumContestでいい。
+                let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
                 .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
-            let poll = contest
-                .current_poll()
-                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
-            if *poll.id() != poll_id {
-                return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
-            }
-
-            let closed = poll.clone().close().map_err(|e| {
-                log::info!("Failed to close poll : {:?}", e);
-                Error::new(StatusCode::BAD_REQUEST, "Failed to close poll")
-            })?;
-            conn.save(&closed)?;
-
-            Ok(PollMsgSource::from(closed))
+                let poll = contest
+                    .current_poll()
+                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
+                if *poll.id() != poll_id {
+                    return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
+                }
+
+                let closed = if force {
+                    poll.clone().force_close()
+                } else {
+                    poll.clone().close()
+                };
+                let closed = closed.map_err(|e| {
+                    log::info!("Failed to close poll : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Failed to close poll")
+                })?;
+                ContestRepository::save(&conn, &closed)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &closed).contest(&contest_id))?;
+
+                Ok(PollMsgSource::from(closed))
+            })
         })
         .await??;
 
@@ -83,37 +160,156 @@ async fn close_poll(
     Ok(response::new(StatusCode::OK, &"resolved"))
 }
 
+/// 誤ってCloseしたPollをOpenに戻し、改めて自動Closeのタイマーを開始する
+async fn reopen_poll(
+    contest_id: ContestId,
+    poll_id: PollId,
+    admin: AuthenticatedAdmin,
+    ctx: Context,
+    duration: Option<Duration>,
+) -> Result<Response, Error> {
+    let (msg_source, closes_at) = ctx
+        .pg
+        .with_conn::<Result<_, Error>, _>(move |conn| {
+            pg::transaction(&conn, || {
+                let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                if contest.status() != ContestStatus::Open {
+                    return Err(Error::new(StatusCode::BAD_REQUEST, "Contest is not open"));
+                }
+                let poll = contest
+                    .current_poll()
+                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
+                if *poll.id() != poll_id {
+                    return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
+                }
+
+                let reopened = poll.clone().reopen(duration).map_err(|e| {
+                    log::info!("Failed to reopen poll : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Failed to reopen poll")
+                })?;
+                ContestRepository::save(&conn, &reopened)?;
+                AdminRepository::save(
+                    &conn,
+                    &audit::record(&admin, &reopened).contest(&contest_id),
+                )?;
+
+                let closes_at = reopened.closes_at();
+                Ok((PollMsgSource::from(reopened), closes_at))
+            })
+        })
+        .await??;
+
+    // Closeする前のタイマーが残っていても、新しいタイマーを開始すれば目覚めた時に終了する
+    if let Some(closes_at) = closes_at {
+        start_close_timer(ctx.clone(), closes_at - Utc::now(), contest_id, poll_id).await;
+    }
+
+    ctx.contest_manager
+        .broadcast_msg(contest_id, msg_source)
+        .await;
+
+    Ok(response::new(StatusCode::OK, &"reopened"))
+}
+
+/// 採点せずにPollを終了する。
+/// OpenなPollも中止できる。
+async fn void_poll(
+    contest_id: ContestId,
+    poll_id: PollId,
+    admin: AuthenticatedAdmin,
+    ctx: Context,
+) -> Result<Response, Error> {
+    let msg_source = ctx
+        .pg
+        .with_conn::<Result<_, Error>, _>(move |conn| {
+            pg::transaction(&conn, || {
+                let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                let poll = contest
+                    .current_poll()
+                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
+                if *poll.id() != poll_id {
+                    return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
+                }
+
+                let voided = poll.clone().void().map_err(|e| {
+                    log::info!("Failed to void poll : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Failed to void poll")
+                })?;
+                ContestRepository::save(&conn, &voided)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &voided).contest(&contest_id))?;
+
+                // 賭けられていたポイントを払い戻す
+                let settled = voided.settle_stakes()?;
+                ContestRepository::save(&conn, &settled)?;
+
+                Ok(PollMsgSource::from(settled.poll))
+            })
+        })
+        .await??;
+
+    ctx.contest_manager
+        .broadcast_msg(contest_id, msg_source)
+        .await;
+
+    Ok(response::new(StatusCode::OK, &"voided"))
+}
+
 async fn resolve_poll(
     contest_id: ContestId,
     poll_id: PollId,
+    admin: AuthenticatedAdmin,
     ctx: Context,
-    resolved_choice: ChoiceName,
+    resolution: Resolution,
+    answer_nonce: Option<String>,
 ) -> Result<Response, Error> {
     let msg_source = ctx
         .pg
         .with_conn::<Result<_, Error>, _>(move |conn| {
-            // TODO: DetailedContestである必要ない。MinumumContestでいい。
-            let contest = conn
-                .query_by_id::<DetailedContest<DetailedPoll>>(&contest_id)?
+            pg::transaction(&conn, || {
+                // TODO: DetailedContestである必要ない。MinumumContestでいい。
+                let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
                 .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
-            // TODO
-            // contest経由で、resolve_pollする
-            // その結果のPollResolvedで所有権を取ることにより、
-            // 無駄なcloneをなくす
-            let poll = contest
-                .current_poll()
-                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
-            if *poll.id() != poll_id {
-                return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
-            }
-
-            let resolved = poll.clone().resolve(resolved_choice).map_err(|e| {
-                log::info!("Failed to resolve poll : {:?}", e);
-                Error::new(StatusCode::BAD_REQUEST, "Failed to resolve poll")
-            })?;
-            conn.save(&resolved)?;
-
-            Ok(PollMsgSource::from(resolved))
+                // TODO
+                // contest経由で、resolve_pollする
+                // その結果のPollResolvedで所有権を取ることにより、
+                // 無駄なcloneをなくす
+                let poll = contest
+                    .current_poll()
+                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
+                if *poll.id() != poll_id {
+                    return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
+                }
+
+                let resolved = poll
+                    .clone()
+                    .resolve(resolution, answer_nonce)
+                    .map_err(|e| {
+                        log::info!("Failed to resolve poll : {:?}", e);
+                        Error::new(StatusCode::BAD_REQUEST, "Failed to resolve poll")
+                    })?;
+                ContestRepository::save(&conn, &resolved)?;
+                AdminRepository::save(
+                    &conn,
+                    &audit::record(&admin, &resolved).contest(&contest_id),
+                )?;
+
+                // 正解した回答に賭けられていたポイントを払い戻す
+                let settled = resolved.settle_stakes()?;
+                ContestRepository::save(&conn, &settled)?;
+
+                Ok(PollMsgSource::from(settled.poll))
+            })
         })
         .await??;
 
@@ -123,3 +319,93 @@ async fn resolve_poll(
 
     Ok(response::new(StatusCode::OK, &"resolved"))
 }
+
+/// Resolve済みのPollの正解を訂正する。
+/// 現在のPollに限らず、過去のPollも訂正できる。
+/// 過去のPollを現在のPollとして表示させないよう、過去のPollでは訂正のMsgを送る。
+/// Closeしたあとのcontestでは、再計算したスコアも送り直す。
+async fn re_resolve_poll(
+    contest_id: ContestId,
+    poll_id: PollId,
+    admin: AuthenticatedAdmin,
+    ctx: Context,
+    resolution: Resolution,
+) -> Result<Response, Error> {
+    let (poll_msg_source, corrected_msg_source, closed_msg_source) = ctx
+        .pg
+        .with_conn::<Result<_, Error>, _>(move |conn| {
+            pg::transaction(&conn, || {
+                let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                if contest.status() == ContestStatus::Archived {
+                    return Err(Error::new(
+                        StatusCode::BAD_REQUEST,
+                        "Contest is already archived",
+                    ));
+                }
+                let poll = contest
+                    .polls()
+                    .iter()
+                    .find(|poll| *poll.id() == poll_id)
+                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Poll not found"))?;
+
+                let re_resolved = poll.clone().re_resolve(&admin, resolution).map_err(|e| {
+                    log::info!("Failed to re-resolve poll : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Failed to re-resolve poll")
+                })?;
+                ContestRepository::save(&conn, &re_resolved)?;
+                AdminRepository::save(
+                    &conn,
+                    &audit::record(&admin, &re_resolved).contest(&contest_id),
+                )?;
+
+                // 訂正後のPollでスコアを計算し直す
+                let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                let is_current = contest
+                    .current_poll()
+                    .map(|current| *current.id() == poll_id)
+                    .unwrap_or(false);
+                let (poll_msg_source, corrected_msg_source) = if is_current {
+                    (Some(PollMsgSource::from(re_resolved)), None)
+                } else {
+                    (
+                        None,
+                        Some(CorrectedMsgSource::from((&re_resolved, &contest))),
+                    )
+                };
+                let closed_msg_source = if contest.status() == ContestStatus::Closed {
+                    Some(ClosedMsgSource::from(contest))
+                } else {
+                    None
+                };
+
+                Ok((poll_msg_source, corrected_msg_source, closed_msg_source))
+            })
+        })
+        .await??;
+
+    if let Some(poll_msg_source) = poll_msg_source {
+        ctx.contest_manager
+            .broadcast_msg(contest_id, poll_msg_source)
+            .await;
+    }
+    if let Some(corrected_msg_source) = corrected_msg_source {
+        ctx.contest_manager
+            .broadcast_msg(contest_id, corrected_msg_source)
+            .await;
+    }
+    if let Some(closed_msg_source) = closed_msg_source {
+        ctx.contest_manager
+            .broadcast_msg(contest_id, closed_msg_source)
+            .await;
+    }
+
+    Ok(response::new(StatusCode::OK, &"re-resolved"))
+}
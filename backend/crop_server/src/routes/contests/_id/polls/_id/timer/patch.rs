@@ -0,0 +1,138 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+    routes::{contests::_id::polls::post::start_close_timer, ws::contests::_id::PollMsgSource},
+};
+use chrono::{DateTime, Duration, Utc};
+use crop_domain::admin::{
+    audit::{self, Audited},
+    model::{AuthenticatedAdmin, Permission},
+    repository::AdminRepository,
+};
+use crop_domain::contest::poll::{self, DetailedPoll, Poll, PollId};
+use crop_domain::contest::{Contest, ContestId, ContestRepository, DetailedContest, Updatable};
+use crop_infra::pg;
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use warp::Filter as _;
+
+/// 制限時間のあるPollのタイマーを操作する
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ReqBody {
+    /// 制限時間を `sec` 秒延長する。一度に延長できるのは1日まで。
+    Extend { sec: i64 },
+    /// タイマーを一時停止する。停止中も投票は受け付ける。
+    Pause,
+    /// 一時停止していた時間だけ制限時間を延ばして、タイマーを再開する
+    Resume,
+}
+
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "polls" / PollId / "timer")
+        .and(warp::filters::method::patch())
+        .and(auth::admin_with(ctx.clone(), Permission::ControlPollTimer))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |contest_id, poll_id, admin, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(contest_id, poll_id, admin, body, ctx))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    contest_id: ContestId,
+    poll_id: PollId,
+    admin: AuthenticatedAdmin,
+    body: ReqBody,
+    ctx: Context,
+) -> Result<Response, Error> {
+    match body {
+        ReqBody::Extend { sec } => {
+            if sec <= 0 || sec > poll::MAX_EXTENSION_SEC {
+                return Err(Error::new(
+                    StatusCode::BAD_REQUEST,
+                    "Extension must be between 1 second and 1 day",
+                ));
+            }
+            // 動いているタイマーは、元の締め切りで目覚めた時に待ち直す
+            update_timer(contest_id, poll_id, admin, ctx, move |poll| {
+                poll.extend(Duration::seconds(sec))
+            })
+            .await?;
+            Ok(response::new(StatusCode::OK, &"extended"))
+        }
+        ReqBody::Pause => {
+            // 動いているタイマーは、目覚めた時に停止中であれば終了する
+            update_timer(contest_id, poll_id, admin, ctx, |poll| poll.pause()).await?;
+            Ok(response::new(StatusCode::OK, &"paused"))
+        }
+        ReqBody::Resume => {
+            let closes_at = update_timer(contest_id, poll_id, admin, ctx.clone(), |poll| {
+                poll.resume()
+            })
+            .await?;
+            if let Some(closes_at) = closes_at {
+                start_close_timer(ctx, closes_at - Utc::now(), contest_id, poll_id).await;
+            }
+            Ok(response::new(StatusCode::OK, &"resumed"))
+        }
+    }
+}
+
+/// 現在のPollのタイマーを変更して、ブロードキャストする。
+/// 変更後の締め切り日時を返す。
+async fn update_timer<F, P>(
+    contest_id: ContestId,
+    poll_id: PollId,
+    admin: AuthenticatedAdmin,
+    ctx: Context,
+    f: F,
+) -> Result<Option<DateTime<Utc>>, Error>
+where
+    F: FnOnce(DetailedPoll) -> anyhow::Result<P> + Send + 'static,
+    P: Poll + poll::WithAttrs + poll::WithUserChoices + Updatable + Audited + Send + Sync + 'static,
+{
+    let (msg_source, closes_at) = ctx
+        .pg
+        .with_conn::<Result<_, Error>, _>(move |conn| {
+            pg::transaction(&conn, || {
+                let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                let poll = contest
+                    .current_poll()
+                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest has no poll"))?;
+                if *poll.id() != poll_id {
+                    return Err(Error::new(StatusCode::NOT_FOUND, "poll id mismatch"));
+                }
+
+                let updated = f(poll.clone()).map_err(|e| {
+                    log::info!("Failed to update poll timer : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Failed to update poll timer")
+                })?;
+                ContestRepository::save(&conn, &updated)?;
+                AdminRepository::save(
+                    &conn,
+                    &audit::record(&admin, &updated).contest(&contest_id),
+                )?;
+
+                let closes_at = updated.closes_at();
+                Ok((PollMsgSource::from(updated), closes_at))
+            })
+        })
+        .await??;
+
+    ctx.contest_manager
+        .broadcast_msg(contest_id, msg_source)
+        .await;
+
+    Ok(closes_at)
+}
@@ -1,2 +1,3 @@
 pub mod _id;
+pub mod next;
 pub mod post;
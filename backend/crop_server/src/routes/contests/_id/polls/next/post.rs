@@ -0,0 +1,83 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+    routes::{contests::_id::polls::post::start_close_timer, ws::contests::_id::PollMsgSource},
+};
+use crop_domain::admin::{
+    audit,
+    model::{AuthenticatedAdmin, Permission},
+    repository::AdminRepository,
+};
+use crop_domain::contest::poll::{self, DetailedPoll, Poll, PollId};
+use crop_domain::contest::{ContestId, ContestRepository, DetailedContest};
+use crop_infra::pg;
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Serialize;
+use warp::Filter as _;
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody<'a>(&'a PollId);
+
+/// キューの先頭の下書きをPollとして公開する
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "polls" / "next")
+        .and(warp::filters::method::post())
+        .and(auth::admin_with(ctx.clone(), Permission::AddPoll))
+        .and_then(move |contest_id, admin| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id, admin))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    ctx: Context,
+    contest_id: ContestId,
+    admin: AuthenticatedAdmin,
+) -> Result<Response, Error> {
+    let poll = ctx
+        .pg
+        .with_conn::<Result<poll::New, Error>, _>(move |conn| {
+            pg::transaction(&conn, || {
+                let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
+                .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                let queue = conn.query_drafts(&contest_id)?;
+                if queue.head().is_none() {
+                    return Err(Error::new(StatusCode::NOT_FOUND, "No draft in the queue"));
+                }
+
+                let published = queue.publish_head(&contest).map_err(|e| {
+                    log::info!("failed to publish a draft : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Can not publish the draft")
+                })?;
+                ContestRepository::save(&conn, &published)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &published.added))?;
+                Ok(published.added.poll)
+            })
+        })
+        .await??;
+
+    let poll_id = *poll.id();
+
+    // 指定時間後にPollをCloseする
+    if let Some(dur) = poll.duration() {
+        start_close_timer(ctx.clone(), *dur, contest_id, poll_id).await;
+    }
+
+    // 公開したPollをブロードキャストする
+    let msg_source = PollMsgSource::from(poll);
+    ctx.contest_manager
+        .broadcast_msg(contest_id, msg_source)
+        .await;
+
+    Ok(response::new(StatusCode::CREATED, &ResBody(&poll_id)))
+}
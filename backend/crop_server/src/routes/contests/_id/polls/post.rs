@@ -5,9 +5,18 @@ use crate::{
     response::{self, Response},
     routes::ws::contests::_id::PollMsgSource,
 };
-use chrono::Duration;
-use crop_domain::contest::poll::{self, Choice, DetailedPoll, Poll, PollId};
-use crop_domain::contest::{Contest, ContestId, ContestRepository as _, DetailedContest};
+use chrono::{Duration, Utc};
+use crop_domain::admin::{
+    audit,
+    model::{AuthenticatedAdmin, Permission},
+    repository::AdminRepository,
+};
+use crop_domain::contest::poll::{
+    self, AnswerCommitment, Choice, DetailedPoll, NumericSpec, Poll, PollId, PollKind, PollScoring,
+    PollStatus,
+};
+use crop_domain::contest::{Contest, ContestId, ContestRepository, DetailedContest, NewPoll};
+use crop_infra::pg;
 use http::StatusCode;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -16,8 +25,30 @@ use warp::Filter as _;
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct ReqBody {
     title: String,
+    /// 省略した場合は単一選択
+    #[serde(default)]
+    kind: PollKind,
+    /// 複数選択のPollや、数値・順位で答えるPollの採点方法。省略した場合は完全一致のみ正解
+    #[serde(default)]
+    scoring: PollScoring,
     duration_sec: Option<i32>,
+    /// 締め切りの何秒前から回答を変更できなくするか。制限時間のあるPollにのみ指定できる
+    lock_before_close_sec: Option<i32>,
+    /// 数値で答えるPollでは空にする
+    #[serde(default)]
     choices: Vec<Choice>,
+    /// 数値で答えるPollの、回答できる値の範囲
+    numeric: Option<NumericSpec>,
+    /// 順位を予想するPollで、何位までを予想するか
+    ranking_size: Option<usize>,
+    /// falseの場合は正解の無いアンケートにする。省略した場合は採点する
+    is_scored: Option<bool>,
+    /// 指定した場合は、選択肢の回答にポイントを賭けられる。
+    /// 正解すると、賭けたポイントにこの倍率を掛けて払い戻す
+    stake_multiplier: Option<f64>,
+    /// 正解のコミットメント。16進数で表した `SHA-256(正解のChoice名 || ':' || nonce)`。
+    /// 単一選択のPollにのみ付けられる
+    answer_commitment: Option<String>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -27,29 +58,62 @@ pub struct ResBody<'a>(&'a PollId);
 pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
     warp::path!("contests" / ContestId / "polls")
         .and(warp::filters::method::post())
-        .and(auth::admin())
+        .and(auth::admin_with(ctx.clone(), Permission::AddPoll))
         .and(warp::filters::body::json::<ReqBody>())
-        .and_then(move |contest_id, _admin, body| {
+        .and_then(move |contest_id, admin, body| {
             ctx.clone()
-                .handle_request(move |ctx| inner(ctx, contest_id, body))
+                .handle_request(move |ctx| inner(ctx, contest_id, admin, body))
         })
         .recover(Error::recover)
         .unify()
         .boxed()
 }
 
-async fn inner(ctx: Context, contest_id: ContestId, body: ReqBody) -> Result<Response, Error> {
+async fn inner(
+    ctx: Context,
+    contest_id: ContestId,
+    admin: AuthenticatedAdmin,
+    body: ReqBody,
+) -> Result<Response, Error> {
     // PollをDBに追加する
     let poll = ctx
         .pg
         .with_conn::<Result<poll::New, Error>, _>(move |conn| {
-            let duration = body.duration_sec.map(|s| Duration::seconds(s as i64));
-            let contest = conn
-                .query_by_id::<DetailedContest<DetailedPoll>>(&contest_id)?
+            pg::transaction(&conn, || {
+                let duration = body.duration_sec.map(|s| Duration::seconds(s as i64));
+                let lock_before_close = body
+                    .lock_before_close_sec
+                    .map(|s| Duration::seconds(s as i64));
+                let answer_commitment = body
+                    .answer_commitment
+                    .map(|s| s.parse::<AnswerCommitment>())
+                    .transpose()
+                    .map_err(|e| {
+                        log::info!("Invalid answer commitment : {:?}", e);
+                        Error::new(StatusCode::BAD_REQUEST, "Invalid answer commitment")
+                    })?;
+                let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                    &conn,
+                    &contest_id,
+                )?
                 .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
-            let added = contest.add_poll(body.title, duration, body.choices)?;
-            conn.save(&added)?;
-            Ok(added.poll)
+                let added = contest.add_poll(NewPoll {
+                    title: body.title,
+                    kind: body.kind,
+                    scoring: body.scoring,
+                    duration,
+                    lock_before_close,
+                    choices: body.choices,
+                    numeric: body.numeric,
+                    ranking_size: body.ranking_size,
+                    is_scored: body.is_scored.unwrap_or(true),
+                    stake_multiplier: body.stake_multiplier,
+                    answer_commitment,
+                })?;
+                ContestRepository::save(&conn, &added)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &added))?;
+                Ok(added.poll)
+            })
         })
         .await??;
 
@@ -57,7 +121,7 @@ async fn inner(ctx: Context, contest_id: ContestId, body: ReqBody) -> Result<Res
 
     // 指定時間後にPollをCloseする
     if let Some(dur) = poll.duration() {
-        let _ = tokio::spawn(start_close_timer(ctx.clone(), *dur, contest_id, poll_id));
+        start_close_timer(ctx.clone(), *dur, contest_id, poll_id).await;
     }
 
     // 追加したPollをブロードキャストする
@@ -69,39 +133,122 @@ async fn inner(ctx: Context, contest_id: ContestId, body: ReqBody) -> Result<Res
     Ok(response::new(StatusCode::CREATED, &ResBody(&poll_id)))
 }
 
-async fn start_close_timer(ctx: Context, dur: Duration, contest_id: ContestId, poll_id: PollId) {
-    // Closeするまで待つ
-    let tokio_dur = tokio::time::Duration::from_secs(dur.num_seconds() as u64);
-    tokio::time::delay_for(tokio_dur).await;
+/// 指定時間後にPollをCloseするタイマーを開始する。
+/// `POST /contests/:id/sync` からも、tools経由で追加されたPollのために使われる。
+///
+/// 同じPollで前に開始したタイマーは、目覚めた時に何もせず終了する。
+/// 待っている間に制限時間が延長された場合は、新しい締め切りまで待ち直す。
+/// 一時停止された場合は終了し、再開した時に改めてタイマーを開始する。
+pub(crate) async fn start_close_timer(
+    ctx: Context,
+    dur: Duration,
+    contest_id: ContestId,
+    poll_id: PollId,
+) {
+    let generation = ctx.timer_manager.start(poll_id).await;
+    tokio::spawn(run_close_timer(ctx, dur, contest_id, poll_id, generation));
+}
+
+async fn run_close_timer(
+    ctx: Context,
+    dur: Duration,
+    contest_id: ContestId,
+    poll_id: PollId,
+    generation: u64,
+) {
+    let mut dur = dur;
+    loop {
+        // Closeするまで待つ
+        let tokio_dur = tokio::time::Duration::from_millis(dur.num_milliseconds().max(0) as u64);
+        tokio::time::delay_for(tokio_dur).await;
+
+        if !ctx.timer_manager.is_current(&poll_id, generation).await {
+            log::info!("Timer of poll {:?} is superseded", poll_id);
+            return;
+        }
 
-    close_poll(ctx, contest_id, poll_id).await;
+        match close_poll(ctx.clone(), contest_id, poll_id).await {
+            Some(remaining) => dur = remaining,
+            None => {
+                ctx.timer_manager.finish(&poll_id, generation).await;
+                return;
+            }
+        }
+    }
 }
 
-async fn close_poll(ctx: Context, contest_id: ContestId, poll_id: PollId) {
-    let msg_source = ctx
+enum TimerChecked {
+    Closed(Box<PollMsgSource<poll::Closed<DetailedPoll>>>),
+    /// まだ締め切り前
+    Waiting(Duration),
+    Stopped,
+}
+
+/// DBに接続できなかった場合に、確認し直すまでの時間
+const RETRY_INTERVAL_SECS: i64 = 5;
+
+/// まだ締め切り前の場合は、残り時間を返す。
+/// DBのエラーでCloseできなかった場合も、少し待ってから確認し直す。
+async fn close_poll(ctx: Context, contest_id: ContestId, poll_id: PollId) -> Option<Duration> {
+    let checked = ctx
         .pg
-        .with_conn(move |conn| {
+        .with_conn(move |conn| -> anyhow::Result<TimerChecked> {
             // TODO: DetailedContestである必要ない。MinumumContestでいい。
-            let contest = conn
-                .query_by_id::<DetailedContest<DetailedPoll>>(&contest_id)
-                .unwrap()
-                .unwrap();
-            let poll = contest.current_poll().unwrap();
-
-            if *poll.id() != poll_id {
-                panic!("A new poll is created while prev poll is not closed");
+            let contest = ContestRepository::query_by_id::<DetailedContest<DetailedPoll>>(
+                &conn,
+                &contest_id,
+            )?;
+            let poll = match contest.as_ref().and_then(|contest| contest.current_poll()) {
+                Some(poll) => poll,
+                None => {
+                    log::warn!("Contest {:?} has no poll to close", contest_id);
+                    return Ok(TimerChecked::Stopped);
+                }
+            };
+
+            // 待っている間に、Adminやtoolsによって既にCloseされていることがある
+            if *poll.id() != poll_id || poll.status() != PollStatus::Open {
+                log::info!("Poll {:?} is already closed", poll_id);
+                return Ok(TimerChecked::Stopped);
+            }
+
+            if poll.paused_at().is_some() {
+                log::info!("Poll {:?} is paused", poll_id);
+                return Ok(TimerChecked::Stopped);
+            }
+
+            if let Some(closes_at) = poll.closes_at() {
+                let now = Utc::now();
+                if now < closes_at {
+                    return Ok(TimerChecked::Waiting(closes_at - now));
+                }
             }
 
-            let closed = poll.clone().close().unwrap();
-            conn.save(&closed).unwrap();
+            let closed = poll.clone().close()?;
+            ContestRepository::save(&conn, &closed)?;
 
-            PollMsgSource::from(closed)
+            Ok(TimerChecked::Closed(Box::new(PollMsgSource::from(closed))))
         })
         .await
-        .unwrap();
+        .and_then(|checked| checked);
 
-    // CloseMsgをブロードキャスト
-    ctx.contest_manager
-        .broadcast_msg(contest_id, msg_source)
-        .await;
+    let checked = match checked {
+        Ok(checked) => checked,
+        Err(e) => {
+            log::error!("Failed to close poll {:?} : {:?}", poll_id, e);
+            return Some(Duration::seconds(RETRY_INTERVAL_SECS));
+        }
+    };
+
+    match checked {
+        TimerChecked::Closed(msg_source) => {
+            // CloseMsgをブロードキャスト
+            ctx.contest_manager
+                .broadcast_msg(contest_id, *msg_source)
+                .await;
+            None
+        }
+        TimerChecked::Waiting(remaining) => Some(remaining),
+        TimerChecked::Stopped => None,
+    }
 }
@@ -0,0 +1,53 @@
+use crate::{
+    context::Context,
+    error::Error,
+    response::{self, Response},
+};
+use crop_domain::contest::ContestId;
+use crop_domain::reward::{
+    verification::Verification, NotCommitted, RewardId, RewardRepository as _,
+};
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::Serialize;
+use warp::Filter as _;
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody(Verification);
+
+/// 抽選で選ぶ賞品の、seedのハッシュ値と検証に必要な情報を返す。
+/// 参加者が抽選を検証できるよう、認証は必要ない。
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "rewards" / RewardId / "verification")
+        .and(warp::filters::method::get())
+        .and_then(move |contest_id, reward_id| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id, reward_id))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(
+    ctx: Context,
+    contest_id: ContestId,
+    reward_id: RewardId,
+) -> Result<Response, Error> {
+    ctx.pg
+        .with_conn::<Result<Response, Error>, _>(move |conn| {
+            match conn.query_verification(&reward_id) {
+                Ok(Some(verification)) if verification.contest_id() == &contest_id => {
+                    Ok(response::new(StatusCode::OK, &ResBody(verification)))
+                }
+                Ok(_) => Err(Error::new(StatusCode::NOT_FOUND, "Reward not found")),
+                Err(e) if e.is::<NotCommitted>() => Err(Error::new(
+                    StatusCode::NOT_FOUND,
+                    "Reward is not drawn by committed seed",
+                )),
+                Err(e) => Err(Error::from(e)),
+            }
+        })
+        .await?
+}
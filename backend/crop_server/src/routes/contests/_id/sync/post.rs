@@ -0,0 +1,75 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+    routes::{
+        contests::_id::polls::post::start_close_timer,
+        ws::contests::_id::{ClosedMsgSource, PollMsgSource},
+    },
+};
+use chrono::Utc;
+use crop_domain::admin::model::Permission;
+use crop_domain::contest::poll::{DetailedPoll, Poll, PollStatus};
+use crop_domain::contest::{
+    Contest, ContestId, ContestRepository as _, ContestStatus, DetailedContest,
+};
+use http::StatusCode;
+use warp::Filter as _;
+
+/// DBに保存されているContestの状態を、WSのクライアントに再送する。
+///
+/// toolsなどからDBを直接更新した場合に、稼働中のサーバーへ通知するために使う。
+/// 現在のPollがOpenで制限時間がある場合は、残り時間でCloseするタイマーも開始する。
+/// Close済みのContestでは、Pollの正解の訂正などを反映したスコアも再送する。
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / ContestId / "sync")
+        .and(warp::filters::method::post())
+        .and(auth::admin_with(ctx.clone(), Permission::SyncContest))
+        .and_then(move |contest_id, _admin| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, contest_id))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, contest_id: ContestId) -> Result<Response, Error> {
+    let (poll, closed) = ctx
+        .pg
+        .with_conn::<Result<(Option<DetailedPoll>, Option<ClosedMsgSource>), Error>, _>(
+            move |conn| {
+                let contest = conn
+                    .query_by_id::<DetailedContest<DetailedPoll>>(&contest_id)?
+                    .ok_or_else(|| Error::new(StatusCode::NOT_FOUND, "Contest not found"))?;
+                let poll = contest.current_poll().cloned();
+                let closed = if contest.status() == ContestStatus::Closed {
+                    Some(ClosedMsgSource::from(contest))
+                } else {
+                    None
+                };
+                Ok((poll, closed))
+            },
+        )
+        .await??;
+
+    if let Some(poll) = poll {
+        if poll.status() == PollStatus::Open {
+            // 一時停止中のPollでは、再開するまでタイマーを開始しない
+            if let Some(closes_at) = poll.closes_at() {
+                start_close_timer(ctx.clone(), closes_at - Utc::now(), contest_id, *poll.id())
+                    .await;
+            }
+        }
+
+        ctx.contest_manager
+            .broadcast_msg(contest_id, PollMsgSource::from(poll))
+            .await;
+    }
+    if let Some(closed) = closed {
+        ctx.contest_manager.broadcast_msg(contest_id, closed).await;
+    }
+
+    Ok(response::new(StatusCode::OK, &"synced"))
+}
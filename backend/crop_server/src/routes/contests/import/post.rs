@@ -0,0 +1,66 @@
+use crate::{
+    context::Context,
+    error::Error,
+    filters::auth,
+    response::{self, Response},
+};
+use crop_domain::admin::{
+    audit,
+    model::{AuthenticatedAdmin, Permission},
+    repository::AdminRepository,
+};
+use crop_domain::contest::{
+    model::{Contest, ContestId},
+    repository::ContestRepository,
+    script::Script,
+};
+use crop_infra::pg;
+use http::StatusCode;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use warp::Filter as _;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ReqBody(Script);
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct ResBody(ContestId);
+
+/// 台本からUpcomingなContestを作成する。
+/// 各Pollは下書きとして保存され、参加者にはまだ見えない。
+pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
+    warp::path!("contests" / "import")
+        .and(warp::filters::method::post())
+        .and(auth::admin_with(ctx.clone(), Permission::CreateContest))
+        .and(warp::filters::body::json::<ReqBody>())
+        .and_then(move |admin, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, admin, body))
+        })
+        .recover(Error::recover)
+        .unify()
+        .boxed()
+}
+
+async fn inner(ctx: Context, admin: AuthenticatedAdmin, body: ReqBody) -> Result<Response, Error> {
+    let contest_id = ctx
+        .pg
+        .with_conn::<Result<ContestId, Error>, _>(move |conn| {
+            pg::transaction(&conn, || {
+                let imported = body.0.import().map_err(|e| {
+                    log::info!("invalid contest script : {:?}", e);
+                    Error::new(StatusCode::BAD_REQUEST, "Invalid contest script")
+                })?;
+                ContestRepository::save(&conn, &imported)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &imported))?;
+                Ok(*imported.contest.id())
+            })
+        })
+        .await??;
+
+    ctx.contest_manager.enable_subscribe(contest_id).await;
+
+    Ok(response::new(StatusCode::CREATED, &ResBody(contest_id)))
+}
@@ -1,3 +1,4 @@
 pub mod _id;
 pub mod get;
+pub mod import;
 pub mod post;
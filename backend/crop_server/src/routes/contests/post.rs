@@ -5,11 +5,17 @@ use crate::{
     response::{self, Response},
 };
 use chrono::{DateTime, Utc};
+use crop_domain::admin::{
+    audit,
+    model::{AuthenticatedAdmin, Permission},
+    repository::AdminRepository,
+};
 use crop_domain::contest::{
     self,
     model::{Contest, ContestId},
-    repository::ContestRepository as _,
+    repository::ContestRepository,
 };
+use crop_infra::pg;
 use http::StatusCode;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -29,21 +35,27 @@ pub struct ResBody(ContestId);
 pub fn route(ctx: Context) -> warp::filters::BoxedFilter<(Response,)> {
     warp::path!("contests")
         .and(warp::filters::method::post())
-        .and(auth::admin())
+        .and(auth::admin_with(ctx.clone(), Permission::CreateContest))
         .and(warp::filters::body::json::<ReqBody>())
-        .and_then(move |_admin, body| ctx.clone().handle_request(move |ctx| inner(ctx, body)))
+        .and_then(move |admin, body| {
+            ctx.clone()
+                .handle_request(move |ctx| inner(ctx, admin, body))
+        })
         .recover(Error::recover)
         .unify()
         .boxed()
 }
 
-async fn inner(ctx: Context, body: ReqBody) -> Result<Response, Error> {
+async fn inner(ctx: Context, admin: AuthenticatedAdmin, body: ReqBody) -> Result<Response, Error> {
     let contest_id = ctx
         .pg
         .with_conn::<Result<ContestId, Error>, _>(move |conn| {
-            let contest = contest::new(body.title, body.category, body.event_start_at);
-            conn.save(&contest)?;
-            Ok(*contest.id())
+            pg::transaction(&conn, || {
+                let contest = contest::new(body.title, body.category, body.event_start_at);
+                ContestRepository::save(&conn, &contest)?;
+                AdminRepository::save(&conn, &audit::record(&admin, &contest))?;
+                Ok(*contest.id())
+            })
         })
         .await??;
 
@@ -1,4 +1,5 @@
 pub mod accounts;
+pub mod admin;
 pub mod admins;
 pub mod contests;
 pub mod ws;
@@ -9,14 +10,22 @@ use warp::{filters::cors, reject::Rejection, reply::Reply, Filter};
 pub fn filter(ctx: Context) -> impl Filter<Extract = (impl Reply,), Error = Rejection> + Clone {
     let cors_wrapper = cors::cors()
         .allow_any_origin()
-        .allow_methods(vec!["POST", "PATCH", "PUT", "OPTIONS"])
+        .allow_methods(vec!["GET", "POST", "PATCH", "PUT", "DELETE", "OPTIONS"])
         .allow_headers(vec!["Content-Type", "Authorization"]);
 
     let rest_routes = contests::get::route(ctx.clone())
         .or(contests::post::route(ctx.clone()))
+        .or(contests::import::post::route(ctx.clone()))
         .or(contests::_id::get::route(ctx.clone()))
         .or(contests::_id::patch::route(ctx.clone()))
+        .or(contests::_id::sync::post::route(ctx.clone()))
+        .or(contests::_id::export::get::route(ctx.clone()))
+        .or(contests::_id::drafts::get::route(ctx.clone()))
+        .or(contests::_id::drafts::post::route(ctx.clone()))
+        .or(contests::_id::drafts::_id::patch::route(ctx.clone()))
+        .or(contests::_id::drafts::_id::delete::route(ctx.clone()))
         .or(contests::_id::polls::post::route(ctx.clone()))
+        .or(contests::_id::polls::next::post::route(ctx.clone()))
         .or(contests::_id::polls::_id::comments::post::route(
             ctx.clone(),
         ))
@@ -24,8 +33,27 @@ pub fn filter(ctx: Context) -> impl Filter<Extract = (impl Reply,), Error = Reje
             ctx.clone(),
         ))
         .or(contests::_id::polls::_id::patch::route(ctx.clone()))
+        .or(contests::_id::polls::_id::timer::patch::route(ctx.clone()))
+        .or(contests::_id::polls::_id::market::get::route(ctx.clone()))
+        .or(contests::_id::polls::_id::market::post::route(ctx.clone()))
+        .or(contests::_id::polls::_id::market::trades::post::route(
+            ctx.clone(),
+        ))
+        .or(contests::_id::rewards::_id::verification::get::route(
+            ctx.clone(),
+        ))
         .or(accounts::post::route(ctx.clone()))
-        .or(admins::me::access_tokens::post::route(ctx.clone()));
+        .or(accounts::access_tokens::post::route(ctx.clone()))
+        .or(accounts::me::credentials::post::route(ctx.clone()))
+        .or(accounts::me::wallet::get::route(ctx.clone()))
+        .or(admins::me::access_tokens::post::route(ctx.clone()))
+        .or(admins::me::totp::post::route(ctx.clone()))
+        .or(admins::me::totp::verification::post::route(ctx.clone()))
+        .or(admins::get::route(ctx.clone()))
+        .or(admins::post::route(ctx.clone()))
+        .or(admins::_id::patch::route(ctx.clone()))
+        .or(admin::audit_log::get::route(ctx.clone()))
+        .or(admin::wallets::_id::transactions::post::route(ctx.clone()));
 
     let rest = rest_routes.with(cors_wrapper);
 
@@ -1,7 +1,10 @@
 use chrono::{DateTime, Utc};
 use crop_domain::account::{self, Account, AccountId};
 use crop_domain::contest::comment::Comment;
-use crop_domain::contest::poll::{self, Choice, ChoiceName, Poll, PollId, PollStatus, Stats};
+use crop_domain::contest::poll::{
+    self, market::Market, AnswerCommitment, Choice, ChoiceName, NumericSpec, Poll, PollId,
+    PollKind, PollScoring, PollStatus, Stats,
+};
 use crop_domain::contest::{self, Contest};
 use schemars::JsonSchema;
 use serde::Serialize;
@@ -13,9 +16,13 @@ use warp::filters::ws::Message;
 pub enum OutgoingMsg<'a> {
     Poll(PollMsg<'a>),
     Comment(CommentMsg<'a>),
+    /// マーケットの価格が変わったときに受け取るMsg
+    Market(MarketMsg<'a>),
     /// Contestがcloseしたときに受け取るMsg
     /// 自分のスコア情報が載っている
     Closed(ClosedMsg),
+    /// 現在のPollではない、過去のPollの正解が訂正されたときに受け取るMsg
+    Corrected(CorrectedMsg<'a>),
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
@@ -23,14 +30,45 @@ pub struct PollMsg<'a> {
     id: &'a PollId,
     title: &'a str,
     status: PollStatus,
+    kind: PollKind,
+    scoring: PollScoring,
+    /// falseの場合は正解の無いアンケート。Resolveされず、Close後の `stats` が結果になる
+    is_scored: bool,
+    /// 回答にポイントを賭けられるPollで、正解したときに払い戻す倍率
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stake_multiplier: Option<f64>,
     created_at: &'a DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     duration_sec: Option<i64>,
+    /// 回答を変更できなくなる日時。締め切りと同じく、一時停止中は `paused_at` で時間が止まる
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locks_at: Option<DateTime<Utc>>,
     idx: usize,
     #[schemars(with = "Vec<Choice>")]
     choices: &'a [Choice],
+    /// 単一選択のPollの正解
     #[serde(skip_serializing_if = "Option::is_none")]
     resolved_choice: Option<&'a ChoiceName>,
+    /// 複数選択のPollの正解や、順位を予想するPollの実際の順位
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<Vec<ChoiceName>>")]
+    resolved_choices: Option<&'a [ChoiceName]>,
+    /// 順位を予想するPollで、何位までを予想するか
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ranking_size: Option<usize>,
+    /// 数値で答えるPollの、回答できる値の範囲
+    #[serde(skip_serializing_if = "Option::is_none")]
+    numeric: Option<&'a NumericSpec>,
+    /// 数値で答えるPollの正解
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_value: Option<f64>,
+    /// 正解のコミットメント。Resolveされるとnonceも公開される。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer_commitment: Option<&'a AnswerCommitment>,
+    /// タイマーが一時停止している場合、停止した日時。
+    /// 再開すると、停止していた時間だけ `duration_sec` が延びる。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paused_at: Option<&'a DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stats: Option<&'a Stats>,
 }
@@ -43,12 +81,39 @@ pub struct CommentMsg<'a> {
     choice: Option<&'a ChoiceName>,
 }
 
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MarketMsg<'a> {
+    poll_id: &'a PollId,
+    /// 選択肢ごとの価格。その選択肢が正解になる確率の推定値で、合計は1になる
+    prices: &'a HashMap<ChoiceName, f64>,
+}
+
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct ClosedMsg {
-    /// 何問のPollが出題されたか
+    /// 何問のPollが採点されたか。アンケートと中止されたPollは含まない。
     num_polls: usize,
-    /// 何問正解したか
-    account_score: Option<usize>,
+    /// 獲得した点数。1問の満点は1点で、部分点を含む
+    account_score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CorrectedMsg<'a> {
+    poll_id: &'a PollId,
+    idx: usize,
+    /// 単一選択のPollの、訂正後の正解
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_choice: Option<&'a ChoiceName>,
+    /// 複数選択のPollや、順位を予想するPollの、訂正後の正解
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<Vec<ChoiceName>>")]
+    resolved_choices: Option<&'a [ChoiceName]>,
+    /// 数値で答えるPollの、訂正後の正解
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_value: Option<f64>,
+    /// 訂正後の正解で計算し直した、このPollの点数
+    account_points: Option<f64>,
+    /// 訂正後の正解で計算し直した、これまでに獲得した点数
+    account_score: Option<f64>,
 }
 
 impl<'a> OutgoingMsg<'a> {
@@ -93,7 +158,7 @@ where
     fn from(poll: P) -> PollMsgSource<P> {
         let stats = match poll.status() {
             PollStatus::Open => None,
-            PollStatus::Closed => Some(poll.compute_stats()),
+            PollStatus::Closed | PollStatus::Voided => Some(poll.compute_stats()),
         };
         PollMsgSource { poll, stats }
     }
@@ -108,11 +173,25 @@ where
             id: self.poll.id(),
             title: self.poll.title(),
             status: self.poll.status(),
+            kind: self.poll.kind(),
+            scoring: self.poll.scoring(),
+            is_scored: self.poll.is_scored(),
+            stake_multiplier: self.poll.stake_multiplier(),
             created_at: self.poll.created_at(),
             duration_sec: self.poll.duration().map(|d| d.num_seconds()),
+            locks_at: self.poll.locks_at(),
             idx: self.poll.idx(),
             choices: self.poll.choices(),
             resolved_choice: self.poll.resolved_choice(),
+            resolved_choices: match self.poll.kind() {
+                PollKind::Multiple | PollKind::Ranking => self.poll.resolved_choices(),
+                PollKind::Single | PollKind::Numeric => None,
+            },
+            ranking_size: self.poll.ranking_size(),
+            numeric: self.poll.numeric(),
+            resolved_value: self.poll.resolved_value(),
+            answer_commitment: self.poll.answer_commitment(),
+            paused_at: self.poll.paused_at(),
             stats: self.stats.as_ref(),
         })
     }
@@ -149,6 +228,34 @@ where
     }
 }
 
+/*
+ * ===========
+ * MarketMsgSource
+ * ===========
+ */
+pub struct MarketMsgSource {
+    poll_id: PollId,
+    prices: HashMap<ChoiceName, f64>,
+}
+
+impl From<&Market> for MarketMsgSource {
+    fn from(market: &Market) -> MarketMsgSource {
+        MarketMsgSource {
+            poll_id: *market.poll_id(),
+            prices: market.prices(),
+        }
+    }
+}
+
+impl OutgoingMsgSource for MarketMsgSource {
+    fn into_out_msg<'a>(&'a self, _account_id: &'a AccountId) -> OutgoingMsg<'a> {
+        OutgoingMsg::Market(MarketMsg {
+            poll_id: &self.poll_id,
+            prices: &self.prices,
+        })
+    }
+}
+
 /*
  * ===========
  * ClosedMsgSource
@@ -156,7 +263,7 @@ where
  */
 pub struct ClosedMsgSource {
     num_polls: usize,
-    account_scores: HashMap<AccountId, usize>,
+    account_scores: HashMap<AccountId, f64>,
 }
 
 impl<C> From<C> for ClosedMsgSource
@@ -167,7 +274,11 @@ where
 {
     fn from(contest: C) -> ClosedMsgSource {
         ClosedMsgSource {
-            num_polls: contest.num_polls(),
+            num_polls: contest
+                .polls()
+                .iter()
+                .filter(|poll| poll.counts_toward_score())
+                .count(),
             account_scores: contest.compute_account_scores(),
         }
     }
@@ -181,3 +292,56 @@ impl OutgoingMsgSource for ClosedMsgSource {
         })
     }
 }
+
+/*
+ * ===========
+ * CorrectedMsgSource
+ * ===========
+ */
+pub struct CorrectedMsgSource {
+    poll_id: PollId,
+    idx: usize,
+    resolved_choice: Option<ChoiceName>,
+    resolved_choices: Option<Vec<ChoiceName>>,
+    resolved_value: Option<f64>,
+    account_points: HashMap<AccountId, f64>,
+    account_scores: HashMap<AccountId, f64>,
+}
+
+impl<P, C> From<(&P, &C)> for CorrectedMsgSource
+where
+    P: Poll + poll::WithAttrs + poll::WithUserChoices,
+    C: Contest + contest::WithPolls,
+    <C as contest::WithPolls>::Poll: poll::WithAttrs + poll::WithUserChoices,
+{
+    fn from((poll, contest): (&P, &C)) -> CorrectedMsgSource {
+        CorrectedMsgSource {
+            poll_id: *poll.id(),
+            idx: poll.idx(),
+            resolved_choice: poll.resolved_choice().cloned(),
+            resolved_choices: match poll.kind() {
+                PollKind::Multiple | PollKind::Ranking => {
+                    poll.resolved_choices().map(|choices| choices.to_vec())
+                }
+                PollKind::Single | PollKind::Numeric => None,
+            },
+            resolved_value: poll.resolved_value(),
+            account_points: poll.compute_points(),
+            account_scores: contest.compute_account_scores(),
+        }
+    }
+}
+
+impl OutgoingMsgSource for CorrectedMsgSource {
+    fn into_out_msg<'a>(&'a self, account_id: &'a AccountId) -> OutgoingMsg<'a> {
+        OutgoingMsg::Corrected(CorrectedMsg {
+            poll_id: &self.poll_id,
+            idx: self.idx,
+            resolved_choice: self.resolved_choice.as_ref(),
+            resolved_choices: self.resolved_choices.as_deref(),
+            resolved_value: self.resolved_value,
+            account_points: self.account_points.get(account_id).copied(),
+            account_score: self.account_scores.get(account_id).copied(),
+        })
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{context::Context, routes};
+use crate::{context::Context, filters::client::RemoteAddr, routes};
 use futures::future;
+use hyper::{server::conn::AddrStream, service::Service as _};
 use std::{convert::Infallible, net::SocketAddr};
 use warp::{filters::log::log, Filter as _};
 
@@ -9,8 +10,15 @@ impl Server {
     pub async fn bind(socket: impl Into<SocketAddr> + 'static, ctx: Context) {
         let filter = routes::filter(ctx.clone()).with(log("crop_server"));
         let svc = warp_json_rpc::service(filter);
-        let make_svc =
-            hyper::service::make_service_fn(move |_| future::ok::<_, Infallible>(svc.clone()));
+        let make_svc = hyper::service::make_service_fn(move |conn: &AddrStream| {
+            // warp::serviceを経由するとremote addrが失われるので、リクエストに付与しておく
+            let remote = RemoteAddr(conn.remote_addr());
+            let svc = svc.clone();
+            future::ok::<_, Infallible>(hyper::service::service_fn(move |mut req| {
+                req.extensions_mut().insert(remote);
+                svc.clone().call(req)
+            }))
+        });
 
         hyper::Server::bind(&socket.into())
             .serve(make_svc)
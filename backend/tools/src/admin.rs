@@ -0,0 +1,127 @@
+use crate::{get_env_var_or_panic, read_password};
+use crop_domain::admin::{
+    self,
+    model::{Admin as _, BriefAdmin, Role},
+    repository::AdminRepository as _,
+};
+use crop_infra::pg::Pool;
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+pub enum Cmd {
+    /// Adminを作成する。パスワードは対話的に入力する。
+    Create {
+        #[structopt(short, long)]
+        email: String,
+        /// superadmin, operator, moderatorのいずれか
+        #[structopt(short, long, parse(try_from_str = parse_role))]
+        role: Role,
+    },
+    /// 全てのAdminを表示する
+    List,
+    /// Adminのパスワードを変更する。パスワードは対話的に入力する。
+    ResetPassword {
+        #[structopt(short, long)]
+        email: String,
+    },
+    /// Adminを無効化する
+    Disable {
+        #[structopt(short, long)]
+        email: String,
+    },
+    /// 認証アプリを紛失したAdminのTOTPを解除する
+    ResetTotp {
+        #[structopt(short, long)]
+        email: String,
+    },
+}
+
+pub async fn run(cmd: Cmd) -> anyhow::Result<()> {
+    let pool = Pool::new(get_env_var_or_panic("DATABASE_URL"));
+
+    match cmd {
+        Cmd::Create { email, role } => {
+            let pass = read_new_password()?;
+            pool.with_conn(move |conn| {
+                if conn.query_by_email::<BriefAdmin>(email.as_str())?.is_some() {
+                    return Err(anyhow::anyhow!("Admin {} already exists", email));
+                }
+                let new = admin::model::new(email, pass.as_str(), role)?;
+                conn.save(&new)?;
+                println!("Admin {} is created : {}", new.email(), new.id().0);
+                Ok(())
+            })
+            .await?
+        }
+        Cmd::List => {
+            pool.with_conn(|conn| {
+                for admin in conn.query_all::<BriefAdmin>()? {
+                    println!(
+                        "{}\t{}\t{:?}\t{}",
+                        admin.id().0,
+                        admin.email(),
+                        admin.role(),
+                        if admin.is_disabled() {
+                            "disabled"
+                        } else {
+                            "active"
+                        },
+                    );
+                }
+                Ok(())
+            })
+            .await?
+        }
+        Cmd::ResetPassword { email } => {
+            let pass = read_new_password()?;
+            pool.with_conn(move |conn| {
+                let admin = query_by_email(&conn, email.as_str())?;
+                conn.save(&admin.reset_password(pass.as_str())?)?;
+                println!("Password of {} is reset", email);
+                Ok(())
+            })
+            .await?
+        }
+        Cmd::Disable { email } => {
+            pool.with_conn(move |conn| {
+                let admin = query_by_email(&conn, email.as_str())?;
+                conn.save(&admin.disable()?)?;
+                println!("Admin {} is disabled", email);
+                Ok(())
+            })
+            .await?
+        }
+        Cmd::ResetTotp { email } => {
+            pool.with_conn(move |conn| {
+                let admin = query_by_email(&conn, email.as_str())?;
+                conn.save(&admin.reset_totp())?;
+                println!("TOTP of {} is reset", email);
+                Ok(())
+            })
+            .await?
+        }
+    }
+}
+
+fn query_by_email(conn: &crop_infra::pg::Connection, email: &str) -> anyhow::Result<BriefAdmin> {
+    conn.query_by_email::<BriefAdmin>(email)?
+        .ok_or_else(|| anyhow::anyhow!("Admin {} is not found", email))
+}
+
+fn read_new_password() -> anyhow::Result<String> {
+    let pass = read_password("Password: ");
+    let confirmation = read_password("Confirm password: ");
+    if pass != confirmation {
+        return Err(anyhow::anyhow!("Passwords do not match"));
+    }
+    Ok(pass)
+}
+
+fn parse_role(s: &str) -> Result<Role, String> {
+    match s {
+        "superadmin" => Ok(Role::Superadmin),
+        "operator" => Ok(Role::Operator),
+        "moderator" => Ok(Role::Moderator),
+        _ => Err(format!("Unknown role : {}", s)),
+    }
+}
@@ -0,0 +1,639 @@
+use crate::get_env_var_or_panic;
+use chrono::{DateTime, Duration, Utc};
+use crop_domain::{
+    admin::{
+        audit,
+        model::{Admin as _, BriefAdmin, Permission},
+        repository::AdminRepository,
+    },
+    contest::{
+        self,
+        export::Format,
+        poll::{
+            commitment, market, BriefPoll, Choice, ChoiceColor, ChoiceName, DetailedPoll,
+            NumericSpec, Poll as _, PollId, PollKind, PollScoring, Resolution,
+        },
+        script::Script,
+        BriefContest, Contest as _, ContestId, ContestRepository, ContestStatus, DetailedContest,
+        NewPoll,
+    },
+};
+use crop_infra::pg::{self, Connection, Pool};
+use hyper::{Body, Client, Method, Request};
+use hyper_tls::HttpsConnector;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+/// Webの管理画面が使えない時のために、Contestを直接操作する
+#[derive(StructOpt, Debug)]
+pub struct Cmd {
+    /// 操作を行うAdminのemail。権限の確認と監査ログの記録に使う。
+    #[structopt(long)]
+    admin: String,
+    /// 稼働中のサーバーのURL（例: http://localhost:8080）。
+    /// 指定すると、変更をWSのクライアントに通知する。
+    /// AccessTokenは環境変数 ADMIN_ACCESS_TOKEN から読み込む。
+    #[structopt(long)]
+    notify: Option<String>,
+    #[structopt(subcommand)]
+    op: Op,
+}
+
+#[derive(StructOpt, Debug)]
+enum Op {
+    /// Contestを作成する
+    Create {
+        #[structopt(long)]
+        title: String,
+        #[structopt(long)]
+        category: String,
+        /// RFC3339形式
+        #[structopt(long)]
+        event_start_at: Option<DateTime<Utc>>,
+        /// Openにせず、Upcomingとして作成する
+        #[structopt(long)]
+        upcoming: bool,
+    },
+    /// 台本ファイル（YAMLまたはJSON）から、UpcomingなContestを作成する。
+    /// 各Pollは下書きとして保存される。
+    Import {
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+    },
+    /// UpcomingなContestを開始する
+    Open { contest_id: ContestId },
+    /// Pollを追加する
+    AddPoll {
+        contest_id: ContestId,
+        #[structopt(long)]
+        title: String,
+        #[structopt(long)]
+        duration_sec: Option<i64>,
+        /// 締め切りの何秒前から回答を変更できなくするか
+        #[structopt(long, requires = "duration-sec")]
+        lock_before_close_sec: Option<i64>,
+        /// "name:color" の形式で、選択肢の数だけ指定する
+        #[structopt(long = "choice", parse(try_from_str = parse_choice), required_unless = "min")]
+        choices: Vec<(ChoiceName, ChoiceColor)>,
+        /// 複数の選択肢を選べるPollにする
+        #[structopt(long)]
+        multiple: bool,
+        /// 複数選択のPollで、部分点を与える
+        #[structopt(long, requires = "multiple")]
+        partial: bool,
+        /// 選択肢の代わりに、数値で答えるPollにする。回答できる最小値
+        #[structopt(long, requires_all = &["max", "step"], conflicts_with_all = &["choices", "multiple"])]
+        min: Option<f64>,
+        /// 回答できる最大値
+        #[structopt(long, requires = "min")]
+        max: Option<f64>,
+        /// 回答の刻み
+        #[structopt(long, requires = "min")]
+        step: Option<f64>,
+        /// 正解との差がこの値以下の回答を正解とする
+        #[structopt(long, requires = "min")]
+        tolerance: Option<f64>,
+        /// 正解に近い順に点数を与える
+        #[structopt(long, requires = "min", conflicts_with = "tolerance")]
+        closest: bool,
+        /// 選択肢を上位から順に並べて答えるPollにする。何位までを予想するか
+        #[structopt(long, conflicts_with_all = &["multiple", "min"])]
+        ranking: Option<usize>,
+        /// 順位を予想するPollで、順位が一致した選択肢の割合を得点にする
+        #[structopt(long, requires = "ranking")]
+        position: bool,
+        /// 順位を予想するPollで、上下関係が一致した選択肢の組の割合を得点にする
+        #[structopt(long, requires = "ranking", conflicts_with = "position")]
+        kendall: bool,
+        /// 正解の無いアンケートにする。Resolveせず、スコアにも数えない
+        #[structopt(long, conflicts_with = "answer")]
+        survey: bool,
+        /// 回答にポイントを賭けられるようにし、正解したときに払い戻す倍率を指定する
+        #[structopt(long, conflicts_with = "survey")]
+        stake_multiplier: Option<f64>,
+        /// 正解が事前に決まっている場合に指定する。
+        /// 正解のコミットメントを公開し、Resolveに必要なnonceを表示する。
+        #[structopt(long)]
+        answer: Option<String>,
+    },
+    /// 下書きのキューの先頭を、Pollとして公開する
+    PublishNext { contest_id: ContestId },
+    /// 現在のPollをCloseする
+    ClosePoll {
+        contest_id: ContestId,
+        /// 制限時間の前や、一時停止中でもCloseする
+        #[structopt(long)]
+        force: bool,
+    },
+    /// 誤ってCloseした現在のPollを、Openに戻す。
+    /// 自動Closeのタイマーを開始するには --notify が必要。
+    ReopenPoll {
+        contest_id: ContestId,
+        /// 今からの制限時間。省略した場合は、Closeした時点の残り時間になる。
+        #[structopt(long)]
+        duration_sec: Option<i64>,
+    },
+    /// 現在のPollの制限時間を延長する
+    ExtendPoll {
+        contest_id: ContestId,
+        #[structopt(long)]
+        sec: i64,
+    },
+    /// 現在のPollのタイマーを一時停止する
+    PausePoll { contest_id: ContestId },
+    /// 一時停止していた時間だけ制限時間を延ばして、タイマーを再開する。
+    /// 再開後の自動Closeには --notify が必要。
+    ResumePoll { contest_id: ContestId },
+    /// 現在のPollに、選択肢を取引するマーケットを開く
+    OpenMarket {
+        contest_id: ContestId,
+        /// LMSRの流動性。大きいほど価格が動きにくい
+        #[structopt(long)]
+        liquidity: f64,
+        /// 1アカウントが使えるポイントの上限
+        #[structopt(long)]
+        budget: f64,
+    },
+    /// 現在のPollの正解を設定する
+    ResolvePoll {
+        contest_id: ContestId,
+        /// 複数選択のPollでは正解の数だけ、順位を予想するPollでは上位から順に指定する
+        #[structopt(long = "choice", required_unless = "value")]
+        choices: Vec<String>,
+        /// 数値で答えるPollの正解
+        #[structopt(long, conflicts_with = "choices")]
+        value: Option<f64>,
+        /// Pollの追加時に表示されたnonce
+        #[structopt(long)]
+        nonce: Option<String>,
+    },
+    /// Resolve済みのPollの正解を訂正する。
+    /// Close済みのContestでは、--notify でスコアも再送される。
+    ReResolvePoll {
+        contest_id: ContestId,
+        poll_id: PollId,
+        /// 複数選択のPollでは正解の数だけ、順位を予想するPollでは上位から順に指定する
+        #[structopt(long = "choice", required_unless = "value")]
+        choices: Vec<String>,
+        /// 数値で答えるPollの正解
+        #[structopt(long, conflicts_with = "choices")]
+        value: Option<f64>,
+    },
+    /// 現在のPollを、採点せずに終了する
+    VoidPoll { contest_id: ContestId },
+    /// ContestをCloseする
+    Close { contest_id: ContestId },
+    /// ContestをArchiveする
+    Archive { contest_id: ContestId },
+    /// 終了したContestの結果を書き出す
+    Export {
+        contest_id: ContestId,
+        /// csv または json。省略した場合はjson
+        #[structopt(long)]
+        format: Option<Format>,
+        /// 省略した場合は標準出力に書き出す
+        #[structopt(long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+}
+
+pub async fn run(cmd: Cmd) -> anyhow::Result<()> {
+    let pool = Pool::new(get_env_var_or_panic("DATABASE_URL"));
+    let admin_email = cmd.admin;
+    let op = cmd.op;
+
+    let contest_id = pool
+        .with_conn(move |conn| {
+            let admin = query_admin(&conn, admin_email.as_str())?;
+            pg::transaction(&conn, || apply(&conn, &admin, op))
+        })
+        .await??;
+
+    if let Some(server_url) = cmd.notify {
+        // DBの更新は完了しているので、通知に失敗してもエラーにはしない
+        if let Err(e) = notify(server_url.as_str(), &contest_id).await {
+            eprintln!("Warning : failed to notify the server : {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+fn apply(conn: &Connection, admin: &BriefAdmin, op: Op) -> anyhow::Result<ContestId> {
+    match op {
+        Op::Create {
+            title,
+            category,
+            event_start_at,
+            upcoming,
+        } => {
+            require(admin, Permission::CreateContest)?;
+            let mut new = contest::new(title, category, event_start_at);
+            if upcoming {
+                new = new.upcoming();
+            }
+            ContestRepository::save(conn, &new)?;
+            AdminRepository::save(conn, &audit::record(admin, &new))?;
+            println!("Contest is created : {}", new.id().0);
+            Ok(*new.id())
+        }
+        Op::Import { file } => {
+            require(admin, Permission::CreateContest)?;
+            let imported = read_script(file.as_path())?.import()?;
+            ContestRepository::save(conn, &imported)?;
+            AdminRepository::save(conn, &audit::record(admin, &imported))?;
+            println!(
+                "Contest is imported : {} ({} polls)",
+                imported.contest.id().0,
+                imported.drafts.len()
+            );
+            Ok(*imported.contest.id())
+        }
+        Op::Open { contest_id } => {
+            require(admin, Permission::OpenContest)?;
+            let opened = query_contest::<BriefContest>(conn, &contest_id)?.open()?;
+            ContestRepository::save(conn, &opened)?;
+            AdminRepository::save(conn, &audit::record(admin, &opened))?;
+            println!("Contest is opened");
+            Ok(contest_id)
+        }
+        Op::AddPoll {
+            contest_id,
+            title,
+            duration_sec,
+            lock_before_close_sec,
+            choices,
+            multiple,
+            partial,
+            min,
+            max,
+            step,
+            tolerance,
+            closest,
+            ranking,
+            position,
+            kendall,
+            survey,
+            stake_multiplier,
+            answer,
+        } => {
+            require(admin, Permission::AddPoll)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let choices = choices
+                .into_iter()
+                .enumerate()
+                .map(|(idx, (name, color))| Choice { idx, name, color })
+                .collect::<Vec<_>>();
+            let answer = answer
+                .map(|answer| {
+                    let answer = ChoiceName(answer);
+                    if !choices.iter().any(|c| c.name == answer) {
+                        return Err(anyhow::anyhow!("Answer is not a part of the choices"));
+                    }
+                    let nonce = commitment::gen_nonce();
+                    Ok((commitment::commit(&answer, nonce.as_str()), nonce))
+                })
+                .transpose()?;
+            let (answer_commitment, nonce) = match answer {
+                Some((commitment, nonce)) => (Some(commitment), Some(nonce)),
+                None => (None, None),
+            };
+            // --max と --step は --min と一緒にしか指定できない
+            let numeric = match (min, max, step) {
+                (Some(min), Some(max), Some(step)) => Some(NumericSpec {
+                    min,
+                    max,
+                    step,
+                    tolerance,
+                }),
+                _ => None,
+            };
+            let kind = if numeric.is_some() {
+                PollKind::Numeric
+            } else if ranking.is_some() {
+                PollKind::Ranking
+            } else if multiple {
+                PollKind::Multiple
+            } else {
+                PollKind::Single
+            };
+            let scoring = if partial {
+                PollScoring::Partial
+            } else if position {
+                PollScoring::Position
+            } else if kendall {
+                PollScoring::Kendall
+            } else if closest {
+                PollScoring::Closest
+            } else if tolerance.is_some() {
+                PollScoring::Tolerance
+            } else {
+                PollScoring::Exact
+            };
+            let added = contest.add_poll(NewPoll {
+                title,
+                kind,
+                scoring,
+                duration: duration_sec.map(Duration::seconds),
+                lock_before_close: lock_before_close_sec.map(Duration::seconds),
+                choices,
+                numeric,
+                ranking_size: ranking,
+                is_scored: !survey,
+                stake_multiplier,
+                answer_commitment,
+            })?;
+            ContestRepository::save(conn, &added)?;
+            AdminRepository::save(conn, &audit::record(admin, &added))?;
+            println!("Poll is added : {}", added.poll.id().0);
+            if let Some(nonce) = nonce {
+                println!("Answer nonce : {}", nonce);
+                println!("Keep the nonce secret until the poll is resolved");
+            }
+            if duration_sec.is_some() {
+                println!("The poll is closed automatically only if the server is notified");
+            }
+            Ok(contest_id)
+        }
+        Op::PublishNext { contest_id } => {
+            require(admin, Permission::AddPoll)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let published =
+                ContestRepository::query_drafts(conn, &contest_id)?.publish_head(&contest)?;
+            ContestRepository::save(conn, &published)?;
+            AdminRepository::save(conn, &audit::record(admin, &published.added))?;
+            println!("Poll is published : {}", published.added.poll.id().0);
+            if published.added.poll.duration().is_some() {
+                println!("The poll is closed automatically only if the server is notified");
+            }
+            Ok(contest_id)
+        }
+        Op::ClosePoll { contest_id, force } => {
+            require(admin, Permission::ClosePoll)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let poll = contest
+                .current_poll()
+                .ok_or_else(|| anyhow::anyhow!("Contest has no poll"))?;
+            let closed = if force {
+                poll.clone().force_close()?
+            } else {
+                poll.clone().close()?
+            };
+            ContestRepository::save(conn, &closed)?;
+            AdminRepository::save(conn, &audit::record(admin, &closed).contest(&contest_id))?;
+            println!("Poll is closed");
+            Ok(contest_id)
+        }
+        Op::ReopenPoll {
+            contest_id,
+            duration_sec,
+        } => {
+            require(admin, Permission::ReopenPoll)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            if contest.status() != ContestStatus::Open {
+                return Err(anyhow::anyhow!("Contest is not open"));
+            }
+            let poll = contest
+                .current_poll()
+                .ok_or_else(|| anyhow::anyhow!("Contest has no poll"))?;
+            let reopened = poll.clone().reopen(duration_sec.map(Duration::seconds))?;
+            ContestRepository::save(conn, &reopened)?;
+            AdminRepository::save(conn, &audit::record(admin, &reopened).contest(&contest_id))?;
+            println!("Poll is reopened");
+            Ok(contest_id)
+        }
+        Op::ExtendPoll { contest_id, sec } => {
+            require(admin, Permission::ControlPollTimer)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let poll = contest
+                .current_poll()
+                .ok_or_else(|| anyhow::anyhow!("Contest has no poll"))?;
+            let extended = poll.clone().extend(Duration::seconds(sec))?;
+            ContestRepository::save(conn, &extended)?;
+            AdminRepository::save(conn, &audit::record(admin, &extended).contest(&contest_id))?;
+            println!(
+                "Poll is extended to {} sec",
+                extended.new_duration().num_seconds()
+            );
+            Ok(contest_id)
+        }
+        Op::PausePoll { contest_id } => {
+            require(admin, Permission::ControlPollTimer)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let poll = contest
+                .current_poll()
+                .ok_or_else(|| anyhow::anyhow!("Contest has no poll"))?;
+            let paused = poll.clone().pause()?;
+            ContestRepository::save(conn, &paused)?;
+            AdminRepository::save(conn, &audit::record(admin, &paused).contest(&contest_id))?;
+            println!("Poll is paused");
+            Ok(contest_id)
+        }
+        Op::ResumePoll { contest_id } => {
+            require(admin, Permission::ControlPollTimer)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let poll = contest
+                .current_poll()
+                .ok_or_else(|| anyhow::anyhow!("Contest has no poll"))?;
+            let resumed = poll.clone().resume()?;
+            ContestRepository::save(conn, &resumed)?;
+            AdminRepository::save(conn, &audit::record(admin, &resumed).contest(&contest_id))?;
+            println!("Poll is resumed");
+            Ok(contest_id)
+        }
+        Op::OpenMarket {
+            contest_id,
+            liquidity,
+            budget,
+        } => {
+            require(admin, Permission::OpenMarket)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let poll = contest
+                .current_poll()
+                .ok_or_else(|| anyhow::anyhow!("Contest has no poll"))?;
+            if ContestRepository::query_market(conn, poll.id())?.is_some() {
+                return Err(anyhow::anyhow!("Market is already opened"));
+            }
+            let opened = market::open(poll, liquidity, budget)?;
+            ContestRepository::save(conn, &opened)?;
+            AdminRepository::save(conn, &audit::record(admin, &opened).contest(&contest_id))?;
+            println!("Market is opened");
+            Ok(contest_id)
+        }
+        Op::ResolvePoll {
+            contest_id,
+            choices,
+            value,
+            nonce,
+        } => {
+            require(admin, Permission::ResolvePoll)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let poll = contest
+                .current_poll()
+                .ok_or_else(|| anyhow::anyhow!("Contest has no poll"))?;
+            let resolved = poll.clone().resolve(to_resolution(choices, value), nonce)?;
+            ContestRepository::save(conn, &resolved)?;
+            AdminRepository::save(conn, &audit::record(admin, &resolved).contest(&contest_id))?;
+            ContestRepository::save(conn, &resolved.settle_stakes()?)?;
+            println!("Poll is resolved");
+            Ok(contest_id)
+        }
+        Op::ReResolvePoll {
+            contest_id,
+            poll_id,
+            choices,
+            value,
+        } => {
+            require(admin, Permission::ReResolvePoll)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            if contest.status() == ContestStatus::Archived {
+                return Err(anyhow::anyhow!("Contest is already archived"));
+            }
+            let poll = contest
+                .polls()
+                .iter()
+                .find(|poll| *poll.id() == poll_id)
+                .ok_or_else(|| anyhow::anyhow!("Poll not found"))?;
+            let re_resolved = poll
+                .clone()
+                .re_resolve(admin, to_resolution(choices, value))?;
+            ContestRepository::save(conn, &re_resolved)?;
+            AdminRepository::save(
+                conn,
+                &audit::record(admin, &re_resolved).contest(&contest_id),
+            )?;
+            println!(
+                "Poll is re-resolved : {} -> {}",
+                re_resolved.previous(),
+                re_resolved.resolution().unwrap()
+            );
+            Ok(contest_id)
+        }
+        Op::VoidPoll { contest_id } => {
+            require(admin, Permission::VoidPoll)?;
+            let contest = query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let poll = contest
+                .current_poll()
+                .ok_or_else(|| anyhow::anyhow!("Contest has no poll"))?;
+            let voided = poll.clone().void()?;
+            ContestRepository::save(conn, &voided)?;
+            AdminRepository::save(conn, &audit::record(admin, &voided).contest(&contest_id))?;
+            ContestRepository::save(conn, &voided.settle_stakes()?)?;
+            println!("Poll is voided");
+            Ok(contest_id)
+        }
+        Op::Close { contest_id } => {
+            require(admin, Permission::CloseContest)?;
+            let closed = query_contest::<DetailedContest<BriefPoll>>(conn, &contest_id)?.close()?;
+            ContestRepository::save(conn, &closed)?;
+            AdminRepository::save(conn, &audit::record(admin, &closed))?;
+            println!("Contest is closed");
+            Ok(contest_id)
+        }
+        Op::Archive { contest_id } => {
+            require(admin, Permission::ArchiveContest)?;
+            let archived = query_contest::<BriefContest>(conn, &contest_id)?.archive()?;
+            ContestRepository::save(conn, &archived)?;
+            AdminRepository::save(conn, &audit::record(admin, &archived))?;
+            println!("Contest is archived");
+            Ok(contest_id)
+        }
+        Op::Export {
+            contest_id,
+            format,
+            output,
+        } => {
+            require(admin, Permission::ExportContest)?;
+            let export = ContestRepository::query_export(conn, &contest_id)?
+                .ok_or_else(|| anyhow::anyhow!("Contest {} is not found", contest_id.0))?;
+            let content = match format.unwrap_or_default() {
+                Format::Csv => export.to_csv()?,
+                Format::Json => serde_json::to_string_pretty(&export)?,
+            };
+            match output {
+                Some(path) => std::fs::write(path, content)?,
+                None => print!("{}", content),
+            }
+            Ok(contest_id)
+        }
+    }
+}
+
+/// 拡張子が .json のファイルはJSONとして、それ以外はYAMLとして読み込む
+fn read_script(path: &Path) -> anyhow::Result<Script> {
+    let content = std::fs::read_to_string(path)?;
+    let script = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(content.as_str())?,
+        _ => serde_yaml::from_str(content.as_str())?,
+    };
+    Ok(script)
+}
+
+pub(crate) fn query_admin(conn: &Connection, email: &str) -> anyhow::Result<BriefAdmin> {
+    let admin = AdminRepository::query_by_email::<BriefAdmin>(conn, email)?
+        .ok_or_else(|| anyhow::anyhow!("Admin {} is not found", email))?;
+    if admin.is_disabled() {
+        return Err(anyhow::anyhow!("Admin {} is disabled", email));
+    }
+    Ok(admin)
+}
+
+pub(crate) fn query_contest<C>(conn: &Connection, contest_id: &ContestId) -> anyhow::Result<C>
+where
+    C: contest::Queryable,
+{
+    ContestRepository::query_by_id::<C>(conn, contest_id)?
+        .ok_or_else(|| anyhow::anyhow!("Contest {} is not found", contest_id.0))
+}
+
+pub(crate) fn require(admin: &BriefAdmin, permission: Permission) -> anyhow::Result<()> {
+    if !admin.has_permission(permission) {
+        return Err(anyhow::anyhow!(
+            "Admin {} is not permitted to {:?}",
+            admin.email(),
+            permission
+        ));
+    }
+    Ok(())
+}
+
+async fn notify(server_url: &str, contest_id: &ContestId) -> anyhow::Result<()> {
+    let access_token = std::env::var("ADMIN_ACCESS_TOKEN")
+        .map_err(|_| anyhow::anyhow!("ADMIN_ACCESS_TOKEN is not specified"))?;
+    let uri = format!(
+        "{}/contests/{}/sync",
+        server_url.trim_end_matches('/'),
+        contest_id.0
+    );
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header("Authorization", format!("Bearer {}", access_token))
+        .body(Body::empty())?;
+
+    let client = Client::builder().build::<_, Body>(HttpsConnector::new());
+    let res = client.request(req).await?;
+    if !res.status().is_success() {
+        return Err(anyhow::anyhow!("Server responded {}", res.status()));
+    }
+    println!("Server is notified");
+    Ok(())
+}
+
+/// `--value` が指定されていれば数値、そうでなければ選択肢の正解
+fn to_resolution(choices: Vec<String>, value: Option<f64>) -> Resolution {
+    match value {
+        Some(value) => Resolution::Value(value),
+        None => Resolution::Choices(choices.into_iter().map(ChoiceName).collect()),
+    }
+}
+
+fn parse_choice(s: &str) -> Result<(ChoiceName, ChoiceColor), String> {
+    let mut split = s.splitn(2, ':');
+    match (split.next(), split.next()) {
+        (Some(name), Some(color)) if !name.is_empty() && !color.is_empty() => {
+            Ok((ChoiceName(name.to_string()), ChoiceColor(color.to_string())))
+        }
+        _ => Err(format!("Choice must be \"name:color\" : {}", s)),
+    }
+}
@@ -0,0 +1,72 @@
+use crop_domain::{
+    account::AccountId,
+    reward::draw::{self, AccountScore},
+};
+use data_encoding::HEXLOWER;
+use serde::Deserialize;
+use std::path::PathBuf;
+use structopt::StructOpt;
+use uuid::Uuid;
+
+/// 賞品の抽選を検証する。DBには接続しない。
+#[derive(StructOpt, Debug)]
+pub enum Cmd {
+    /// 公開されたseedとスコアから抽選をやり直し、当選者を表示する
+    Verify {
+        /// 当選者を選んだ後に公開されたseed（16進数）
+        #[structopt(long)]
+        seed: String,
+        /// Contestが終了する前に公開されたseedのハッシュ値（16進数）
+        #[structopt(long)]
+        seed_hash: String,
+        /// 賞品の数
+        #[structopt(long)]
+        quantity: usize,
+        /// 各アカウントのスコアを含むJSONファイル。
+        /// 抽選の検証APIのレスポンスをそのまま使える。
+        /// Contestの書き出しも使えるが、抽選後に正解が訂正された場合は結果が一致しない。
+        #[structopt(long, parse(from_os_str))]
+        scores: PathBuf,
+        /// 公開された当選者を当選順に指定すると、やり直した結果と比較する
+        #[structopt(long = "winner")]
+        winners: Vec<Uuid>,
+    },
+}
+
+/// `accounts` 以外のフィールドは無視する
+#[derive(Deserialize)]
+struct Scores {
+    accounts: Vec<AccountScore>,
+}
+
+pub fn run(cmd: Cmd) -> anyhow::Result<()> {
+    match cmd {
+        Cmd::Verify {
+            seed,
+            seed_hash,
+            quantity,
+            scores,
+            winners,
+        } => {
+            let seed = HEXLOWER.decode(seed.to_lowercase().as_bytes())?;
+            let seed_hash = HEXLOWER.decode(seed_hash.to_lowercase().as_bytes())?;
+            let content = std::fs::read_to_string(scores)?;
+            let scores = serde_json::from_str::<Scores>(content.as_str())?.accounts;
+
+            let recomputed = draw::verify(seed.as_slice(), seed_hash.as_slice(), &scores, quantity)
+                .ok_or_else(|| anyhow::anyhow!("Seed does not match the seed hash"))?;
+            for (i, account_id) in recomputed.iter().enumerate() {
+                println!("{} : {}", i + 1, account_id.0);
+            }
+
+            if !winners.is_empty() {
+                let winners = winners.into_iter().map(AccountId).collect::<Vec<_>>();
+                if winners != recomputed {
+                    return Err(anyhow::anyhow!("Winners do not match the lottery"));
+                }
+                println!("Winners match the lottery");
+            }
+            Ok(())
+        }
+    }
+}
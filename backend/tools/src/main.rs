@@ -1,22 +1,55 @@
 use crop_domain::admin::service::auth;
 use structopt::StructOpt;
 
-fn main() {
+mod admin;
+mod contest;
+mod lottery;
+mod reward;
+
+#[tokio::main]
+async fn main() {
     let args = Args::from_args();
 
-    match args {
-        Args::Credential { pass } => {
+    let res = match args {
+        Args::Credential => {
+            let pass = read_password("Password: ");
             let cred = auth::derive_credentials(pass.as_str());
             println!("salt : {:?}", cred.salt_hex());
             println!("cred : {:?}", cred.cred_hex());
+            Ok(())
         }
+        Args::Admin(cmd) => admin::run(cmd).await,
+        Args::Contest(cmd) => contest::run(cmd).await,
+        Args::Reward(cmd) => reward::run(cmd).await,
+        Args::Lottery(cmd) => lottery::run(cmd),
+    };
+
+    if let Err(e) = res {
+        eprintln!("Error : {}", e);
+        std::process::exit(1);
     }
 }
 
 #[derive(StructOpt, Debug)]
 enum Args {
-    Credential {
-        #[structopt(short, long)]
-        pass: String,
-    },
+    /// adminsテーブルに直接書き込むためのsalt/credを生成する
+    Credential,
+    /// adminsテーブルを操作する
+    Admin(admin::Cmd),
+    /// Contestを操作する
+    Contest(contest::Cmd),
+    /// Contestの賞品を操作する
+    Reward(reward::Cmd),
+    /// 賞品の抽選を検証する
+    Lottery(lottery::Cmd),
+}
+
+/// パスワードをargvで受け取ると履歴などに残るので、
+/// 端末から対話的に読み込む
+fn read_password(prompt: &str) -> String {
+    rpassword::read_password_from_tty(Some(prompt)).expect("Failed to read password")
+}
+
+fn get_env_var_or_panic(key: &'static str) -> String {
+    std::env::var(key).unwrap_or_else(|_| panic!("{} is not specified", key))
 }
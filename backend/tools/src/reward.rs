@@ -0,0 +1,178 @@
+use crate::contest::{query_admin, query_contest, require};
+use crate::get_env_var_or_panic;
+use crop_domain::{
+    admin::{
+        audit,
+        model::{BriefAdmin, Permission},
+        repository::AdminRepository,
+    },
+    contest::{poll::DetailedPoll, BriefContest, ContestId, DetailedContest},
+    reward::{self, draw, notifier::LocalNotifier, Reward, RewardId, RewardRepository, Selection},
+};
+use crop_infra::pg::{self, Connection, Pool};
+use data_encoding::HEXLOWER;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Contestの賞品を登録し、当選者にギフトコードを送付する
+#[derive(StructOpt, Debug)]
+pub struct Cmd {
+    /// 操作を行うAdminのemail。権限の確認と監査ログの記録に使う。
+    #[structopt(long)]
+    admin: String,
+    #[structopt(subcommand)]
+    op: Op,
+}
+
+#[derive(StructOpt, Debug)]
+enum Op {
+    /// 賞品を登録する
+    Create {
+        contest_id: ContestId,
+        #[structopt(long)]
+        name: String,
+        /// 当選者の数
+        #[structopt(long)]
+        quantity: usize,
+        /// top（スコアの上位から）または lottery（スコアで重み付けした抽選）
+        #[structopt(long, parse(try_from_str = parse_selection))]
+        selection: Selection,
+    },
+    /// ギフトコードを1行に1つ書いたファイルから、コードを登録する
+    AddCodes {
+        reward_id: RewardId,
+        #[structopt(long, parse(from_os_str))]
+        file: PathBuf,
+    },
+    /// Contestの賞品と当選者の一覧を表示する
+    List { contest_id: ContestId },
+    /// 抽選に使うseedを決め、公開するハッシュ値を表示する。
+    /// Contestが終了する前に行う必要がある。
+    CommitSeed { reward_id: RewardId },
+    /// 終了したContestのスコアから当選者を選ぶ
+    Draw { reward_id: RewardId },
+    /// まだギフトコードを受け取っていない当選者に送付する
+    Deliver {
+        reward_id: RewardId,
+        /// 送付する内容を書き出すファイル
+        #[structopt(long, parse(from_os_str))]
+        outbox: PathBuf,
+    },
+}
+
+pub async fn run(cmd: Cmd) -> anyhow::Result<()> {
+    let pool = Pool::new(get_env_var_or_panic("DATABASE_URL"));
+    let admin_email = cmd.admin;
+    let op = cmd.op;
+
+    pool.with_conn(move |conn| {
+        let admin = query_admin(&conn, admin_email.as_str())?;
+        apply(&conn, &admin, op)
+    })
+    .await?
+}
+
+fn apply(conn: &Connection, admin: &BriefAdmin, op: Op) -> anyhow::Result<()> {
+    match op {
+        Op::Create {
+            contest_id,
+            name,
+            quantity,
+            selection,
+        } => {
+            require(admin, Permission::ManageRewards)?;
+            // 存在しないContestに賞品を登録しないよう確認しておく
+            query_contest::<DetailedContest<DetailedPoll>>(conn, &contest_id)?;
+            let new = reward::new(contest_id, name, quantity, selection)?;
+            pg::transaction(conn, || {
+                RewardRepository::save(conn, &new)?;
+                AdminRepository::save(conn, &audit::record(admin, &new))
+            })?;
+            println!("Reward is created : {}", new.id().0);
+        }
+        Op::AddCodes { reward_id, file } => {
+            require(admin, Permission::ManageRewards)?;
+            let content = std::fs::read_to_string(file)?;
+            let codes = content.lines().map(str::to_string).collect();
+            let added = query_reward(conn, &reward_id)?.add_codes(codes)?;
+            RewardRepository::save(conn, &added)?;
+            println!("{} codes are added", added.codes().len());
+        }
+        Op::List { contest_id } => {
+            require(admin, Permission::ManageRewards)?;
+            let rewards = RewardRepository::query_by_contest_id(conn, &contest_id)?;
+            println!("{}", serde_json::to_string_pretty(&rewards)?);
+        }
+        Op::CommitSeed { reward_id } => {
+            require(admin, Permission::ManageRewards)?;
+            let reward = query_reward(conn, &reward_id)?;
+            let contest = query_contest::<BriefContest>(conn, reward.contest_id())?;
+            let committed = reward.commit_seed(&contest, draw::gen_seed())?;
+            pg::transaction(conn, || {
+                RewardRepository::save(conn, &committed)?;
+                AdminRepository::save(conn, &audit::record(admin, &committed))
+            })?;
+            println!("Seed hash : {}", HEXLOWER.encode(committed.seed_hash()));
+        }
+        Op::Draw { reward_id } => {
+            require(admin, Permission::ManageRewards)?;
+            let reward = query_reward(conn, &reward_id)?;
+            let contest =
+                query_contest::<DetailedContest<DetailedPoll>>(conn, reward.contest_id())?;
+            let drawn = reward.draw(&contest)?;
+            pg::transaction(conn, || {
+                RewardRepository::save(conn, &drawn)?;
+                AdminRepository::save(conn, &audit::record(admin, &drawn))
+            })?;
+            if let Some(seed) = drawn.seed() {
+                println!("Seed : {}", HEXLOWER.encode(seed));
+            }
+            for (i, account_id) in drawn.winners().enumerate() {
+                println!("{} : {}", i + 1, account_id.0);
+            }
+        }
+        Op::Deliver { reward_id, outbox } => {
+            require(admin, Permission::ManageRewards)?;
+            let reward = query_reward(conn, &reward_id)?;
+            let notifier = LocalNotifier::new(outbox);
+            let account_ids = reward
+                .undelivered_winners()
+                .map(|w| *w.account_id())
+                .collect::<Vec<_>>();
+            let recipients = RewardRepository::query_recipients(conn, account_ids.as_slice())?;
+
+            // 1人の送付に失敗しても、残りの当選者への送付は続ける
+            let mut num_failed = 0;
+            for winner in reward.undelivered_winners() {
+                let recipient = recipients.get(winner.account_id()).ok_or_else(|| {
+                    anyhow::anyhow!("Account {} is not found", winner.account_id().0)
+                })?;
+                let delivered = reward.deliver(winner, recipient, &notifier)?;
+                RewardRepository::save(conn, &delivered)?;
+                if let Some(e) = delivered.error() {
+                    num_failed += 1;
+                    eprintln!("Failed to deliver to {} : {}", winner.account_id().0, e);
+                }
+            }
+            println!(
+                "{} codes are delivered, {} failed",
+                account_ids.len() - num_failed,
+                num_failed
+            );
+        }
+    }
+    Ok(())
+}
+
+fn query_reward(conn: &Connection, reward_id: &RewardId) -> anyhow::Result<Reward> {
+    RewardRepository::query_by_id(conn, reward_id)?
+        .ok_or_else(|| anyhow::anyhow!("Reward {} is not found", reward_id.0))
+}
+
+fn parse_selection(s: &str) -> Result<Selection, String> {
+    match s {
+        "top" => Ok(Selection::Top),
+        "lottery" => Ok(Selection::Lottery),
+        _ => Err(format!("Selection must be \"top\" or \"lottery\" : {}", s)),
+    }
+}
@@ -25,16 +25,28 @@ export const ResolvePoll: React.FC = () => {
       const confirmed = window.confirm(
         `「${poll.title}」を「${choice}」でResolveします。よろしいですか？`
       );
-      if (confirmed) {
-        pollApi
-          .resolve({
-            contestId: contest.id,
-            pollId: poll.id,
-            choice,
-            accessToken
-          })
-          .then(res => alert("Resolveしました"));
+      if (!confirmed) {
+        return;
       }
+      // 正解のコミットメントがある場合は、Poll作成時のnonceが必要
+      let nonce: string | undefined;
+      if (poll.answer_commitment) {
+        nonce =
+          window.prompt("Poll作成時に発行されたnonceを入力してください") ||
+          undefined;
+        if (!nonce) {
+          return;
+        }
+      }
+      pollApi
+        .resolve({
+          contestId: contest.id,
+          pollId: poll.id,
+          choice,
+          nonce,
+          accessToken
+        })
+        .then(res => alert("Resolveしました"));
     }
   };
 
@@ -48,6 +60,7 @@ export const ResolvePoll: React.FC = () => {
           .find(
             poll =>
               poll.status === "Closed" &&
+              poll.is_scored &&
               poll.resolved_choice === undefined
           );
           if (poll) {
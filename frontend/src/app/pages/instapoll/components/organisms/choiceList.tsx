@@ -49,7 +49,9 @@ export const ChoiceList: React.FC<Props> = ({ poll, selected, onSelected }) => {
                 poll.resolved_choice ? poll.resolved_choice === name : undefined
               }
               voteRate={Math.floor(
-                (poll.stats!.votePerChoice[name] / poll.stats!.totalVotes) * 100
+                ((poll.stats!.votePerChoice?.[name] ?? 0) /
+                  poll.stats!.totalVotes) *
+                  100
               )}
             />
           ))}
@@ -5,13 +5,32 @@ import {
   WhiteBaseColor,
   Alto
 } from "app/components/color";
-import { Timer as TimerModel } from "model/poll";
+import { LockTimer, Timer as TimerModel } from "model/poll";
 
 interface Props {
   timer?: TimerModel;
+  lock?: LockTimer;
 }
 
-export const Timer: React.FC<Props> = ({ timer }) => {
+export const Timer: React.FC<Props> = ({ timer, lock }) => {
+  // 回答の締切と投票の締切は別の時刻なので、両方を並べて表示する
+  return (
+    <>
+      {lock !== undefined && <LockTimerView lock={lock} />}
+      <CloseTimerView timer={timer} />
+    </>
+  );
+};
+
+const LockTimerView: React.FC<{ lock: LockTimer }> = ({ lock }) => {
+  if (lock === "locked") {
+    return <Container>回答締切<TimeOut>確定</TimeOut></Container>;
+  } else {
+    return <Container>回答締切<CountDownTimer>{formatTime(lock)}</CountDownTimer></Container>;
+  }
+};
+
+const CloseTimerView: React.FC<{ timer?: TimerModel }> = ({ timer }) => {
   if ( timer === undefined) {
     return <Container>投票時間<TimeOut>-</TimeOut></Container>;
   }
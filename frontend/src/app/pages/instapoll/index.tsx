@@ -21,7 +21,7 @@ export const InstapollPage: React.FC<Props> = ({ contestId }) => {
   const [contest, setContest] = React.useState<Contest | undefined>();
   const [ws, setWs] = React.useState<WebSocket | undefined>();
   const [state, dispatch] = React.useReducer(reducer, initialState);
-  const { poll, comments, timer } = state;
+  const { poll, comments, timer, lockTimer } = state;
 
   React.useEffect(() => {
     if (!accessToken) {
@@ -98,6 +98,7 @@ export const InstapollPage: React.FC<Props> = ({ contestId }) => {
         poll={poll}
         comments={comments}
         timer={timer}
+        lockTimer={lockTimer}
         contest={contest}
         sendComment={sendComment}
         updateMyChoice={updateMyChoice}
@@ -116,6 +117,9 @@ const testpoll = {
   created_at: moment(),
   duration_sec: 30,
   status: "Open" as const,
+  kind: "Single" as const,
+  scoring: "Exact" as const,
+  is_scored: true,
   choices: [
     { name: "Lebron", color: "#4583e4", idx: 0 },
     { name: "Lebron青年期", color: "#4583e4", idx: 1 },
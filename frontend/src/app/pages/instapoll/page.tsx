@@ -8,7 +8,7 @@ import {
   WhiteBaseColor,
   BlackColor
 } from "app/components/color";
-import { Poll, Comment, Timer } from "model/poll";
+import { Poll, Comment, Timer, LockTimer } from "model/poll";
 import { Contest } from "model/contest";
 
 import { Timer as TimerComponent } from "./components/organisms/timer";
@@ -24,6 +24,7 @@ interface Props {
   poll?: Poll;
   comments: (Comment & { color: string })[];
   timer: Timer;
+  lockTimer?: LockTimer;
   contest: Contest;
   sendComment: (comment: string) => void;
   updateMyChoice: (choice: string) => void;
@@ -33,6 +34,7 @@ export const Page: React.FC<Props> = ({
   poll,
   comments,
   timer,
+  lockTimer,
   contest,
   sendComment,
   updateMyChoice
@@ -89,7 +91,7 @@ pollがresolve && 不正解 => WrongModal
         {isCorrect !== undefined ? <ResultModal isCorrect={isCorrect} /> : null}
         <Header>
           <Logo />
-          <TimerComponent timer={timer} />
+          <TimerComponent timer={timer} lock={lockTimer} />
           {/* <Score numer={2} denom={3} /> */}
         </Header>
         <PollCard>
@@ -1,12 +1,13 @@
 import produce from "immer";
 import { Moment } from "moment";
 
-import { Comment, Poll, Timer } from "model/poll";
+import { Comment, LockTimer, Poll, Timer } from "model/poll";
 
 export type State = {
   poll?: Poll;
   comments: (Comment & { color: string })[];
   timer?: Timer;
+  lockTimer?: LockTimer;
 };
 
 export const initialState: State = {
@@ -46,16 +47,30 @@ export const reducer = (state: State, action: Action): State => {
     switch (action.type) {
       case "tick":
         if (state.poll) {
+          // 一時停止中は、停止した時点の残り時間を表示し続ける
+          const now = state.poll.paused_at || action.time;
           state.timer =
             state.poll.created_at.unix() +
             state.poll.duration_sec -
-            action.time.unix();
-          if (state.timer < 0) {
+            now.unix();
+          if (state.timer < 0 || state.poll.status !== "Open") {
             state.timer = "closed";
           }
+          if (state.poll.locks_at && state.timer !== "closed") {
+            state.lockTimer = state.poll.locks_at.unix() - now.unix();
+            if (state.lockTimer <= 0) {
+              state.lockTimer = "locked";
+            }
+          } else {
+            state.lockTimer = undefined;
+          }
         }
         break;
       case "updatePoll":
+        if (state.poll && action.poll.idx < state.poll.idx) {
+          // 過去のPollの正解の訂正は、表示中のPollを置き換えない
+          break;
+        }
         if (state.poll?.id !== action.poll.id) {
           // 新しいPollのときはCommentをクリアする
           state.comments = [];
@@ -1,7 +1,19 @@
 import * as D from "@mojotech/json-type-validation";
 import moment, { Moment } from "moment";
 
+import {
+  AnswerCommitment,
+  NumericSpec,
+  PollKind,
+  PollScoring
+} from "model/poll";
 import * as http from "./";
+import {
+  AnswerCommitmentDecoder,
+  NumericSpecDecoder,
+  PollKindDecoder,
+  PollScoringDecoder
+} from "./poll";
 
 export const getList = async (): Promise<GetListRes> =>
   http.get({ path: "/contests", decoder: GetListResDecoder });
@@ -44,17 +56,28 @@ interface GetRes {
   event_start_at?: Moment;
   polls: {
     id: string;
-    status: "Open" | "Closed";
+    status: "Open" | "Closed" | "Voided";
     title: string;
+    kind: PollKind;
+    scoring: PollScoring;
+    is_scored: boolean;
+    stake_multiplier?: number;
     idx: number;
     created_at: Moment;
     duration_sec: number;
+    lock_before_close_sec?: number;
     choices: {
       name: string;
       color: string;
       idx: number;
     }[];
     resolved_choice?: string;
+    resolved_choices?: string[];
+    ranking_size?: number;
+    numeric?: NumericSpec;
+    resolved_value?: number;
+    answer_commitment?: AnswerCommitment;
+    paused_at?: Moment;
   }[];
 }
 
@@ -74,12 +97,18 @@ const GetResDecoder: D.Decoder<GetRes> = D.object({
       id: D.string(),
       status: D.union(
         D.constant<"Open">("Open"),
-        D.constant<"Closed">("Closed")
+        D.constant<"Closed">("Closed"),
+        D.constant<"Voided">("Voided")
       ),
       title: D.string(),
+      kind: PollKindDecoder,
+      scoring: PollScoringDecoder,
+      is_scored: D.boolean(),
+      stake_multiplier: D.optional(D.number()),
       idx: D.number(),
       created_at: D.string().map(s => moment(s)),
       duration_sec: D.number(),
+      lock_before_close_sec: D.optional(D.number()),
       choices: D.array(
         D.object({
           name: D.string(),
@@ -87,7 +116,13 @@ const GetResDecoder: D.Decoder<GetRes> = D.object({
           idx: D.number()
         })
       ),
-      resolved_choice: D.optional(D.string())
+      resolved_choice: D.optional(D.string()),
+      resolved_choices: D.optional(D.array(D.string())),
+      ranking_size: D.optional(D.number()),
+      numeric: D.optional(NumericSpecDecoder),
+      resolved_value: D.optional(D.number()),
+      answer_commitment: D.optional(AnswerCommitmentDecoder),
+      paused_at: D.optional(D.string().map(s => moment(s)))
     })
   )
 });
@@ -0,0 +1,70 @@
+import * as D from "@mojotech/json-type-validation";
+
+import * as http from "./";
+
+export const get = async (arg: {
+  contestId: string;
+  pollId: string;
+  accessToken: string;
+}): Promise<GetRes> =>
+  http.get({
+    path: `/contests/${arg.contestId}/polls/${arg.pollId}/market`,
+    accessToken: arg.accessToken,
+    decoder: GetResDecoder
+  });
+
+export interface Position {
+  // 支払ったポイントの合計
+  spent: number;
+  // 選択肢ごとの保有株数
+  shares: Record<string, number>;
+}
+
+interface GetRes {
+  liquidity: number;
+  budget: number;
+  // 選択肢ごとの価格。その選択肢が正解になる確率の推定値で、合計は1になる
+  prices: Record<string, number>;
+  position: Position;
+  // PollがResolveまたは中止された後の払い戻し
+  payout?: number;
+}
+
+export const PositionDecoder: D.Decoder<Position> = D.object({
+  spent: D.number(),
+  shares: D.dict(D.number())
+});
+
+const GetResDecoder: D.Decoder<GetRes> = D.object({
+  liquidity: D.number(),
+  budget: D.number(),
+  prices: D.dict(D.number()),
+  position: PositionDecoder,
+  payout: D.optional(D.number())
+});
+
+// 選択肢の株を `amount` ポイント分買う。ポイントは整数で、残高から引き落とされる。
+// 他の取引が先に成立していた場合は失敗するので、価格を確認してやり直す。
+export const postTrade = async (arg: {
+  contestId: string;
+  pollId: string;
+  choice: string;
+  amount: number;
+  accessToken: string;
+}): Promise<PostTradeRes> =>
+  http.post({
+    path: `/contests/${arg.contestId}/polls/${arg.pollId}/market/trades`,
+    body: { choice: arg.choice, amount: arg.amount },
+    accessToken: arg.accessToken,
+    decoder: PostTradeResDecoder
+  });
+
+interface PostTradeRes {
+  shares: number;
+  position: Position;
+}
+
+const PostTradeResDecoder: D.Decoder<PostTradeRes> = D.object({
+  shares: D.number(),
+  position: PositionDecoder
+});
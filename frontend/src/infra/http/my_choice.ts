@@ -2,15 +2,26 @@ import * as D from "@mojotech/json-type-validation";
 
 import * as http from "./";
 
+// 複数選択のPollでは、選んだ選択肢を全て配列で渡す。
+// 順位を予想するPollでは、上位から順に並べた配列を渡す。
+// 数値で答えるPollでは、回答の値を渡す。
+// 賭けられるPollでは、選択肢の回答にポイントを賭けられる。
+// 賭けるポイントも回答と一緒に置き換わるので、省略すると賭けていたポイントが残高に戻る。
 export const put = async (arg: {
   contestId: string;
   pollId: string;
-  choice: string;
+  choice: string | string[] | number;
+  stake?: number;
   accessToken: string;
 }): Promise<void> =>
   http.put({
     path: `/contests/${arg.contestId}/polls/${arg.pollId}/my_choice`,
-    body: { choice: arg.choice },
+    body:
+      typeof arg.choice === "number"
+        ? { value: arg.choice }
+        : Array.isArray(arg.choice)
+        ? { choices: arg.choice, stake: arg.stake }
+        : { choice: arg.choice, stake: arg.stake },
     accessToken: arg.accessToken,
     decoder: D.anyJson()
   });
@@ -1,6 +1,13 @@
 import * as D from "@mojotech/json-type-validation";
 import moment, { Moment } from "moment";
 
+import {
+  AnswerCommitment,
+  HistogramBin,
+  NumericSpec,
+  PollKind,
+  PollScoring
+} from "model/poll";
 import * as http from "./";
 
 export const get = async (): Promise<GetRes> =>
@@ -10,7 +17,7 @@ interface GetRes {
   id: string;
   idx: number;
   title: string;
-  status: "Open" | "Closed";
+  status: "Open" | "Closed" | "Voided";
   created_at: Moment;
   duration_sec: number;
   choices: {
@@ -21,15 +28,30 @@ interface GetRes {
   resolved?: string;
   stats?: {
     totalVotes: number;
-    votePerChoice: Record<string, number>;
+    // 選択肢で答えるPollの、各選択肢の得票数
+    votePerChoice?: Record<string, number>;
+    // 順位を予想するPollの、各選択肢が1位から順に何票ずつ得たか
+    votePerRank?: Record<string, number[]>;
+    // 数値で答えるPollの、回答の分布
+    histogram?: HistogramBin[];
   };
 }
 
+export const HistogramBinDecoder: D.Decoder<HistogramBin> = D.object({
+  lower: D.number(),
+  upper: D.number(),
+  count: D.number()
+});
+
 const GetResDecoder: D.Decoder<GetRes> = D.object({
   id: D.string(),
   idx: D.number(),
   title: D.string(),
-  status: D.union(D.constant<"Open">("Open"), D.constant<"Closed">("Closed")),
+  status: D.union(
+    D.constant<"Open">("Open"),
+    D.constant<"Closed">("Closed"),
+    D.constant<"Voided">("Voided")
+  ),
   created_at: D.string().map(s => moment(s)),
   duration_sec: D.number(),
   choices: D.array(
@@ -43,7 +65,9 @@ const GetResDecoder: D.Decoder<GetRes> = D.object({
   stats: D.optional(
     D.object({
       totalVotes: D.number(),
-      votePerChoice: D.dict(D.number())
+      votePerChoice: D.optional(D.dict(D.number())),
+      votePerRank: D.optional(D.dict(D.array(D.number()))),
+      histogram: D.optional(D.array(HistogramBinDecoder))
     })
   )
 });
@@ -51,30 +75,142 @@ const GetResDecoder: D.Decoder<GetRes> = D.object({
 export const post = async (args: {
   contestId: string;
   title: string;
+  kind?: PollKind;
+  scoring?: PollScoring;
   durationSec: number;
+  // 締め切りの何秒前から回答を変更できなくするか
+  lockBeforeCloseSec?: number;
   choices: { name: string; color: string; idx: number }[];
+  numeric?: NumericSpec;
+  rankingSize?: number;
+  // falseの場合は正解の無いアンケートにする
+  isScored?: boolean;
+  // 指定した場合は、回答にポイントを賭けられるようにする
+  stakeMultiplier?: number;
   accessToken: string;
 }): Promise<string> =>
   http.post({
     path: `/contests/${args.contestId}/polls`,
     body: {
       title: args.title,
+      kind: args.kind,
+      scoring: args.scoring,
       duration_sec: args.durationSec,
-      choices: args.choices
+      lock_before_close_sec: args.lockBeforeCloseSec,
+      choices: args.choices,
+      numeric: args.numeric,
+      ranking_size: args.rankingSize,
+      is_scored: args.isScored,
+      stake_multiplier: args.stakeMultiplier
     },
     accessToken: args.accessToken,
     decoder: D.string()
   });
 
+export const PollKindDecoder: D.Decoder<PollKind> = D.union(
+  D.constant<"Single">("Single"),
+  D.constant<"Multiple">("Multiple"),
+  D.constant<"Numeric">("Numeric"),
+  D.constant<"Ranking">("Ranking")
+);
+
+export const PollScoringDecoder: D.Decoder<PollScoring> = D.union(
+  D.constant<"Exact">("Exact"),
+  D.constant<"Partial">("Partial"),
+  D.constant<"Tolerance">("Tolerance"),
+  D.constant<"Closest">("Closest"),
+  D.constant<"Position">("Position"),
+  D.constant<"Kendall">("Kendall")
+);
+
+export const NumericSpecDecoder: D.Decoder<NumericSpec> = D.object({
+  min: D.number(),
+  max: D.number(),
+  step: D.number(),
+  tolerance: D.optional(D.number())
+});
+
+export const AnswerCommitmentDecoder: D.Decoder<AnswerCommitment> = D.object({
+  hash: D.string(),
+  nonce: D.optional(D.string())
+});
+
 export const resolve = async (arg: {
   contestId: string;
   pollId: string;
-  choice: string;
+  choice: string | string[] | number;
+  nonce?: string;
+  accessToken: string;
+}): Promise<void> =>
+  http.patch({
+    path: `/contests/${arg.contestId}/polls/${arg.pollId}`,
+    body: { ...resolvedChoiceBody(arg.choice), answer_nonce: arg.nonce },
+    accessToken: arg.accessToken,
+    decoder: D.anyJson()
+  });
+
+// 複数選択のPollでは、正解の選択肢を全て配列で渡す。
+// 順位を予想するPollでは、実際の上位から順に並べた配列を渡す。
+// 数値で答えるPollでは、正解の値を渡す。
+const resolvedChoiceBody = (choice: string | string[] | number) =>
+  typeof choice === "number"
+    ? { resolved_value: choice }
+    : Array.isArray(choice)
+    ? { resolved_choices: choice }
+    : { resolved_choice: choice };
+
+// Resolve済みのPollの正解を訂正する。Superadminのみ実行できる。
+export const reResolve = async (arg: {
+  contestId: string;
+  pollId: string;
+  choice: string | string[] | number;
+  accessToken: string;
+}): Promise<void> =>
+  http.patch({
+    path: `/contests/${arg.contestId}/polls/${arg.pollId}`,
+    body: { ...resolvedChoiceBody(arg.choice), correction: true },
+    accessToken: arg.accessToken,
+    decoder: D.anyJson()
+  });
+
+// 制限時間のあるPollのタイマーを操作する
+export const updateTimer = async (arg: {
+  contestId: string;
+  pollId: string;
+  op: { op: "extend"; sec: number } | { op: "pause" } | { op: "resume" };
+  accessToken: string;
+}): Promise<void> =>
+  http.patch({
+    path: `/contests/${arg.contestId}/polls/${arg.pollId}/timer`,
+    body: arg.op,
+    accessToken: arg.accessToken,
+    decoder: D.anyJson()
+  });
+
+// 制限時間の前でも、Pollを強制的にCloseする
+export const forceClose = async (arg: {
+  contestId: string;
+  pollId: string;
+  accessToken: string;
+}): Promise<void> =>
+  http.patch({
+    path: `/contests/${arg.contestId}/polls/${arg.pollId}`,
+    body: { status: "Closed", force: true },
+    accessToken: arg.accessToken,
+    decoder: D.anyJson()
+  });
+
+// 誤ってCloseしたPollをOpenに戻す。
+// durationSecを省略した場合は、Closeした時点の残り時間で再開する。
+export const reopen = async (arg: {
+  contestId: string;
+  pollId: string;
+  durationSec?: number;
   accessToken: string;
 }): Promise<void> =>
   http.patch({
     path: `/contests/${arg.contestId}/polls/${arg.pollId}`,
-    body: { resolved_choice: arg.choice },
+    body: { status: "Open", duration_sec: arg.durationSec },
     accessToken: arg.accessToken,
     decoder: D.anyJson()
   });
@@ -0,0 +1,58 @@
+import * as D from "@mojotech/json-type-validation";
+import moment, { Moment } from "moment";
+
+import * as http from "./";
+
+export const get = async (arg: { accessToken: string }): Promise<Wallet> =>
+  http.get({
+    path: "/accounts/me/wallet",
+    accessToken: arg.accessToken,
+    decoder: WalletDecoder
+  });
+
+export type TransactionKind =
+  | "Grant"
+  | "Adjustment"
+  | "Stake"
+  | "Payout"
+  | "Refund";
+
+export interface WalletEntry {
+  transaction_id: string;
+  kind: TransactionKind;
+  contest_id?: string;
+  poll_id?: string;
+  memo?: string;
+  // 残高の増減。減った場合は負の値
+  amount: number;
+  created_at: Moment;
+}
+
+export interface Wallet {
+  account_id: string;
+  balance: number;
+  // 新しいものから順に並ぶ
+  entries: WalletEntry[];
+}
+
+const WalletEntryDecoder: D.Decoder<WalletEntry> = D.object({
+  transaction_id: D.string(),
+  kind: D.union(
+    D.constant<"Grant">("Grant"),
+    D.constant<"Adjustment">("Adjustment"),
+    D.constant<"Stake">("Stake"),
+    D.constant<"Payout">("Payout"),
+    D.constant<"Refund">("Refund")
+  ),
+  contest_id: D.optional(D.string()),
+  poll_id: D.optional(D.string()),
+  memo: D.optional(D.string()),
+  amount: D.number(),
+  created_at: D.string().map(s => moment(s))
+});
+
+const WalletDecoder: D.Decoder<Wallet> = D.object({
+  account_id: D.string(),
+  balance: D.number(),
+  entries: D.array(WalletEntryDecoder)
+});
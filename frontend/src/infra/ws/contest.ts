@@ -2,7 +2,22 @@ import * as D from "@mojotech/json-type-validation";
 import moment, { Moment } from "moment";
 
 import * as ws from "infra/ws";
-import { Comment, Poll } from "model/poll";
+import {
+  AnswerCommitment,
+  Comment,
+  HistogramBin,
+  NumericSpec,
+  Poll,
+  PollKind,
+  PollScoring
+} from "model/poll";
+import {
+  AnswerCommitmentDecoder,
+  HistogramBinDecoder,
+  NumericSpecDecoder,
+  PollKindDecoder,
+  PollScoringDecoder
+} from "infra/http/poll";
 
 export interface Params {
   contestId: string;
@@ -10,6 +25,8 @@ export interface Params {
   onComment: (comment: Comment) => void;
   onPoll: (poll: Poll) => void;
   onClosed: (result: ClosedMsg) => void;
+  onMarket?: (market: MarketMsg) => void;
+  onCorrected?: (correction: CorrectedMsg) => void;
 }
 
 export const open = ({
@@ -17,7 +34,9 @@ export const open = ({
   accessToken,
   onComment,
   onPoll,
-  onClosed
+  onClosed,
+  onMarket,
+  onCorrected
 }: Params): WebSocket => {
   return ws.open({
     path: `/contests/${contestId}/${accessToken}`,
@@ -33,12 +52,18 @@ export const open = ({
         case "Closed":
           onClosed(msg);
           break;
+        case "Market":
+          onMarket?.(msg);
+          break;
+        case "Corrected":
+          onCorrected?.(msg);
+          break;
       }
     }
   });
 };
 
-type IncomingMsg = CommentMsg | PollMsg | ClosedMsg;
+type IncomingMsg = CommentMsg | PollMsg | ClosedMsg | MarketMsg | CorrectedMsg;
 
 interface CommentMsg {
   type: "Comment";
@@ -51,7 +76,11 @@ interface PollMsg {
   type: "Poll";
   id: string;
   title: string;
-  status: "Open" | "Closed";
+  status: "Open" | "Closed" | "Voided";
+  kind: PollKind;
+  scoring: PollScoring;
+  is_scored: boolean;
+  stake_multiplier?: number;
   idx: number;
   created_at: Moment,
   duration_sec: number;
@@ -61,9 +90,18 @@ interface PollMsg {
     idx: number;
   }[];
   resolved_choice?: string;
+  resolved_choices?: string[];
+  ranking_size?: number;
+  numeric?: NumericSpec;
+  resolved_value?: number;
+  answer_commitment?: AnswerCommitment;
+  paused_at?: Moment;
+  locks_at?: Moment;
   stats?: {
     totalVotes: number;
-    votePerChoice: Record<string, number>;
+    votePerChoice?: Record<string, number>;
+    votePerRank?: Record<string, number[]>;
+    histogram?: HistogramBin[];
   };
 }
 
@@ -73,6 +111,25 @@ interface ClosedMsg {
   account_score?: number;
 }
 
+interface MarketMsg {
+  type: "Market";
+  poll_id: string;
+  // 選択肢ごとの価格。その選択肢が正解になる確率の推定値
+  prices: Record<string, number>;
+}
+
+// 過去のPollの正解が訂正された。現在のPollは変わらない
+interface CorrectedMsg {
+  type: "Corrected";
+  poll_id: string;
+  idx: number;
+  resolved_choice?: string;
+  resolved_choices?: string[];
+  resolved_value?: number;
+  account_points?: number;
+  account_score?: number;
+}
+
 const CommentMsgDecoder: D.Decoder<CommentMsg> = D.object({
   type: D.constant<"Comment">("Comment"),
   account_name: D.string(),
@@ -84,7 +141,15 @@ const PollMsgDecoder: D.Decoder<PollMsg> = D.object({
   type: D.constant<"Poll">("Poll"),
   id: D.string(),
   title: D.string(),
-  status: D.union(D.constant<"Open">("Open"), D.constant<"Closed">("Closed")),
+  status: D.union(
+    D.constant<"Open">("Open"),
+    D.constant<"Closed">("Closed"),
+    D.constant<"Voided">("Voided")
+  ),
+  kind: PollKindDecoder,
+  scoring: PollScoringDecoder,
+  is_scored: D.boolean(),
+  stake_multiplier: D.optional(D.number()),
   idx: D.number(),
   created_at: D.string().map(s => moment(s)),
   duration_sec: D.number(),
@@ -96,10 +161,19 @@ const PollMsgDecoder: D.Decoder<PollMsg> = D.object({
     })
   ),
   resolved_choice: D.optional(D.string()),
+  resolved_choices: D.optional(D.array(D.string())),
+  ranking_size: D.optional(D.number()),
+  numeric: D.optional(NumericSpecDecoder),
+  resolved_value: D.optional(D.number()),
+  answer_commitment: D.optional(AnswerCommitmentDecoder),
+  paused_at: D.optional(D.string().map(s => moment(s))),
+  locks_at: D.optional(D.string().map(s => moment(s))),
   stats: D.optional(
     D.object({
       totalVotes: D.number(),
-      votePerChoice: D.dict(D.number())
+      votePerChoice: D.optional(D.dict(D.number())),
+      votePerRank: D.optional(D.dict(D.array(D.number()))),
+      histogram: D.optional(D.array(HistogramBinDecoder))
     })
   )
 });
@@ -110,8 +184,27 @@ const ClosedMsgDecoder: D.Decoder<ClosedMsg> = D.object({
   account_score: D.optional(D.number())
 });
 
+const MarketMsgDecoder: D.Decoder<MarketMsg> = D.object({
+  type: D.constant<"Market">("Market"),
+  poll_id: D.string(),
+  prices: D.dict(D.number())
+});
+
+const CorrectedMsgDecoder: D.Decoder<CorrectedMsg> = D.object({
+  type: D.constant<"Corrected">("Corrected"),
+  poll_id: D.string(),
+  idx: D.number(),
+  resolved_choice: D.optional(D.string()),
+  resolved_choices: D.optional(D.array(D.string())),
+  resolved_value: D.optional(D.number()),
+  account_points: D.optional(D.number()),
+  account_score: D.optional(D.number())
+});
+
 const IncomingMsgDecoder: D.Decoder<IncomingMsg> = D.union(
   CommentMsgDecoder,
   PollMsgDecoder,
-  ClosedMsgDecoder
+  ClosedMsgDecoder,
+  MarketMsgDecoder,
+  CorrectedMsgDecoder
 );
@@ -3,8 +3,15 @@ import { Moment } from "moment";
 export interface Poll {
   id: string;
   idx: number;
-  status: "Open" | "Closed";
+  status: "Open" | "Closed" | "Voided";
   title: string;
+  // 複数選択のPollでは、1つ以上の選択肢を選べる
+  kind: PollKind;
+  scoring: PollScoring;
+  // falseの場合は正解の無いアンケート。Resolveされず、Close後のstatsが結果になる
+  is_scored: boolean;
+  // 回答にポイントを賭けられるPollで、正解したときに払い戻す倍率
+  stake_multiplier?: number;
   choices: {
     name: string;
     color: string;
@@ -13,13 +20,66 @@ export interface Poll {
   created_at: Moment,
   duration_sec: number,
   resolved_choice?: string;
+  // 複数選択のPollの正解や、順位を予想するPollの実際の順位
+  resolved_choices?: string[];
+  // 順位を予想するPollで、何位までを予想するか
+  ranking_size?: number;
+  // 数値で答えるPollの、回答できる値の範囲
+  numeric?: NumericSpec;
+  // 数値で答えるPollの正解
+  resolved_value?: number;
+  answer_commitment?: AnswerCommitment;
+  // タイマーを一時停止した日時
+  paused_at?: Moment;
+  // 回答を変更できなくなる日時
+  locks_at?: Moment;
   stats?: {
     totalVotes: number;
-    votePerChoice: Record<string, number>;
+    // 選択肢で答えるPollの、各選択肢の得票数
+    votePerChoice?: Record<string, number>;
+    // 順位を予想するPollの、各選択肢が1位から順に何票ずつ得たか
+    votePerRank?: Record<string, number[]>;
+    // 数値で答えるPollの、回答の分布
+    histogram?: HistogramBin[];
   };
   selected?: string; // selected_choice
 }
 
+export type PollKind = "Single" | "Multiple" | "Numeric" | "Ranking";
+
+// 複数選択のPollや、数値・順位で答えるPollの採点方法。
+// Partialでは不正解の選択肢の数だけ減点した部分点を与える。
+// Toleranceでは正解との差がtolerance以下なら正解、Closestでは正解に近い順に点数を与える。
+// Positionでは順位が一致した割合、Kendallでは上下関係が一致した組の割合を点数にする。
+export type PollScoring =
+  | "Exact"
+  | "Partial"
+  | "Tolerance"
+  | "Closest"
+  | "Position"
+  | "Kendall";
+
+// min から max までの step 刻みの値で答える
+export interface NumericSpec {
+  min: number;
+  max: number;
+  step: number;
+  tolerance?: number;
+}
+
+// lower 以上 upper 以下の回答の数
+export interface HistogramBin {
+  lower: number;
+  upper: number;
+  count: number;
+}
+
+// 正解のコミットメント。nonceはResolveされた後に公開される。
+export interface AnswerCommitment {
+  hash: string;
+  nonce?: string;
+}
+
 export interface Comment {
   account_name: string;
   comment: string;
@@ -27,3 +87,5 @@ export interface Comment {
 }
 
 export type Timer = number | "closed";
+// 回答締切までの残り秒数。締切後は "locked"
+export type LockTimer = number | "locked";